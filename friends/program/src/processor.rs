@@ -84,7 +84,7 @@ impl Processor {
             if generated_request_key != *last_request_from_to_acc.key {
                 return Err(ProgramError::InvalidSeeds);
             }
-            mem::swap(request_from_to, last_request_from_to);
+            *request_from_to = mem::take(last_request_from_to);
             request_from_to.serialize(&mut *request_from_to_acc.data.borrow_mut())?;
             last_request_from_to.serialize(&mut *last_request_from_to_acc.data.borrow_mut())?;
         }
@@ -110,7 +110,7 @@ impl Processor {
             if generated_request_key != *last_request_to_from_acc.key {
                 return Err(ProgramError::InvalidSeeds);
             }
-            mem::swap(request_to_from, last_request_to_from);
+            *request_to_from = mem::take(last_request_to_from);
             request_to_from.serialize(&mut *request_to_from_acc.data.borrow_mut())?;
             last_request_to_from.serialize(&mut *last_request_to_from_acc.data.borrow_mut())?;
         }
@@ -0,0 +1,365 @@
+//! Property test of swap with last removal of friend requests.
+//!
+//! Random sequences of requests, denials and cancellations are applied both to in process accounts
+//! and to plain vectors, after each step counters and every request of both lists must match.
+//!
+//! `cargo test --test swap`, `SWAP_SEED=<seed>` replays single failed case.
+
+use std::collections::HashMap;
+
+use arbitrary::{Arbitrary, Unstructured};
+use borsh::{BorshDeserialize, BorshSerialize};
+use satellite_friends::{
+    id, instruction,
+    processor::Processor,
+    state::{FriendInfo, Request},
+};
+use solana_program::{
+    account_info::AccountInfo,
+    entrypoint::ProgramResult,
+    instruction::Instruction,
+    pubkey::Pubkey,
+    rent::Rent,
+    sysvar::{self, Sysvar},
+};
+
+const USERS: usize = 4;
+const CASES: u64 = 64;
+const CASE_BYTES: usize = 1024;
+
+#[derive(Arbitrary, Debug)]
+enum Op {
+    /// request to itself is skipped
+    Request { from: u8, to: u8 },
+    /// `slot` chooses which of incoming requests of `user` to deny
+    Deny { user: u8, slot: u8 },
+    /// `slot` chooses which of outgoing requests of `user` to remove
+    Remove { user: u8, slot: u8 },
+    /// same as [Op::Remove] but passes request before last as last, must fail without changes
+    RemoveWrongLast { user: u8, slot: u8 },
+}
+
+/// in process accounts, all owned by program and created rent exempt on first use
+#[derive(Default)]
+struct Ledger {
+    accounts: HashMap<Pubkey, (u64, Vec<u8>)>,
+}
+
+impl Ledger {
+    fn insert(&mut self, key: Pubkey, data: Vec<u8>) {
+        let lamports = Rent::default().minimum_balance(data.len());
+        self.accounts.insert(key, (lamports, data));
+    }
+
+    fn read<T: BorshDeserialize>(&self, key: &Pubkey) -> Option<T> {
+        self.accounts
+            .get(key)
+            .map(|(_, data)| T::try_from_slice(data).unwrap())
+    }
+
+    /// runs instruction, on error changes are dropped as runtime does
+    fn process(&mut self, instruction: &Instruction, lens: &[usize]) -> ProgramResult {
+        for (meta, len) in instruction.accounts.iter().zip(lens) {
+            if !self.accounts.contains_key(&meta.pubkey) {
+                self.insert(meta.pubkey, vec![0; *len]);
+            }
+        }
+
+        let snapshot = self.accounts.clone();
+        let program_id = id();
+        let result = {
+            let unique: Vec<AccountInfo> = self
+                .accounts
+                .iter_mut()
+                .filter(|(key, _)| instruction.accounts.iter().any(|m| m.pubkey == **key))
+                .map(|(key, (lamports, data))| {
+                    let is_signer = instruction
+                        .accounts
+                        .iter()
+                        .any(|m| m.pubkey == *key && m.is_signer);
+                    AccountInfo::new(key, is_signer, true, lamports, data, &program_id, false, 0)
+                })
+                .collect();
+            let accounts: Vec<AccountInfo> = instruction
+                .accounts
+                .iter()
+                .map(|m| unique.iter().find(|a| *a.key == m.pubkey).unwrap().clone())
+                .collect();
+            Processor::process_instruction(&program_id, &accounts, &instruction.data)
+        };
+
+        if result.is_err() {
+            self.accounts = snapshot;
+        }
+        result
+    }
+}
+
+/// expected state, swap with last is [Vec::swap_remove]
+struct Model {
+    users: Vec<Pubkey>,
+    bases: Vec<Pubkey>,
+    /// receivers of requests made by user
+    outgoing: Vec<Vec<usize>>,
+    /// senders of requests user got
+    incoming: Vec<Vec<usize>>,
+}
+
+impl Model {
+    fn friend_info(&self, user: usize) -> Pubkey {
+        Pubkey::create_with_seed(&self.bases[user], Processor::FRIEND_INFO_SEED, &id()).unwrap()
+    }
+
+    fn request(&self, user: usize, index: usize, seed: &str) -> Pubkey {
+        Pubkey::create_with_seed(&self.bases[user], &format!("{:?}{}", index, seed), &id()).unwrap()
+    }
+
+    fn outgoing(&self, user: usize, index: usize) -> Pubkey {
+        self.request(user, index, Processor::OUTGOING_REQUEST)
+    }
+
+    fn incoming(&self, user: usize, index: usize) -> Pubkey {
+        self.request(user, index, Processor::INCOMING_REQUEST)
+    }
+}
+
+fn setup() -> (Ledger, Model) {
+    let users: Vec<Pubkey> = (0..USERS).map(|_| Pubkey::new_unique()).collect();
+    let bases = users
+        .iter()
+        .map(|user| Pubkey::find_program_address(&[&user.to_bytes()[..32]], &id()).0)
+        .collect();
+    let model = Model {
+        users,
+        bases,
+        outgoing: vec![Vec::new(); USERS],
+        incoming: vec![Vec::new(); USERS],
+    };
+
+    let mut ledger = Ledger::default();
+    for (index, user) in model.users.iter().enumerate() {
+        let friend_info = FriendInfo {
+            user: *user,
+            ..FriendInfo::default()
+        };
+        ledger.insert(model.friend_info(index), friend_info.try_to_vec().unwrap());
+        ledger.insert(*user, Vec::new());
+    }
+
+    let mut rent_data = vec![0; Rent::size_of()];
+    let (rent_key, rent_owner) = (sysvar::rent::id(), sysvar::id());
+    let mut lamports = 0;
+    Rent::default()
+        .to_account_info(&mut AccountInfo::new(
+            &rent_key,
+            false,
+            false,
+            &mut lamports,
+            &mut rent_data,
+            &rent_owner,
+            false,
+            0,
+        ))
+        .unwrap();
+    ledger.insert(rent_key, rent_data);
+
+    (ledger, model)
+}
+
+fn make_request(ledger: &mut Ledger, model: &mut Model, from: usize, to: usize) {
+    if from == to {
+        return;
+    }
+
+    let instruction = instruction::make_request(
+        &id(),
+        &model.outgoing(from, model.outgoing[from].len()),
+        &model.incoming(to, model.incoming[to].len()),
+        &model.friend_info(from),
+        &model.friend_info(to),
+        &model.users[from],
+    )
+    .unwrap();
+
+    ledger
+        .process(&instruction, &[Request::LEN, Request::LEN])
+        .unwrap();
+    model.outgoing[from].push(to);
+    model.incoming[to].push(from);
+}
+
+/// removes request between `from` and `to` at given positions of their lists
+fn remove_request(
+    ledger: &mut Ledger,
+    model: &mut Model,
+    (from, outgoing): (usize, usize),
+    (to, incoming): (usize, usize),
+    deny: bool,
+    wrong_last: bool,
+) {
+    let outgoing_len = model.outgoing[from].len();
+    let incoming_len = model.incoming[to].len();
+    if wrong_last && (outgoing_len < 2 || incoming_len < 2) {
+        return;
+    }
+    let last = |len: usize| if wrong_last { len - 2 } else { len - 1 };
+
+    let accounts = (
+        &model.outgoing(from, outgoing),
+        &model.incoming(to, incoming),
+        &model.outgoing(from, last(outgoing_len)),
+        &model.incoming(to, last(incoming_len)),
+        &model.friend_info(from),
+        &model.friend_info(to),
+    );
+    let instruction = if deny {
+        instruction::deny_request(
+            &id(),
+            accounts.0,
+            accounts.1,
+            accounts.2,
+            accounts.3,
+            accounts.4,
+            accounts.5,
+            &model.users[to],
+        )
+    } else {
+        instruction::remove_request(
+            &id(),
+            accounts.0,
+            accounts.1,
+            accounts.2,
+            accounts.3,
+            accounts.4,
+            accounts.5,
+            &model.users[from],
+        )
+    }
+    .unwrap();
+
+    let result = ledger.process(&instruction, &[]);
+    if wrong_last {
+        assert!(result.is_err(), "wrong last request accepted");
+    } else {
+        result.unwrap();
+        model.outgoing[from].swap_remove(outgoing);
+        model.incoming[to].swap_remove(incoming);
+    }
+}
+
+fn deny(ledger: &mut Ledger, model: &mut Model, to: usize, slot: usize) {
+    if model.incoming[to].is_empty() {
+        return;
+    }
+    let incoming = slot % model.incoming[to].len();
+    let from = model.incoming[to][incoming];
+    let outgoing = model.outgoing[from].iter().position(|user| *user == to);
+
+    remove_request(
+        ledger,
+        model,
+        (from, outgoing.unwrap()),
+        (to, incoming),
+        true,
+        false,
+    );
+}
+
+fn remove(ledger: &mut Ledger, model: &mut Model, from: usize, slot: usize, wrong_last: bool) {
+    if model.outgoing[from].is_empty() {
+        return;
+    }
+    let outgoing = slot % model.outgoing[from].len();
+    let to = model.outgoing[from][outgoing];
+    let incoming = model.incoming[to].iter().position(|user| *user == from);
+
+    remove_request(
+        ledger,
+        model,
+        (from, outgoing),
+        (to, incoming.unwrap()),
+        false,
+        wrong_last,
+    );
+}
+
+fn check(ledger: &Ledger, model: &Model) {
+    for user in 0..USERS {
+        let friend_info: FriendInfo = ledger.read(&model.friend_info(user)).unwrap();
+        assert_eq!(friend_info.user, model.users[user]);
+        assert_eq!(friend_info.friends, 0);
+        assert_eq!(
+            friend_info.requests_outgoing,
+            model.outgoing[user].len() as u64
+        );
+        assert_eq!(
+            friend_info.requests_incoming,
+            model.incoming[user].len() as u64
+        );
+
+        for (index, to) in model.outgoing[user].iter().enumerate() {
+            let request: Request = ledger.read(&model.outgoing(user, index)).unwrap();
+            assert_eq!(request.from, model.users[user]);
+            assert_eq!(request.to, model.users[*to]);
+        }
+        for (index, from) in model.incoming[user].iter().enumerate() {
+            let request: Request = ledger.read(&model.incoming(user, index)).unwrap();
+            assert_eq!(request.from, model.users[*from]);
+            assert_eq!(request.to, model.users[user]);
+        }
+
+        let after_last: Option<Request> =
+            ledger.read(&model.outgoing(user, model.outgoing[user].len()));
+        assert_eq!(after_last.unwrap_or_default(), Request::default());
+        let after_last: Option<Request> =
+            ledger.read(&model.incoming(user, model.incoming[user].len()));
+        assert_eq!(after_last.unwrap_or_default(), Request::default());
+    }
+}
+
+/// deterministic bytes, so failures are reproducible without fuzzer corpus
+fn case_bytes(seed: u64) -> Vec<u8> {
+    let mut state = seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1;
+    (0..CASE_BYTES)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state as u8
+        })
+        .collect()
+}
+
+fn run(seed: u64) {
+    let bytes = case_bytes(seed);
+    let mut unstructured = Unstructured::new(&bytes);
+
+    let (mut ledger, mut model) = setup();
+    while !unstructured.is_empty() {
+        let user = |user: u8| user as usize % USERS;
+        match unstructured.arbitrary().unwrap() {
+            Op::Request { from, to } => make_request(&mut ledger, &mut model, user(from), user(to)),
+            Op::Deny { user: to, slot } => deny(&mut ledger, &mut model, user(to), slot as usize),
+            Op::Remove { user: from, slot } => {
+                remove(&mut ledger, &mut model, user(from), slot as usize, false)
+            }
+            Op::RemoveWrongLast { user: from, slot } => {
+                remove(&mut ledger, &mut model, user(from), slot as usize, true)
+            }
+        }
+        check(&ledger, &model);
+    }
+}
+
+#[test]
+fn request_deny_remove_keeps_indices_dense() {
+    let seeds = match std::env::var("SWAP_SEED") {
+        Ok(seed) => vec![seed.parse().unwrap()],
+        Err(_) => (0..CASES).collect(),
+    };
+
+    for seed in seeds {
+        let result = std::panic::catch_unwind(|| run(seed));
+        assert!(result.is_ok(), "failed with SWAP_SEED={}", seed);
+    }
+}
@@ -24,12 +24,14 @@ LAYOUT.addVariant(
   1,
   BufferLayout.struct([BufferLayout.seq(BufferLayout.u8(), 32, "name"),
                        BufferLayout.seq(BufferLayout.u8(), 64, "hash"),
-                       BufferLayout.seq(BufferLayout.u8(), 128, "status")]),
+                       BufferLayout.seq(BufferLayout.u8(), 128, "status"),
+                       BufferLayout.u8("bump")]),
   "initializeDweller"
 );
 LAYOUT.addVariant(
   2,
-  BufferLayout.struct([BufferLayout.seq(BufferLayout.u8(), 32, "name")]),
+  BufferLayout.struct([BufferLayout.seq(BufferLayout.u8(), 32, "name"),
+                       BufferLayout.u8("bump")]),
   "initializeServer"
 );
 
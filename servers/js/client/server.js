@@ -22,14 +22,24 @@ const DWELLER_SERVER_SEED = "DwellerServer";
 
 const SERVER_MEMBER_SEED = "ServerMember";
 
-function initializeDweller(dweller, name, hash, status) {
+// bump of base of indexed lists of account, stored by program so it does not search for it
+async function findBaseBump(account) {
+  const [, bump] = await PublicKey.findProgramAddress(
+    [account.toBytes()],
+    SERVER_PROGRAM_ID
+  );
+  return bump;
+}
+
+function initializeDweller(dweller, name, hash, status, bump) {
   return new TransactionInstruction({
     keys: [{ pubkey: dweller.publicKey, isSigner: true, isWritable: true }],
     programId: SERVER_PROGRAM_ID,
     data: encodeInstructionData({
       initializeDweller: { name: stringToBuffer(name, 32),
                            hash: stringToBuffer(hash, 64),
-                           status: stringToBuffer(status, 128) },
+                           status: stringToBuffer(status, 128),
+                           bump },
     }),
   });
 }
@@ -39,7 +49,8 @@ function initializeServer(
   server,
   dwellerServer,
   serverMember,
-  name
+  name,
+  bump
 ) {
   return new TransactionInstruction({
     keys: [
@@ -50,7 +61,7 @@ function initializeServer(
     ],
     programId: SERVER_PROGRAM_ID,
     data: encodeInstructionData({
-      initializeServer: { name: stringToBuffer(name, 32) },
+      initializeServer: { name: stringToBuffer(name, 32), bump },
     }),
   });
 }
@@ -60,6 +71,7 @@ async function createDweller(connection, payerAccount, name, hash, status) {
   const lamports = await connection.getMinimumBalanceForRentExemption(space);
 
   const dweller = new Account();
+  const bump = await findBaseBump(dweller.publicKey);
 
   const transaction = new Transaction()
     .add(
@@ -71,7 +83,7 @@ async function createDweller(connection, payerAccount, name, hash, status) {
         programId: SERVER_PROGRAM_ID,
       })
     )
-    .add(initializeDweller(dweller, name, hash, status));
+    .add(initializeDweller(dweller, name, hash, status, bump));
 
   const result = await sendAndConfirmTransaction(
     connection,
@@ -183,7 +195,8 @@ async function createServer(connection, payerAccount, dwellerAccount, name) {
         server.publicKey,
         dwellerServer,
         serverMember,
-        name
+        name,
+        await findBaseBump(server.publicKey)
       )
     );
 
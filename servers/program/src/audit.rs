@@ -0,0 +1,217 @@
+//! Append only audit log of administrative actions.
+//! Entries are emitted into program logs, so indexers can collect them from transaction meta.
+
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use solana_program::{
+    clock::Clock, entrypoint::ProgramResult, log::sol_log_data, pubkey::Pubkey, sysvar::Sysvar,
+};
+
+/// Kind of administrative action
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone, BorshSchema)]
+pub enum AuditAction {
+    /// target is channel
+    AddChannel,
+    /// target is channel
+    DeleteChannel,
+    /// target is group
+    CreateGroup,
+    /// target is group
+    DeleteGroup,
+    /// target is channel
+    AddChannelToGroup,
+    /// target is group channel
+    RemoveChannelFromGroup,
+    /// target is server
+    ReorderChannels,
+    /// target is invited dweller
+    InviteToServer,
+    /// target is dweller whose invite was revoked
+    RevokeInviteServer,
+    /// target is new administrator dweller
+    AddAdmin,
+    /// target is removed administrator dweller
+    RemoveAdmin,
+    /// target is server
+    SetServerName,
+    /// target is server
+    SetServerDb,
+    /// target is pinned message
+    PinMessage,
+    /// target is pinned message
+    UnpinMessage,
+    /// target is server
+    SetServerDescription,
+    /// target is server
+    SetServerBanner,
+    /// target is server
+    SetServerTags,
+    /// target is server
+    SetServerPublic,
+    /// target is server listing
+    ListServer,
+    /// target is server listing
+    UnlistServer,
+    /// target is channel
+    UpdateChannel,
+    /// target is server
+    SetServerGate,
+}
+
+/// Entry of audit log
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone, BorshSchema)]
+pub struct AuditEvent {
+    /// server action was done in
+    pub server: Pubkey,
+    /// dweller who did action
+    pub actor: Pubkey,
+    /// kind
+    pub action: AuditAction,
+    /// account action was done on
+    pub target: Pubkey,
+    /// slot of action
+    pub slot: u64,
+}
+
+impl AuditEvent {
+    /// first field of log data, distinguishes audit entries from other data
+    pub const TAG: [u8; 8] = *b"SatAudit";
+
+    /// prefix of data lines in program logs
+    pub const LOG_PREFIX: &'static str = "Program data: ";
+
+    /// emits entry for current slot into program logs
+    pub fn emit(
+        server: &Pubkey,
+        actor: &Pubkey,
+        action: AuditAction,
+        target: &Pubkey,
+    ) -> ProgramResult {
+        let event = Self {
+            server: *server,
+            actor: *actor,
+            action,
+            target: *target,
+            slot: Clock::get()?.slot,
+        };
+        sol_log_data(&[&Self::TAG, &event.try_to_vec()?]);
+        Ok(())
+    }
+
+    /// decodes entry from program log line, `None` if line is not audit entry,
+    /// line alone does not tell which program logged it, so prefer [Self::decode_all]
+    pub fn decode(log: &str) -> Option<Self> {
+        let mut fields = log.strip_prefix(Self::LOG_PREFIX)?.split_whitespace();
+        if base64::decode(fields.next()?).ok()? != Self::TAG {
+            return None;
+        }
+
+        let data = base64::decode(fields.next()?).ok()?;
+        Self::try_from_slice(&data).ok()
+    }
+
+    /// decodes all entries from program logs of transaction,
+    /// tracks invoke frames to skip data logged by other programs, even ones invoked by this one
+    pub fn decode_all<'a, I: IntoIterator<Item = &'a String>>(logs: I) -> Vec<Self> {
+        let program = crate::id().to_string();
+        // whether program of frame is this program, innermost last
+        let mut frames = Vec::new();
+        let mut events = Vec::new();
+        for log in logs {
+            let mut words = log.split_whitespace();
+            if words.next() == Some("Program") {
+                match (words.next(), words.next()) {
+                    (Some(id), Some("invoke")) => frames.push(id == program),
+                    (Some(_), Some(status))
+                        if status == "success" || status.starts_with("failed") =>
+                    {
+                        frames.pop();
+                    }
+                    _ => {}
+                }
+            }
+
+            if frames.last() == Some(&true) {
+                events.extend(Self::decode(log));
+            }
+        }
+        events
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn decode() {
+        let event = AuditEvent {
+            server: Pubkey::new_unique(),
+            actor: Pubkey::new_unique(),
+            action: AuditAction::DeleteChannel,
+            target: Pubkey::new_unique(),
+            slot: 42,
+        };
+        let log = format!(
+            "{}{} {}",
+            AuditEvent::LOG_PREFIX,
+            base64::encode(AuditEvent::TAG),
+            base64::encode(event.try_to_vec().unwrap())
+        );
+
+        assert_eq!(AuditEvent::decode(&log), Some(event));
+        assert_eq!(
+            AuditEvent::decode("Program log: Instruction: AddChannel"),
+            None
+        );
+        assert_eq!(
+            AuditEvent::decode(&format!(
+                "{}{}",
+                AuditEvent::LOG_PREFIX,
+                base64::encode([1; 8])
+            )),
+            None
+        );
+    }
+
+    #[test]
+    fn decode_all_skips_other_programs() {
+        let event = |slot| AuditEvent {
+            server: Pubkey::new_unique(),
+            actor: Pubkey::new_unique(),
+            action: AuditAction::SetServerName,
+            target: Pubkey::new_unique(),
+            slot,
+        };
+        let data = |event: &AuditEvent| {
+            format!(
+                "{}{} {}",
+                AuditEvent::LOG_PREFIX,
+                base64::encode(AuditEvent::TAG),
+                base64::encode(event.try_to_vec().unwrap())
+            )
+        };
+        let program = crate::id();
+        let foreign = Pubkey::new_unique();
+        let (own, nested, sibling, after) = (event(1), event(2), event(3), event(4));
+        let logs = vec![
+            format!("Program {} invoke [1]", program),
+            "Program log: Instruction: SetServerName".to_string(),
+            format!("Program {} invoke [2]", foreign),
+            data(&nested),
+            format!("Program {} success", foreign),
+            data(&own),
+            format!("Program {} consumed 4242 of 200000 compute units", program),
+            format!("Program {} success", program),
+            format!("Program {} invoke [1]", foreign),
+            data(&sibling),
+            format!("Program {} failed: custom program error: 0x1", foreign),
+            format!("Program {} invoke [1]", program),
+            data(&after),
+            format!("Program {} success", program),
+        ];
+
+        assert_eq!(AuditEvent::decode_all(&logs), vec![own, after]);
+    }
+}
@@ -67,6 +67,138 @@ pub enum Error {
     ///Invalid derived server member laast address
     #[error("Invalid derived server member laast address")]
     InvalidDerivedServerMemberLaastAddress,
+
+    ///Account already resized to latest version
+    #[error("Account already resized to latest version")]
+    AccountAlreadyResized,
+
+    ///Provided accounts do not match positions
+    #[error("Provided accounts do not match positions")]
+    InvalidPositions,
+
+    ///Channel already belongs to group
+    #[error("Channel already belongs to group")]
+    ChannelAlreadyInGroup,
+
+    ///Channel belongs to group
+    #[error("Channel belongs to group")]
+    ChannelInGroup,
+
+    ///Group has nested groups
+    #[error("Group has nested groups")]
+    GroupHasNestedGroups,
+
+    ///Delegate does not belong to dweller
+    #[error("Delegate does not belong to dweller")]
+    InvalidDelegate,
+
+    ///Delegate expired
+    #[error("Delegate expired")]
+    DelegateExpired,
+
+    ///Delegate scope does not allow operation
+    #[error("Delegate scope does not allow operation")]
+    DelegateScopeNotAllowed,
+
+    ///Dweller must leave all servers first
+    #[error("Dweller must leave all servers first")]
+    DwellerHasServers,
+
+    ///Invalid derived pinned message address
+    #[error("Invalid derived pinned message address")]
+    InvalidDerivedPinnedMessageAddress,
+
+    ///Invalid derived direct channel address
+    #[error("Invalid derived direct channel address")]
+    InvalidDerivedDirectChannelAddress,
+
+    ///Direct channel must have from 2 to 10 distinct participants
+    #[error("Direct channel must have from 2 to 10 distinct participants")]
+    InvalidDirectChannelParticipants,
+
+    ///Dweller is not participant of direct channel
+    #[error("Dweller is not participant of direct channel")]
+    NotDirectChannelParticipant,
+
+    ///Only creator of direct channel can remove participants
+    #[error("Only creator of direct channel can remove participants")]
+    NotDirectChannelCreator,
+
+    ///Invalid derived server directory address
+    #[error("Invalid derived server directory address")]
+    InvalidDerivedServerDirectoryAddress,
+
+    ///Invalid derived server listing address
+    #[error("Invalid derived server listing address")]
+    InvalidDerivedServerListingAddress,
+
+    ///Server must be public to be listed
+    #[error("Server must be public to be listed")]
+    ServerNotPublic,
+
+    ///Server already listed
+    #[error("Server already listed")]
+    ServerAlreadyListed,
+
+    ///Server not listed
+    #[error("Server not listed")]
+    ServerNotListed,
+
+    ///Channel topic is not utf8
+    #[error("Channel topic is not utf8")]
+    InvalidChannelTopic,
+
+    ///Slow mode interval too long
+    #[error("Slow mode interval too long")]
+    SlowModeTooLong,
+
+    ///Server is not token gated
+    #[error("Server is not token gated")]
+    ServerNotTokenGated,
+
+    ///Token is not of mint of server gate
+    #[error("Token is not of mint of server gate")]
+    TokenGateWrongMint,
+
+    ///Token is not owned by dweller
+    #[error("Token is not owned by dweller")]
+    TokenGateWrongOwner,
+
+    ///Token balance is below server gate amount
+    #[error("Token balance is below server gate amount")]
+    TokenGateInsufficientBalance,
+
+    ///Account must be resized to latest version first
+    #[error("Account must be resized to latest version first")]
+    AccountNotResized,
+
+    ///Channel must have no pinned messages
+    #[error("Channel must have no pinned messages")]
+    ChannelHasPins,
+
+    ///Dweller must close all direct channels first
+    #[error("Dweller must close all direct channels first")]
+    DwellerHasDirectChannels,
+
+    ///Server must be unlisted first
+    #[error("Server must be unlisted first")]
+    ServerListed,
+
+    ///Dweller must remove all delegates first
+    #[error("Dweller must remove all delegates first")]
+    DwellerHasDelegates,
+
+    ///Delegate scope is empty or has unknown bits
+    #[error("Delegate scope is empty or has unknown bits")]
+    InvalidDelegateScope,
+
+    ///Token gate has amount or mint not matching its kind
+    #[error("Token gate has amount or mint not matching its kind")]
+    InvalidTokenGate,
+
+    ///Server owner can not leave own server
+    #[error("Server owner can not leave own server")]
+    OwnerCannotLeaveServer,
 }
 
 impl From<Error> for ProgramError {
@@ -6,6 +6,8 @@ use solana_program::{
     instruction::AccountMeta, program_error::ProgramError, pubkey::Pubkey, system_program, sysvar,
 };
 
+use crate::{program::find_base_bump, state::TokenGate};
+
 /// Instructions
 #[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone, BorshSchema, ToPrimitive)]
 pub enum Instruction {
@@ -19,7 +21,7 @@ pub enum Instruction {
     /// - signer, write     dweller
     ///
     /// Input:
-    ///  [InitializeDwellerInput]
+    ///  [InitializeDwellerInput], then bump of base of dweller found by [find_base_bump]
     InitializeDweller,
 
     /// Initializes server and joins dweller_owner
@@ -28,20 +30,22 @@ pub enum Instruction {
     /// - signer,  write          server
     /// - derived, write          dweller_server
     /// - derived, write          server_member
-    /// Input: [InitializeServerInput]
+    /// Input: [InitializeServerInput], then bump of base of server found by [find_base_bump]
     InitializeServer,
 
     /// Change dweller's display name
     ///
     /// Accounts:
-    /// - write, signer     dweller
+    /// - write, signer     dweller, not signer if delegate is provided
+    /// - signer            delegate, optional [crate::state::DwellerDelegate] with profile scope
     /// Input: [SetNameInput]
     SetDwellerName,
 
     /// Change dweller's display photo. Consider using PNG or JPEG photos for usability.
     ///
     /// Accounts:
-    /// - signer, write   dweller
+    /// - signer, write   dweller, not signer if delegate is provided
+    /// - signer          delegate, optional [crate::state::DwellerDelegate] with profile scope
     ///
     /// Input: [SetHashInput]
     SetDwellerPhoto,
@@ -49,7 +53,8 @@ pub enum Instruction {
     /// Update the users status
     ///
     /// Accounts:
-    /// - signer, write   dweller owner
+    /// - signer, write   dweller owner, not signer if delegate is provided
+    /// - signer          delegate, optional [crate::state::DwellerDelegate] with profile scope
     ///
     /// Input: [SetDwellerStatusInput]
     SetDwellerStatus,
@@ -59,19 +64,27 @@ pub enum Instruction {
     /// Accounts:
     /// - signer             dweller_administrator
     /// - read, derived      server_administrator for dweller_administrator
-    /// - write              server
+    /// - write              server, resized to [crate::state::StateVersion::V2]
     /// - write, derived     server_channel
     ///
     /// Input:
-    /// [AddChannelInput]
+    /// [AddChannelInput], then bump of base of server_channel found by [find_base_bump]
     AddChannel,
 
+    /// Positions of other channels are kept, leaving gap.
+    ///
+    /// Last channel is moved into index of deleted one, which changes its address,
+    /// so its group channel is pointed to new address
+    /// and its pinned messages are moved to accounts derived from new address.
+    ///
     /// Accounts:
     /// - signer                 dweller_administrator
     /// - read, derived          server_administrator for dweller_administrator
-    /// - write                  server
-    /// - write, derived         server_channel
+    /// - write                  server, resized to [crate::state::StateVersion::V2]
+    /// - write, derived         server_channel, must have no group and no pins
     /// - write, derived         server_channel_last
+    /// - write, derived         group_channel pointing to server_channel_last, only if it is moved and in group
+    /// - write, derived         [pinned_message_last, pinned_message] all pins of server_channel_last in order of index, each followed by created account derived from server_channel at same index, only if it is moved
     DeleteChannel,
 
     /// Initialize group and add to server.
@@ -79,36 +92,49 @@ pub enum Instruction {
     /// Accounts:
     /// - signer            dweller_administrator
     /// - read, derived     server_administrator for `dweller_administrator`
-    /// - write             server
+    /// - write             server, resized to [crate::state::StateVersion::V2]
     /// - write, derived    server_group
+    /// - write, derived    server_group_parent, optional, group to nest new one into
     ///
     /// Input:
-    /// - [CreateGroupInput]
+    /// - [CreateGroupInput], then bump of base of server_group found by [find_base_bump]
     CreateGroup,
 
+    /// Positions of other groups are kept, leaving gap.
+    ///
+    /// Last group is moved into index of deleted one, which changes its address,
+    /// so its nested groups and channels are pointed to new address
+    /// and its group channels are moved to accounts derived from new address.
+    ///
     /// Accounts:
     /// - signer             dweller_administrator    
     /// - read, derived      server_administrator
-    /// - write              server
-    /// - write, derived     server_group
+    /// - write              server, resized to [crate::state::StateVersion::V2]
+    /// - write, derived     server_group, must have no nested groups
     /// - write, derived     server_group_last
-    /// - write, derived     [group_channel] all channels in group
+    /// - write, derived     server_group_parent, only if deleted group is nested
+    /// - write, derived     [group_channel, server_channel] all channels in group in order of group channel index, each followed by channel it points to
+    /// - write, derived     [server_group_nested] all groups nested into server_group_last except deleted one in order of index, only if it is moved
+    /// - write, derived     [group_channel_last, group_channel, server_channel] all channels in server_group_last in order of group channel index, each followed by created account derived from server_group at same index and by channel it points to, only if it is moved
     DeleteGroup,
 
     /// Accounts:
     /// - read, write        server
     /// - read, signer       dweller_administrator
     /// - read, derived      server_administrator
-    /// - read               server_channel
-    //  - write, derived     server_group
+    /// - write, derived     server_channel, must not belong to any group
+    /// - write, derived     server_group, resized to [crate::state::StateVersion::V2]
     /// - write, derived     group_channel
     AddChannelToGroup,
 
+    /// Positions of other channels in group are kept, leaving gap.
+    ///
     /// Accounts:
     /// - write              server
     /// - signer             dweller_administrator
     /// - read, derived      server_administrator
-    /// - read               server_channel
+    /// - write, derived     server_group, resized to [crate::state::StateVersion::V2]
+    /// - write, derived     server_channel
     /// - write, derived     group_channel
     /// - write, derived     group_channel_last
     RemoveChannelFromGroup,
@@ -131,19 +157,23 @@ pub enum Instruction {
     /// Accounts:
     ///   - writeable                  server     
     ///   - writeable, derived         server_member
-    ///   - read, derived              server_member_status
-    ///   - writeable signer           dweller
+    ///   - read, derived              server_member_status, or token account of dweller satisfying [crate::state::TokenGate] of server
+    ///   - writeable signer           dweller, not signer if delegate is provided
     ///   - writeable, derived         dweller_server
+    ///   - signer                     delegate, optional [crate::state::DwellerDelegate] with membership scope
     JoinServer,
 
+    /// Server owner can not leave own server, so dweller owning server can not be closed.
+    ///
     /// Accounts:
     ///
     /// - write                      server
     /// - write, derived             server_member
     /// - write, derived             server_member_last
-    /// - write, signer              dweller
+    /// - write, signer              dweller, not signer if delegate is provided
     /// - write, derived             dweller_server
     /// - write, derived             dweller_server_last
+    /// - signer                     delegate, optional [crate::state::DwellerDelegate] with membership scope
     LeaveServer,
 
     /// Accounts:
@@ -177,6 +207,229 @@ pub enum Instruction {
     ///
     /// Input: [SetHashInput]        
     SetServerDb,
+
+    /// Grows [Dweller], [Server], [ServerChannel], [ServerGroup] or [GroupChannel]
+    /// to latest data size and upgrades it to [StateVersion::V2].
+    /// Payer tops up rent for new data size.
+    /// Stores bump of base of indexed lists, so handlers do not search for it,
+    /// accounts already of latest size without stored bump only get bump.
+    /// Channels and groups get position equal to index, order they were shown before.
+    ///
+    /// Accounts:
+    /// - write, signer      payer
+    /// - write              account, dweller, server, server channel, server group or group channel
+    /// - signer             dweller itself or owner of server
+    /// - read               rent
+    /// - read               system_program
+    /// - read               server group, only for group channel
+    /// - read               server, only for server channel, server group and group channel
+    ///
+    /// Input: [ResizeAccountInput], then bump of base of account found by [find_base_bump]
+    ///
+    /// [Dweller]: crate::state::Dweller
+    /// [Server]: crate::state::Server
+    /// [ServerChannel]: crate::state::ServerChannel
+    /// [ServerGroup]: crate::state::ServerGroup
+    /// [GroupChannel]: crate::state::GroupChannel
+    /// [StateVersion::V2]: crate::state::StateVersion::V2
+    ResizeAccount,
+
+    /// Reorders channels, groups or channels in group.
+    /// Positions held by provided accounts are given to them in order accounts are provided.
+    ///
+    /// Accounts:
+    /// - signer             dweller_administrator
+    /// - read, derived      server_administrator
+    /// - read               server
+    /// - read, derived      server_group, only for [ReorderChannelsInput::GroupChannels]
+    /// - write, derived     [server_channel], [server_group] or [group_channel] in new order
+    ///
+    /// Input: [ReorderChannelsInput]
+    ReorderChannels,
+
+    /// Registers device key which can sign for dweller.
+    /// Delegate account must be created by device key with [crate::state::DwellerDelegate::LEN] and owned by program.
+    ///
+    /// Wherever dweller signs, delegate account signed by device key can be provided instead:
+    /// as `dweller_administrator` or `owner` for admin instructions,
+    /// or as trailing account with not signed dweller for dweller instructions.
+    ///
+    /// Dweller must be resized to [crate::state::StateVersion::V2] to count delegates.
+    ///
+    /// Accounts:
+    /// - write, signer      dweller
+    /// - write, signer      delegate
+    ///
+    /// Input: [AddDelegateInput]
+    AddDelegate,
+
+    /// Removes delegate and sends its lamports to destination.
+    ///
+    /// Accounts:
+    /// - write              dweller
+    /// - signer             authority, dweller or delegate itself
+    /// - write              delegate
+    /// - write              destination
+    RemoveDelegate,
+
+    /// Erases dweller name, photo and status and closes account, refunding rent to destination.
+    /// Dweller must have left all servers, have no direct channels and remove all delegates,
+    /// so owner of server can not be closed.
+    ///
+    /// Accounts:
+    /// - write, signer      dweller
+    /// - write              destination
+    /// - write              [delegate] delegates of dweller to close too
+    CloseDweller,
+
+    /// Pins message to channel.
+    ///
+    /// Accounts:
+    /// - signer             dweller_administrator
+    /// - read, derived      server_administrator for dweller_administrator
+    /// - read               server
+    /// - write, derived     server_channel
+    /// - write, derived     pinned_message
+    ///
+    /// Input: [PinMessageInput]
+    PinMessage,
+
+    /// Unpins message from channel.
+    ///
+    /// Accounts:
+    /// - signer             dweller_administrator
+    /// - read, derived      server_administrator for dweller_administrator
+    /// - read               server
+    /// - write, derived     server_channel
+    /// - write, derived     pinned_message
+    /// - write, derived     pinned_message_last
+    UnpinMessage,
+
+    /// Creates direct channel of dweller with participants.
+    /// Dweller must be resized to [crate::state::StateVersion::V2].
+    ///
+    /// Accounts:
+    /// - write              dweller, creator
+    /// - write, derived     direct_channel
+    /// - signer             dweller or its delegate with membership scope
+    /// - read               [participant] other dwellers, from 1 to 9
+    ///
+    /// Input: [SetHashInput] db hash
+    CreateDirectChannel,
+
+    /// Adds dweller to direct channel, any participant can add.
+    ///
+    /// Accounts:
+    /// - read, signer       dweller, participant, not signer if delegate is provided
+    /// - write              direct_channel
+    /// - read               participant, dweller to add
+    /// - signer             delegate, optional with membership scope
+    AddDirectChannelParticipant,
+
+    /// Removes other dweller from direct channel, only creator which is still participant can remove.
+    /// Channel left with single participant is closed, last direct channel of creator is moved into its place.
+    ///
+    /// Accounts:
+    /// - write, signer      dweller, creator, not signer if delegate is provided
+    /// - write              direct_channel
+    /// - read               participant, dweller to remove
+    /// - write, derived     direct_channel_last, last direct channel of creator
+    /// - signer             delegate, optional with membership scope
+    RemoveDirectChannelParticipant,
+
+    /// Removes dweller from direct channel.
+    /// Channel left with single participant is closed, last direct channel of creator is moved into its place.
+    ///
+    /// Accounts:
+    /// - read, signer       dweller, participant, not signer if delegate is provided
+    /// - write              direct_channel
+    /// - write              creator, dweller which created direct channel
+    /// - write, derived     direct_channel_last, last direct channel of creator
+    /// - signer             delegate, optional with membership scope
+    LeaveDirectChannel,
+
+    /// Server must be resized to [crate::state::StateVersion::V2].
+    ///
+    /// Accounts:
+    /// - read, signer       dweller_administrator
+    /// - read, derived      server_administrator
+    /// - write              server
+    ///
+    /// Input: [SetServerDescriptionInput]
+    SetServerDescription,
+
+    /// Server must be resized to [crate::state::StateVersion::V2].
+    ///
+    /// Accounts:
+    /// - read, signer       dweller_administrator
+    /// - read, derived      server_administrator
+    /// - write              server
+    ///
+    /// Input: [SetHashInput]
+    SetServerBanner,
+
+    /// Server must be resized to [crate::state::StateVersion::V2].
+    ///
+    /// Accounts:
+    /// - read, signer       dweller_administrator
+    /// - read, derived      server_administrator
+    /// - write              server
+    ///
+    /// Input: [SetServerTagsInput]
+    SetServerTags,
+
+    /// Marks server to be shown or hidden in public directories.
+    /// Server must be resized to [crate::state::StateVersion::V2] and unlisted to become private.
+    ///
+    /// Accounts:
+    /// - read, signer       dweller_administrator
+    /// - read, derived      server_administrator
+    /// - write              server
+    ///
+    /// Input: [SetServerPublicInput]
+    SetServerPublic,
+
+    /// Adds public server to program wide directory.
+    /// Directory is created with [AddressTypeInput::ServerDirectory] with program as owner.
+    ///
+    /// Accounts:
+    /// - read, signer       owner, dweller or its delegate with admin scope
+    /// - write              server
+    /// - write, derived     server_directory
+    /// - write, derived     server_listing
+    ListServer,
+
+    /// Removes server from program wide directory.
+    ///
+    /// Accounts:
+    /// - read, signer       owner, dweller or its delegate with admin scope
+    /// - write              server
+    /// - write, derived     server_directory
+    /// - write, derived     server_listing of server
+    /// - write, derived     server_listing_last
+    UnlistServer,
+
+    /// Sets topic, slow mode and age restriction of channel.
+    ///
+    /// Accounts:
+    /// - read, signer       dweller_administrator
+    /// - read, derived      server_administrator for dweller_administrator
+    /// - read               server
+    /// - write, derived     server_channel
+    ///
+    /// Input: [UpdateChannelInput]
+    UpdateChannel,
+
+    /// Sets token allowing dwellers to join without invite.
+    /// Server must be resized to [crate::state::StateVersion::V2].
+    ///
+    /// Accounts:
+    /// - read, signer       dweller_administrator
+    /// - read, derived      server_administrator
+    /// - write              server
+    ///
+    /// Input: [SetServerGateInput]
+    SetServerGate,
 }
 
 /// Address type
@@ -196,6 +449,104 @@ pub enum AddressTypeInput {
     ServerGroup(u64),
     /// type
     GroupChannel(u64),
+    /// type
+    PinnedMessage(u64),
+    /// type
+    DirectChannel(u64),
+    /// type, owner must be program
+    ServerDirectory,
+    /// type
+    ServerListing(u64),
+}
+
+/// instruction data
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone, BorshSchema)]
+pub struct AddDelegateInput {
+    /// unix timestamp after which delegate is not valid, must be in future
+    pub expiry: i64,
+    /// non empty bit set of [crate::state::DwellerDelegate] scopes
+    pub scope: u8,
+}
+
+/// instruction data
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone, BorshSchema)]
+pub struct PinMessageInput {
+    /// Multihash of message content
+    pub content_hash: [u8; 64],
+    /// dweller who wrote message
+    pub author: Pubkey,
+}
+
+/// instruction data
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone, BorshSchema)]
+pub struct SetServerDescriptionInput {
+    /// description
+    pub description: [u8; 256],
+}
+
+/// instruction data
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone, BorshSchema)]
+pub struct SetServerTagsInput {
+    /// category names, empty are not set
+    pub tags: [[u8; 16]; 4],
+}
+
+/// instruction data
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone, BorshSchema)]
+pub struct SetServerPublicInput {
+    /// listed
+    pub public: bool,
+}
+
+/// instruction data
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone, BorshSchema)]
+pub struct SetServerGateInput {
+    /// [crate::state::TokenGateKind::None] removes gate
+    pub gate: TokenGate,
+}
+
+/// instruction data
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone, BorshSchema)]
+pub struct UpdateChannelInput {
+    /// utf8 text, zero padded
+    pub topic: [u8; 128],
+    /// seconds, 0 turns slow mode off, at most [crate::state::ServerChannel::MAX_SLOW_MODE]
+    pub slow_mode: u32,
+    /// age restricted
+    pub nsfw: bool,
+}
+
+/// Type of account to resize
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone, BorshSchema)]
+pub enum ResizeAccountInput {
+    /// type
+    Dweller,
+    /// type
+    Server,
+    /// type
+    ServerChannel,
+    /// type
+    ServerGroup,
+    /// type
+    GroupChannel,
+}
+
+/// What to reorder
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone, BorshSchema)]
+pub enum ReorderChannelsInput {
+    /// [crate::state::ServerChannel]
+    ServerChannels,
+    /// [crate::state::ServerGroup]
+    ServerGroups,
+    /// [crate::state::GroupChannel]
+    GroupChannels,
 }
 
 /// instruction data
@@ -270,6 +621,7 @@ pub fn initialize_dweller(
     let mut data = Instruction::InitializeDweller.try_to_vec()?;
     let mut input = input.try_to_vec()?;
     data.append(&mut input);
+    data.push(find_base_bump(&crate::id(), dweller));
     let accounts = vec![AccountMeta::new(*dweller, true)];
     Ok(solana_program::instruction::Instruction {
         program_id: crate::id(),
@@ -317,6 +669,7 @@ pub fn initialize_server(
     let mut data = Instruction::InitializeServer.try_to_vec()?;
     let mut input = input.try_to_vec()?;
     data.append(&mut input);
+    data.push(find_base_bump(&crate::id(), server));
     let accounts = vec![
         AccountMeta::new(*dweller_owner, true),
         AccountMeta::new(*server, true),
@@ -334,12 +687,17 @@ pub fn initialize_server(
 /// [Instruction::SetDwellerName]
 pub fn set_dweller_name(
     dweller: &Pubkey,
+    delegate: Option<&Pubkey>,
     input: &SetNameInput,
 ) -> Result<solana_program::instruction::Instruction, ProgramError> {
     let mut data = Instruction::SetDwellerName.try_to_vec()?;
     let mut input = input.try_to_vec()?;
     data.append(&mut input);
-    let accounts = vec![AccountMeta::new(*dweller, true)];
+    let mut accounts = vec![AccountMeta::new(*dweller, delegate.is_none())];
+
+    if let Some(delegate) = delegate {
+        accounts.push(AccountMeta::new_readonly(*delegate, true));
+    }
 
     Ok(solana_program::instruction::Instruction {
         program_id: crate::id(),
@@ -351,12 +709,17 @@ pub fn set_dweller_name(
 /// [Instruction::SetDwellerPhoto]
 pub fn set_dweller_photo(
     dweller: &Pubkey,
+    delegate: Option<&Pubkey>,
     input: &SetHashInput,
 ) -> Result<solana_program::instruction::Instruction, ProgramError> {
     let mut data = Instruction::SetDwellerPhoto.try_to_vec()?;
     let mut input = input.try_to_vec()?;
     data.append(&mut input);
-    let accounts = vec![AccountMeta::new(*dweller, true)];
+    let mut accounts = vec![AccountMeta::new(*dweller, delegate.is_none())];
+
+    if let Some(delegate) = delegate {
+        accounts.push(AccountMeta::new_readonly(*delegate, true));
+    }
 
     Ok(solana_program::instruction::Instruction {
         program_id: crate::id(),
@@ -368,12 +731,17 @@ pub fn set_dweller_photo(
 /// [Instruction::SetDwellerStatus]
 pub fn set_dweller_status(
     dweller: &Pubkey,
+    delegate: Option<&Pubkey>,
     input: &SetDwellerStatusInput,
 ) -> Result<solana_program::instruction::Instruction, ProgramError> {
     let mut data = Instruction::SetDwellerStatus.try_to_vec()?;
     let mut input = input.try_to_vec()?;
     data.append(&mut input);
-    let accounts = vec![AccountMeta::new(*dweller, true)];
+    let mut accounts = vec![AccountMeta::new(*dweller, delegate.is_none())];
+
+    if let Some(delegate) = delegate {
+        accounts.push(AccountMeta::new_readonly(*delegate, true));
+    }
 
     Ok(solana_program::instruction::Instruction {
         program_id: crate::id(),
@@ -393,6 +761,7 @@ pub fn add_channel(
     let mut data = Instruction::AddChannel.try_to_vec()?;
     let mut input = input.try_to_vec()?;
     data.append(&mut input);
+    data.push(find_base_bump(&crate::id(), server_channel));
     let accounts = vec![
         AccountMeta::new(*dweller_administrator, true),
         AccountMeta::new_readonly(*server_administrator, false),
@@ -414,9 +783,11 @@ pub fn delete_channel(
     server: &Pubkey,
     server_channel: &Pubkey,
     server_channel_last: &Pubkey,
+    moved_group_channel: Option<&Pubkey>,
+    moved_pins: &[(&Pubkey, &Pubkey)],
 ) -> Result<solana_program::instruction::Instruction, ProgramError> {
     let data = Instruction::DeleteChannel.try_to_vec()?;
-    let accounts = vec![
+    let mut accounts = vec![
         AccountMeta::new_readonly(*dweller_administrator, true),
         AccountMeta::new_readonly(*server_administrator, false),
         AccountMeta::new(*server, false),
@@ -424,6 +795,15 @@ pub fn delete_channel(
         AccountMeta::new(*server_channel_last, false),
     ];
 
+    if let Some(moved_group_channel) = moved_group_channel {
+        accounts.push(AccountMeta::new(*moved_group_channel, false));
+    }
+
+    for (pinned_message_last, pinned_message) in moved_pins {
+        accounts.push(AccountMeta::new(**pinned_message_last, false));
+        accounts.push(AccountMeta::new(**pinned_message, false));
+    }
+
     Ok(solana_program::instruction::Instruction {
         program_id: crate::id(),
         accounts,
@@ -437,18 +817,24 @@ pub fn create_group(
     server_administrator: &Pubkey,
     server: &Pubkey,
     server_group: &Pubkey,
+    server_group_parent: Option<&Pubkey>,
     input: &CreateGroupInput,
 ) -> Result<solana_program::instruction::Instruction, ProgramError> {
     let mut data = Instruction::CreateGroup.try_to_vec()?;
     let mut input = input.try_to_vec()?;
     data.append(&mut input);
-    let accounts = vec![
+    data.push(find_base_bump(&crate::id(), server_group));
+    let mut accounts = vec![
         AccountMeta::new(*dweller_administrator, true),
         AccountMeta::new_readonly(*server_administrator, false),
         AccountMeta::new(*server, false),
         AccountMeta::new(*server_group, false),
     ];
 
+    if let Some(server_group_parent) = server_group_parent {
+        accounts.push(AccountMeta::new(*server_group_parent, false));
+    }
+
     Ok(solana_program::instruction::Instruction {
         program_id: crate::id(),
         accounts,
@@ -463,7 +849,10 @@ pub fn delete_group(
     server: &Pubkey,
     server_group: &Pubkey,
     server_group_last: &Pubkey,
-    group_channels: &[&Pubkey],
+    server_group_parent: Option<&Pubkey>,
+    group_channels: &[(&Pubkey, &Pubkey)],
+    moved_nested_groups: &[&Pubkey],
+    moved_group_channels: &[(&Pubkey, &Pubkey, &Pubkey)],
 ) -> Result<solana_program::instruction::Instruction, ProgramError> {
     let data = Instruction::DeleteGroup.try_to_vec()?;
     let mut accounts = vec![
@@ -474,8 +863,23 @@ pub fn delete_group(
         AccountMeta::new(*server_group_last, false),
     ];
 
-    for account in group_channels {
-        accounts.push(AccountMeta::new(**account, false));
+    if let Some(server_group_parent) = server_group_parent {
+        accounts.push(AccountMeta::new(*server_group_parent, false));
+    }
+
+    for (group_channel, server_channel) in group_channels {
+        accounts.push(AccountMeta::new(**group_channel, false));
+        accounts.push(AccountMeta::new(**server_channel, false));
+    }
+
+    for server_group_nested in moved_nested_groups {
+        accounts.push(AccountMeta::new(**server_group_nested, false));
+    }
+
+    for (group_channel_last, group_channel, server_channel) in moved_group_channels {
+        accounts.push(AccountMeta::new(**group_channel_last, false));
+        accounts.push(AccountMeta::new(**group_channel, false));
+        accounts.push(AccountMeta::new(**server_channel, false));
     }
 
     Ok(solana_program::instruction::Instruction {
@@ -499,7 +903,7 @@ pub fn add_channel_to_group(
         AccountMeta::new_readonly(*server, false),
         AccountMeta::new_readonly(*dweller_administrator, true),
         AccountMeta::new_readonly(*server_administrator, false),
-        AccountMeta::new_readonly(*server_channel, false),
+        AccountMeta::new(*server_channel, false),
         AccountMeta::new(*server_group, false),
         AccountMeta::new(*group_channel, false),
     ];
@@ -517,6 +921,7 @@ pub fn remove_channel_from_group(
     dweller_administrator: &Pubkey,
     server_administrator: &Pubkey,
     server_group: &Pubkey,
+    server_channel: &Pubkey,
     group_channel: &Pubkey,
     group_channel_last: &Pubkey,
 ) -> Result<solana_program::instruction::Instruction, ProgramError> {
@@ -525,7 +930,8 @@ pub fn remove_channel_from_group(
         AccountMeta::new(*server, false),
         AccountMeta::new_readonly(*dweller_administrator, true),
         AccountMeta::new_readonly(*server_administrator, false),
-        AccountMeta::new_readonly(*server_group, false),
+        AccountMeta::new(*server_group, false),
+        AccountMeta::new(*server_channel, false),
         AccountMeta::new(*group_channel, false),
         AccountMeta::new(*group_channel_last, false),
     ];
@@ -588,16 +994,21 @@ pub fn join_server(
     server_member_status: &Pubkey,
     dweller: &Pubkey,
     dweller_server: &Pubkey,
+    delegate: Option<&Pubkey>,
 ) -> Result<solana_program::instruction::Instruction, ProgramError> {
     let data = Instruction::JoinServer.try_to_vec()?;
-    let accounts = vec![
+    let mut accounts = vec![
         AccountMeta::new(*server, false),
         AccountMeta::new(*server_member, false),
         AccountMeta::new_readonly(*server_member_status, false),
-        AccountMeta::new(*dweller, true),
+        AccountMeta::new(*dweller, delegate.is_none()),
         AccountMeta::new(*dweller_server, false),
     ];
 
+    if let Some(delegate) = delegate {
+        accounts.push(AccountMeta::new_readonly(*delegate, true));
+    }
+
     Ok(solana_program::instruction::Instruction {
         program_id: crate::id(),
         accounts,
@@ -613,17 +1024,22 @@ pub fn leave_server(
     dweller: &Pubkey,
     dweller_server: &Pubkey,
     dweller_server_last: &Pubkey,
+    delegate: Option<&Pubkey>,
 ) -> Result<solana_program::instruction::Instruction, ProgramError> {
     let data = Instruction::LeaveServer.try_to_vec()?;
-    let accounts = vec![
+    let mut accounts = vec![
         AccountMeta::new(*server, false),
         AccountMeta::new(*server_member, false),
         AccountMeta::new(*server_member_last, false),
-        AccountMeta::new(*dweller, true),
+        AccountMeta::new(*dweller, delegate.is_none()),
         AccountMeta::new(*dweller_server, false),
         AccountMeta::new(*dweller_server_last, false),
     ];
 
+    if let Some(delegate) = delegate {
+        accounts.push(AccountMeta::new_readonly(*delegate, true));
+    }
+
     Ok(solana_program::instruction::Instruction {
         program_id: crate::id(),
         accounts,
@@ -690,9 +1106,9 @@ pub fn set_server_name(
     let mut input = input.try_to_vec()?;
     data.append(&mut input);
     let accounts = vec![
-        AccountMeta::new(*server, true),
         AccountMeta::new_readonly(*dweller_administrator, true),
-        AccountMeta::new_readonly(*server_administrator, true),
+        AccountMeta::new_readonly(*server_administrator, false),
+        AccountMeta::new(*server, false),
     ];
 
     Ok(solana_program::instruction::Instruction {
@@ -713,9 +1129,506 @@ pub fn set_server_db(
     let mut input = input.try_to_vec()?;
     data.append(&mut input);
     let accounts = vec![
-        AccountMeta::new(*server, true),
         AccountMeta::new_readonly(*dweller_administrator, true),
-        AccountMeta::new_readonly(*server_administrator, true),
+        AccountMeta::new_readonly(*server_administrator, false),
+        AccountMeta::new(*server, false),
+    ];
+
+    Ok(solana_program::instruction::Instruction {
+        program_id: crate::id(),
+        accounts,
+        data,
+    })
+}
+
+/// [Instruction::ResizeAccount]
+pub fn resize_account(
+    payer: &Pubkey,
+    account: &Pubkey,
+    authority: &Pubkey,
+    input: &ResizeAccountInput,
+) -> Result<solana_program::instruction::Instruction, ProgramError> {
+    let mut data = Instruction::ResizeAccount.try_to_vec()?;
+    let mut input = input.try_to_vec()?;
+    data.append(&mut input);
+    data.push(find_base_bump(&crate::id(), account));
+    let accounts = vec![
+        AccountMeta::new(*payer, true),
+        AccountMeta::new(*account, false),
+        AccountMeta::new_readonly(*authority, true),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+
+    Ok(solana_program::instruction::Instruction {
+        program_id: crate::id(),
+        accounts,
+        data,
+    })
+}
+
+/// [Instruction::ResizeAccount] of [ServerChannel], [ServerGroup] or [GroupChannel] of `server`,
+/// `server_group` is container of group channel
+///
+/// [ServerChannel]: crate::state::ServerChannel
+/// [ServerGroup]: crate::state::ServerGroup
+/// [GroupChannel]: crate::state::GroupChannel
+pub fn resize_server_account(
+    payer: &Pubkey,
+    account: &Pubkey,
+    owner: &Pubkey,
+    server: &Pubkey,
+    server_group: Option<&Pubkey>,
+    input: &ResizeAccountInput,
+) -> Result<solana_program::instruction::Instruction, ProgramError> {
+    let mut instruction = resize_account(payer, account, owner, input)?;
+    if let Some(server_group) = server_group {
+        instruction
+            .accounts
+            .push(AccountMeta::new_readonly(*server_group, false));
+    }
+    instruction
+        .accounts
+        .push(AccountMeta::new_readonly(*server, false));
+    Ok(instruction)
+}
+
+/// [Instruction::ReorderChannels]
+pub fn reorder_channels(
+    dweller_administrator: &Pubkey,
+    server_administrator: &Pubkey,
+    server: &Pubkey,
+    server_group: Option<&Pubkey>,
+    in_order: &[&Pubkey],
+    input: &ReorderChannelsInput,
+) -> Result<solana_program::instruction::Instruction, ProgramError> {
+    let mut data = Instruction::ReorderChannels.try_to_vec()?;
+    let mut input = input.try_to_vec()?;
+    data.append(&mut input);
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*dweller_administrator, true),
+        AccountMeta::new_readonly(*server_administrator, false),
+        AccountMeta::new_readonly(*server, false),
+    ];
+
+    if let Some(server_group) = server_group {
+        accounts.push(AccountMeta::new_readonly(*server_group, false));
+    }
+
+    for account in in_order {
+        accounts.push(AccountMeta::new(**account, false));
+    }
+
+    Ok(solana_program::instruction::Instruction {
+        program_id: crate::id(),
+        accounts,
+        data,
+    })
+}
+
+/// [Instruction::AddDelegate]
+pub fn add_delegate(
+    dweller: &Pubkey,
+    delegate: &Pubkey,
+    input: &AddDelegateInput,
+) -> Result<solana_program::instruction::Instruction, ProgramError> {
+    let mut data = Instruction::AddDelegate.try_to_vec()?;
+    let mut input = input.try_to_vec()?;
+    data.append(&mut input);
+    let accounts = vec![
+        AccountMeta::new(*dweller, true),
+        AccountMeta::new(*delegate, true),
+    ];
+
+    Ok(solana_program::instruction::Instruction {
+        program_id: crate::id(),
+        accounts,
+        data,
+    })
+}
+
+/// [Instruction::RemoveDelegate]
+pub fn remove_delegate(
+    dweller: &Pubkey,
+    authority: &Pubkey,
+    delegate: &Pubkey,
+    destination: &Pubkey,
+) -> Result<solana_program::instruction::Instruction, ProgramError> {
+    let data = Instruction::RemoveDelegate.try_to_vec()?;
+    let accounts = vec![
+        AccountMeta::new(*dweller, false),
+        AccountMeta::new_readonly(*authority, true),
+        AccountMeta::new(*delegate, false),
+        AccountMeta::new(*destination, false),
+    ];
+
+    Ok(solana_program::instruction::Instruction {
+        program_id: crate::id(),
+        accounts,
+        data,
+    })
+}
+
+/// [Instruction::CloseDweller]
+pub fn close_dweller(
+    dweller: &Pubkey,
+    destination: &Pubkey,
+    delegates: &[&Pubkey],
+) -> Result<solana_program::instruction::Instruction, ProgramError> {
+    let data = Instruction::CloseDweller.try_to_vec()?;
+    let mut accounts = vec![
+        AccountMeta::new(*dweller, true),
+        AccountMeta::new(*destination, false),
+    ];
+
+    for account in delegates {
+        accounts.push(AccountMeta::new(**account, false));
+    }
+
+    Ok(solana_program::instruction::Instruction {
+        program_id: crate::id(),
+        accounts,
+        data,
+    })
+}
+
+/// [Instruction::PinMessage]
+pub fn pin_message(
+    dweller_administrator: &Pubkey,
+    server_administrator: &Pubkey,
+    server: &Pubkey,
+    server_channel: &Pubkey,
+    pinned_message: &Pubkey,
+    input: &PinMessageInput,
+) -> Result<solana_program::instruction::Instruction, ProgramError> {
+    let mut data = Instruction::PinMessage.try_to_vec()?;
+    let mut input = input.try_to_vec()?;
+    data.append(&mut input);
+    let accounts = vec![
+        AccountMeta::new_readonly(*dweller_administrator, true),
+        AccountMeta::new_readonly(*server_administrator, false),
+        AccountMeta::new_readonly(*server, false),
+        AccountMeta::new(*server_channel, false),
+        AccountMeta::new(*pinned_message, false),
+    ];
+
+    Ok(solana_program::instruction::Instruction {
+        program_id: crate::id(),
+        accounts,
+        data,
+    })
+}
+
+/// [Instruction::UnpinMessage]
+pub fn unpin_message(
+    dweller_administrator: &Pubkey,
+    server_administrator: &Pubkey,
+    server: &Pubkey,
+    server_channel: &Pubkey,
+    pinned_message: &Pubkey,
+    pinned_message_last: &Pubkey,
+) -> Result<solana_program::instruction::Instruction, ProgramError> {
+    let data = Instruction::UnpinMessage.try_to_vec()?;
+    let accounts = vec![
+        AccountMeta::new_readonly(*dweller_administrator, true),
+        AccountMeta::new_readonly(*server_administrator, false),
+        AccountMeta::new_readonly(*server, false),
+        AccountMeta::new(*server_channel, false),
+        AccountMeta::new(*pinned_message, false),
+        AccountMeta::new(*pinned_message_last, false),
+    ];
+
+    Ok(solana_program::instruction::Instruction {
+        program_id: crate::id(),
+        accounts,
+        data,
+    })
+}
+
+/// [Instruction::CreateDirectChannel]
+pub fn create_direct_channel(
+    dweller: &Pubkey,
+    direct_channel: &Pubkey,
+    delegate: Option<&Pubkey>,
+    participants: &[&Pubkey],
+    input: &SetHashInput,
+) -> Result<solana_program::instruction::Instruction, ProgramError> {
+    let mut data = Instruction::CreateDirectChannel.try_to_vec()?;
+    let mut input = input.try_to_vec()?;
+    data.append(&mut input);
+    let mut accounts = vec![
+        AccountMeta::new(*dweller, delegate.is_none()),
+        AccountMeta::new(*direct_channel, false),
+        AccountMeta::new_readonly(*delegate.unwrap_or(dweller), true),
+    ];
+
+    for participant in participants {
+        accounts.push(AccountMeta::new_readonly(**participant, false));
+    }
+
+    Ok(solana_program::instruction::Instruction {
+        program_id: crate::id(),
+        accounts,
+        data,
+    })
+}
+
+/// [Instruction::AddDirectChannelParticipant]
+pub fn add_direct_channel_participant(
+    dweller: &Pubkey,
+    direct_channel: &Pubkey,
+    participant: &Pubkey,
+    delegate: Option<&Pubkey>,
+) -> Result<solana_program::instruction::Instruction, ProgramError> {
+    let data = Instruction::AddDirectChannelParticipant.try_to_vec()?;
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*dweller, delegate.is_none()),
+        AccountMeta::new(*direct_channel, false),
+        AccountMeta::new_readonly(*participant, false),
+    ];
+
+    if let Some(delegate) = delegate {
+        accounts.push(AccountMeta::new_readonly(*delegate, true));
+    }
+
+    Ok(solana_program::instruction::Instruction {
+        program_id: crate::id(),
+        accounts,
+        data,
+    })
+}
+
+/// [Instruction::RemoveDirectChannelParticipant]
+pub fn remove_direct_channel_participant(
+    dweller: &Pubkey,
+    direct_channel: &Pubkey,
+    participant: &Pubkey,
+    direct_channel_last: &Pubkey,
+    delegate: Option<&Pubkey>,
+) -> Result<solana_program::instruction::Instruction, ProgramError> {
+    let data = Instruction::RemoveDirectChannelParticipant.try_to_vec()?;
+    let mut accounts = vec![
+        AccountMeta::new(*dweller, delegate.is_none()),
+        AccountMeta::new(*direct_channel, false),
+        AccountMeta::new_readonly(*participant, false),
+        AccountMeta::new(*direct_channel_last, false),
+    ];
+
+    if let Some(delegate) = delegate {
+        accounts.push(AccountMeta::new_readonly(*delegate, true));
+    }
+
+    Ok(solana_program::instruction::Instruction {
+        program_id: crate::id(),
+        accounts,
+        data,
+    })
+}
+
+/// [Instruction::LeaveDirectChannel]
+pub fn leave_direct_channel(
+    dweller: &Pubkey,
+    direct_channel: &Pubkey,
+    creator: &Pubkey,
+    direct_channel_last: &Pubkey,
+    delegate: Option<&Pubkey>,
+) -> Result<solana_program::instruction::Instruction, ProgramError> {
+    let data = Instruction::LeaveDirectChannel.try_to_vec()?;
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*dweller, delegate.is_none()),
+        AccountMeta::new(*direct_channel, false),
+        AccountMeta::new(*creator, false),
+        AccountMeta::new(*direct_channel_last, false),
+    ];
+
+    if let Some(delegate) = delegate {
+        accounts.push(AccountMeta::new_readonly(*delegate, true));
+    }
+
+    Ok(solana_program::instruction::Instruction {
+        program_id: crate::id(),
+        accounts,
+        data,
+    })
+}
+
+/// [Instruction::SetServerDescription]
+pub fn set_server_description(
+    dweller_administrator: &Pubkey,
+    server_administrator: &Pubkey,
+    server: &Pubkey,
+    input: &SetServerDescriptionInput,
+) -> Result<solana_program::instruction::Instruction, ProgramError> {
+    let mut data = Instruction::SetServerDescription.try_to_vec()?;
+    let mut input = input.try_to_vec()?;
+    data.append(&mut input);
+    let accounts = vec![
+        AccountMeta::new_readonly(*dweller_administrator, true),
+        AccountMeta::new_readonly(*server_administrator, false),
+        AccountMeta::new(*server, false),
+    ];
+
+    Ok(solana_program::instruction::Instruction {
+        program_id: crate::id(),
+        accounts,
+        data,
+    })
+}
+
+/// [Instruction::SetServerBanner]
+pub fn set_server_banner(
+    dweller_administrator: &Pubkey,
+    server_administrator: &Pubkey,
+    server: &Pubkey,
+    input: &SetHashInput,
+) -> Result<solana_program::instruction::Instruction, ProgramError> {
+    let mut data = Instruction::SetServerBanner.try_to_vec()?;
+    let mut input = input.try_to_vec()?;
+    data.append(&mut input);
+    let accounts = vec![
+        AccountMeta::new_readonly(*dweller_administrator, true),
+        AccountMeta::new_readonly(*server_administrator, false),
+        AccountMeta::new(*server, false),
+    ];
+
+    Ok(solana_program::instruction::Instruction {
+        program_id: crate::id(),
+        accounts,
+        data,
+    })
+}
+
+/// [Instruction::SetServerTags]
+pub fn set_server_tags(
+    dweller_administrator: &Pubkey,
+    server_administrator: &Pubkey,
+    server: &Pubkey,
+    input: &SetServerTagsInput,
+) -> Result<solana_program::instruction::Instruction, ProgramError> {
+    let mut data = Instruction::SetServerTags.try_to_vec()?;
+    let mut input = input.try_to_vec()?;
+    data.append(&mut input);
+    let accounts = vec![
+        AccountMeta::new_readonly(*dweller_administrator, true),
+        AccountMeta::new_readonly(*server_administrator, false),
+        AccountMeta::new(*server, false),
+    ];
+
+    Ok(solana_program::instruction::Instruction {
+        program_id: crate::id(),
+        accounts,
+        data,
+    })
+}
+
+/// [Instruction::SetServerPublic]
+pub fn set_server_public(
+    dweller_administrator: &Pubkey,
+    server_administrator: &Pubkey,
+    server: &Pubkey,
+    input: &SetServerPublicInput,
+) -> Result<solana_program::instruction::Instruction, ProgramError> {
+    let mut data = Instruction::SetServerPublic.try_to_vec()?;
+    let mut input = input.try_to_vec()?;
+    data.append(&mut input);
+    let accounts = vec![
+        AccountMeta::new_readonly(*dweller_administrator, true),
+        AccountMeta::new_readonly(*server_administrator, false),
+        AccountMeta::new(*server, false),
+    ];
+
+    Ok(solana_program::instruction::Instruction {
+        program_id: crate::id(),
+        accounts,
+        data,
+    })
+}
+
+/// [Instruction::ListServer]
+pub fn list_server(
+    owner: &Pubkey,
+    server: &Pubkey,
+    server_directory: &Pubkey,
+    server_listing: &Pubkey,
+) -> Result<solana_program::instruction::Instruction, ProgramError> {
+    let data = Instruction::ListServer.try_to_vec()?;
+    let accounts = vec![
+        AccountMeta::new_readonly(*owner, true),
+        AccountMeta::new(*server, false),
+        AccountMeta::new(*server_directory, false),
+        AccountMeta::new(*server_listing, false),
+    ];
+
+    Ok(solana_program::instruction::Instruction {
+        program_id: crate::id(),
+        accounts,
+        data,
+    })
+}
+
+/// [Instruction::UnlistServer]
+pub fn unlist_server(
+    owner: &Pubkey,
+    server: &Pubkey,
+    server_directory: &Pubkey,
+    server_listing: &Pubkey,
+    server_listing_last: &Pubkey,
+) -> Result<solana_program::instruction::Instruction, ProgramError> {
+    let data = Instruction::UnlistServer.try_to_vec()?;
+    let accounts = vec![
+        AccountMeta::new_readonly(*owner, true),
+        AccountMeta::new(*server, false),
+        AccountMeta::new(*server_directory, false),
+        AccountMeta::new(*server_listing, false),
+        AccountMeta::new(*server_listing_last, false),
+    ];
+
+    Ok(solana_program::instruction::Instruction {
+        program_id: crate::id(),
+        accounts,
+        data,
+    })
+}
+
+/// [Instruction::UpdateChannel]
+pub fn update_channel(
+    dweller_administrator: &Pubkey,
+    server_administrator: &Pubkey,
+    server: &Pubkey,
+    server_channel: &Pubkey,
+    input: &UpdateChannelInput,
+) -> Result<solana_program::instruction::Instruction, ProgramError> {
+    let mut data = Instruction::UpdateChannel.try_to_vec()?;
+    let mut input = input.try_to_vec()?;
+    data.append(&mut input);
+    let accounts = vec![
+        AccountMeta::new_readonly(*dweller_administrator, true),
+        AccountMeta::new_readonly(*server_administrator, false),
+        AccountMeta::new_readonly(*server, false),
+        AccountMeta::new(*server_channel, false),
+    ];
+
+    Ok(solana_program::instruction::Instruction {
+        program_id: crate::id(),
+        accounts,
+        data,
+    })
+}
+
+/// [Instruction::SetServerGate]
+pub fn set_server_gate(
+    dweller_administrator: &Pubkey,
+    server_administrator: &Pubkey,
+    server: &Pubkey,
+    input: &SetServerGateInput,
+) -> Result<solana_program::instruction::Instruction, ProgramError> {
+    let mut data = Instruction::SetServerGate.try_to_vec()?;
+    let mut input = input.try_to_vec()?;
+    data.append(&mut input);
+    let accounts = vec![
+        AccountMeta::new_readonly(*dweller_administrator, true),
+        AccountMeta::new_readonly(*server_administrator, false),
+        AccountMeta::new(*server, false),
     ];
 
     Ok(solana_program::instruction::Instruction {
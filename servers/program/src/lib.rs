@@ -1,6 +1,7 @@
 //! Satellite servers
 #![deny(missing_docs)]
 
+pub mod audit;
 mod borsh;
 pub mod error;
 
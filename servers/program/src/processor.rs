@@ -1,17 +1,22 @@
 //! Program state processor
 
 use solana_program::{
-    account_info::AccountInfo, entrypoint::ProgramResult, msg, program_error::ProgramError,
-    pubkey::Pubkey, rent::Rent, system_program, sysvar::Sysvar,
+    account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult, msg,
+    program_error::ProgramError, program_pack::Pack, pubkey::Pubkey, rent::Rent, system_program,
+    sysvar::Sysvar,
 };
 
 use super::borsh::*;
 
 use crate::{
+    audit::{AuditAction, AuditEvent},
     borsh::{AccountWithBorsh, BorshSerializeConst},
     error::Error,
     instruction::*,
-    program::{create_index_with_seed, create_seeded_rent_except_account, swap_accounts},
+    program::{
+        check_base_bump, create_index_with_bump, create_seeded_rent_except_account, find_base_bump,
+        move_child, push_position, remove_position, reorder_positions, swap_accounts,
+    },
     state::*,
 };
 
@@ -21,14 +26,18 @@ use super::prelude::*;
 pub struct Processor {}
 impl Processor {
     fn initialize_dweller<'a>(
-        _program_id: &Pubkey,
+        program_id: &Pubkey,
         dweller: &AccountInfo<'a>,
         input: &InitializeDwellerInput,
+        bump: u8,
     ) -> ProgramResult {
         let mut data = dweller.try_borrow_mut_data()?;
         let mut state = Dweller::deserialize_const(&data)?;
         if state.version == StateVersion::Uninitialized {
-            state.version = StateVersion::V1;
+            state.version = StateVersion::fitting(data.len(), Dweller::LEN);
+            if state.version == StateVersion::V2 {
+                state.extension.bump = check_base_bump(program_id, dweller.key, bump)?;
+            }
             state.name = input.name;
             state.photo_hash = input.hash;
             state.status = input.status;
@@ -40,42 +49,50 @@ impl Processor {
     }
 
     fn set_dweller_name<'a>(
-        _program_id: &Pubkey,
+        program_id: &Pubkey,
         dweller: &AccountInfo<'a>,
+        signer: &AccountInfo<'a>,
         input: &SetNameInput,
     ) -> ProgramResult {
-        if dweller.is_signer {
-            let mut data = dweller.try_borrow_mut_data()?;
-            let mut state = Dweller::deserialize_const(&data)?;
-            if state.version == StateVersion::V1 {
-                state.name = input.name;
-                state.serialize_const(&mut data)?;
-                Ok(())
-            } else {
-                Err(ProgramError::UninitializedAccount)
-            }
+        require_dweller(
+            program_id,
+            dweller.key,
+            signer,
+            DwellerDelegate::SCOPE_PROFILE,
+            ProgramError::MissingRequiredSignature,
+        )?;
+        let mut data = dweller.try_borrow_mut_data()?;
+        let mut state = Dweller::deserialize_const(&data)?;
+        if state.version.is_initialized() {
+            state.name = input.name;
+            state.serialize_const(&mut data)?;
+            Ok(())
         } else {
-            Err(ProgramError::MissingRequiredSignature)
+            Err(ProgramError::UninitializedAccount)
         }
     }
 
     fn set_dweller_photo<'a>(
-        _program_id: &Pubkey,
+        program_id: &Pubkey,
         dweller: &AccountInfo<'a>,
+        signer: &AccountInfo<'a>,
         input: &SetHashInput,
     ) -> ProgramResult {
-        if dweller.is_signer {
-            let mut data = dweller.try_borrow_mut_data()?;
-            let mut state = Dweller::deserialize_const(&data)?;
-            if state.version == StateVersion::V1 {
-                state.photo_hash = input.hash;
-                state.serialize_const(&mut data)?;
-                Ok(())
-            } else {
-                Err(ProgramError::UninitializedAccount)
-            }
+        require_dweller(
+            program_id,
+            dweller.key,
+            signer,
+            DwellerDelegate::SCOPE_PROFILE,
+            ProgramError::MissingRequiredSignature,
+        )?;
+        let mut data = dweller.try_borrow_mut_data()?;
+        let mut state = Dweller::deserialize_const(&data)?;
+        if state.version.is_initialized() {
+            state.photo_hash = input.hash;
+            state.serialize_const(&mut data)?;
+            Ok(())
         } else {
-            Err(ProgramError::MissingRequiredSignature)
+            Err(ProgramError::UninitializedAccount)
         }
     }
 
@@ -86,7 +103,7 @@ impl Processor {
         server: &AccountInfo<'a>,
         input: &SetNameInput,
     ) -> ProgramResult {
-        require_admin(
+        let actor = require_admin(
             program_id,
             dweller_administrator,
             server,
@@ -94,10 +111,10 @@ impl Processor {
         )?;
         let mut data = server.try_borrow_mut_data()?;
         let mut state = Server::deserialize_const(&data)?;
-        if state.version == StateVersion::V1 {
+        if state.version.is_initialized() {
             state.name = input.name;
             state.serialize_const(&mut data)?;
-            Ok(())
+            AuditEvent::emit(server.key, &actor, AuditAction::SetServerName, server.key)
         } else {
             Err(ProgramError::UninitializedAccount)
         }
@@ -110,7 +127,7 @@ impl Processor {
         server: &AccountInfo<'a>,
         input: &SetHashInput,
     ) -> ProgramResult {
-        require_admin(
+        let actor = require_admin(
             program_id,
             dweller_administrator,
             server,
@@ -118,32 +135,61 @@ impl Processor {
         )?;
         let mut data = server.try_borrow_mut_data()?;
         let mut state = Server::deserialize_const(&data)?;
-        if state.version == StateVersion::V1 {
+        if state.version.is_initialized() {
             state.db_hash = input.hash;
             state.serialize_const(&mut data)?;
-            Ok(())
+            AuditEvent::emit(server.key, &actor, AuditAction::SetServerDb, server.key)
+        } else {
+            Err(ProgramError::UninitializedAccount)
+        }
+    }
+
+    fn set_server_extension<'a, F: FnOnce(&mut ServerExtension) -> ProgramResult>(
+        program_id: &Pubkey,
+        dweller_administrator: &AccountInfo<'a>,
+        server_administrator: &AccountInfo<'a>,
+        server: &AccountInfo<'a>,
+        action: AuditAction,
+        update: F,
+    ) -> ProgramResult {
+        let actor = require_admin(
+            program_id,
+            dweller_administrator,
+            server,
+            server_administrator,
+        )?;
+        let mut data = server.try_borrow_mut_data()?;
+        let mut state = Server::deserialize_const(&data)?;
+        if state.version.is_initialized() {
+            update(&mut state.extension)?;
+            state.serialize_const(&mut data)?;
+            AuditEvent::emit(server.key, &actor, action, server.key)
         } else {
             Err(ProgramError::UninitializedAccount)
         }
     }
 
     fn set_dweller_status<'a>(
-        _program_id: &Pubkey,
+        program_id: &Pubkey,
         dweller: &AccountInfo<'a>,
+        signer: &AccountInfo<'a>,
         input: &SetDwellerStatusInput,
     ) -> ProgramResult {
-        if dweller.is_signer {
-            let mut data = dweller.try_borrow_mut_data()?;
-            let mut state = Dweller::deserialize_const(&data)?;
-            if state.version == StateVersion::V1 {
-                state.status = input.status;
-                state.serialize_const(&mut data)?;
-                Ok(())
-            } else {
-                Err(ProgramError::UninitializedAccount)
-            }
+        require_dweller(
+            program_id,
+            dweller.key,
+            signer,
+            DwellerDelegate::SCOPE_PROFILE,
+            ProgramError::MissingRequiredSignature,
+        )?;
+        let mut data = dweller.try_borrow_mut_data()?;
+        let mut state = Dweller::deserialize_const(&data)?;
+        if state.version.is_initialized() {
+            state.status = input.status;
+            state.serialize_const(&mut data)?;
+            Ok(())
         } else {
-            Err(ProgramError::MissingRequiredSignature)
+            Err(ProgramError::UninitializedAccount)
         }
     }
 
@@ -154,6 +200,7 @@ impl Processor {
         dweller_server: &AccountInfo<'a>,
         server_member: &AccountInfo<'a>,
         input: &InitializeServerInput,
+        bump: u8,
     ) -> ProgramResult {
         let mut dweller_data = dweller_owner.try_borrow_mut_data()?;
         let mut dweller_state = Dweller::deserialize_const(&dweller_data)?;
@@ -162,18 +209,24 @@ impl Processor {
         let mut server_state = Server::deserialize_const(&server_data)?;
 
         if server_state.version == StateVersion::Uninitialized {
+            server_state.version = StateVersion::fitting(server_data.len(), Server::LEN);
+            if server_state.version == StateVersion::V2 {
+                server_state.extension.bump = check_base_bump(program_id, server.key, bump)?;
+            }
             server_state.name = input.name;
-            let server_member_key = create_index_with_seed(
+            let server_member_key = create_index_with_bump(
                 program_id,
                 ServerMember::SEED,
                 server.key,
+                server_state.extension.bump.get(),
                 server_state.members,
             )?;
 
-            let dweller_server_key = create_index_with_seed(
+            let dweller_server_key = create_index_with_bump(
                 program_id,
                 DwellerServer::SEED,
                 dweller_owner.key,
+                dweller_state.extension.bump.get(),
                 dweller_state.servers,
             )?;
 
@@ -202,7 +255,6 @@ impl Processor {
                 dweller_state.servers = dweller_state.servers.error_increment()?;
                 dweller_state.serialize_const(&mut dweller_data)?;
 
-                server_state.version = StateVersion::V1;
                 server_state.owner = *dweller_owner.key;
                 server_state.members = server_state.members.error_increment()?;
                 server_state.name = input.name;
@@ -224,8 +276,9 @@ impl Processor {
         server: &AccountInfo<'a>,
         server_channel: &AccountInfo<'a>,
         input: &AddChannelInput,
+        bump: u8,
     ) -> ProgramResult {
-        require_admin(
+        let actor = require_admin(
             program_id,
             dweller_administrator,
             server,
@@ -234,10 +287,11 @@ impl Processor {
 
         let (mut server_data, mut server_state) = server.read_data_with_borsh_mut::<Server>()?;
 
-        let server_channel_key = create_index_with_seed(
+        let server_channel_key = create_index_with_bump(
             program_id,
             ServerChannel::SEED,
             server.key,
+            server_state.extension.bump.get(),
             server_state.channels,
         )?;
 
@@ -245,18 +299,33 @@ impl Processor {
             let (mut channel_data, mut channel_state) =
                 server_channel.read_data_with_borsh_mut::<ServerChannel>()?;
 
-            channel_state.version = StateVersion::V1;
+            channel_state.version = StateVersion::fitting(channel_data.len(), ServerChannel::LEN);
             channel_state.container = *server.key;
             channel_state.type_id = input.type_id;
             channel_state.name = input.name;
             channel_state.index = server_state.channels;
+            channel_state.extension.position = push_position(
+                &server_state.version,
+                &mut server_state.extension.next_channel_position,
+                server_state.channels,
+            )?;
+            if channel_state.version == StateVersion::V2 {
+                channel_state.extension.container_bump = server_state.extension.bump;
+                channel_state.extension.bump =
+                    check_base_bump(program_id, server_channel.key, bump)?;
+            }
 
             server_state.channels = server_state.channels.error_increment()?;
 
             channel_state.serialize_const(&mut channel_data)?;
             server_state.serialize_const(&mut server_data)?;
 
-            Ok(())
+            AuditEvent::emit(
+                server.key,
+                &actor,
+                AuditAction::AddChannel,
+                server_channel.key,
+            )
         } else {
             Err(Error::InvalidDerivedServerChannelAddress.into())
         }
@@ -268,9 +337,11 @@ impl Processor {
         server_administrator: &AccountInfo<'a>,
         server: &AccountInfo<'a>,
         server_group: &AccountInfo<'a>,
+        server_group_parent: Option<&AccountInfo<'a>>,
         input: &CreateGroupInput,
+        bump: u8,
     ) -> ProgramResult {
-        require_admin(
+        let actor = require_admin(
             program_id,
             dweller_administrator,
             server,
@@ -281,25 +352,50 @@ impl Processor {
         let (mut group_data, mut group_state) =
             server_group.read_data_with_borsh_mut::<ServerGroup>()?;
 
-        let server_group_key = create_index_with_seed(
+        let server_group_key = create_index_with_bump(
             program_id,
             ServerGroup::SEED,
             server.key,
+            server_state.extension.bump.get(),
             server_state.groups,
         )?;
 
         if server_group_key == *server_group.key {
+            if let Some(server_group_parent) = server_group_parent {
+                let (mut parent_data, mut parent_state) =
+                    server_group_parent.read_data_with_borsh_mut::<ServerGroup>()?;
+                require_server_group(program_id, server, server_group_parent, &parent_state)?;
+
+                parent_state.extension.groups = parent_state.extension.groups.error_increment()?;
+                parent_state.serialize_const(&mut parent_data)?;
+                group_state.extension.parent = *server_group_parent.key;
+            }
+
             group_state.container = *server.key;
             group_state.name = input.name;
-            group_state.version = StateVersion::V1;
+            group_state.version = StateVersion::fitting(group_data.len(), ServerGroup::LEN);
             group_state.index = server_state.groups;
+            group_state.extension.position = push_position(
+                &server_state.version,
+                &mut server_state.extension.next_group_position,
+                server_state.groups,
+            )?;
+            if group_state.version == StateVersion::V2 {
+                group_state.extension.container_bump = server_state.extension.bump;
+                group_state.extension.bump = check_base_bump(program_id, server_group.key, bump)?;
+            }
 
             server_state.groups = server_state.groups.error_increment()?;
 
             group_state.serialize_const(&mut group_data)?;
             server_state.serialize_const(&mut server_data)?;
 
-            Ok(())
+            AuditEvent::emit(
+                server.key,
+                &actor,
+                AuditAction::CreateGroup,
+                server_group.key,
+            )
         } else {
             Err(Error::InvalidDerivedServerGroupAddress.into())
         }
@@ -315,12 +411,13 @@ impl Processor {
         if owner.is_signer {
             let (mut server_data, mut server_state) =
                 server.read_data_with_borsh_mut::<Server>()?;
-            require_owner(&server_state, owner)?;
+            require_owner(program_id, &server_state, owner)?;
 
-            let administrator_key = create_index_with_seed(
+            let administrator_key = create_index_with_bump(
                 program_id,
                 ServerAdministrator::SEED,
                 server.key,
+                server_state.extension.bump.get(),
                 server_state.administrators,
             )?;
 
@@ -337,7 +434,12 @@ impl Processor {
                     server_state.administrators = server_state.administrators.error_increment()?;
                     server_state.serialize_const(&mut server_data)?;
 
-                    Ok(())
+                    AuditEvent::emit(
+                        server.key,
+                        &server_state.owner,
+                        AuditAction::AddAdmin,
+                        dweller.key,
+                    )
                 } else {
                     Err(ProgramError::AccountAlreadyInitialized)
                 }
@@ -350,46 +452,47 @@ impl Processor {
     }
 
     fn remove_admin<'a>(
-        _program_id: &Pubkey,
+        program_id: &Pubkey,
         owner: &AccountInfo<'a>,
         server: &AccountInfo<'a>,
         server_admin: &AccountInfo<'a>,
         server_admin_last: &AccountInfo<'a>,
     ) -> ProgramResult {
         let (mut server_data, mut server_state) = server.read_data_with_borsh_mut::<Server>()?;
-        if server_state.owner == *owner.key && owner.is_signer {
-            let server_admin_state = server_admin.read_data_with_borsh::<ServerAdministrator>()?;
-            let server_admin_key = crate::program::create_index_with_seed(
-                &crate::id(),
-                ServerAdministrator::SEED,
-                server.key,
-                server_admin_state.index,
-            )?;
+        require_owner(program_id, &server_state, owner)?;
 
-            let server_admin_last_key = crate::program::create_index_with_seed(
-                &crate::id(),
-                ServerAdministrator::SEED,
-                server.key,
-                server_state.administrators.error_decrement()?,
-            )?;
+        let server_admin_state = server_admin.read_data_with_borsh::<ServerAdministrator>()?;
+        let server_admin_key = crate::program::create_index_with_bump(
+            &crate::id(),
+            ServerAdministrator::SEED,
+            server.key,
+            server_state.extension.bump.get(),
+            server_admin_state.index,
+        )?;
 
-            if server_admin_last_key == *server_admin_last.key
-                && server_admin_key == *server_admin.key
-            {
-                crate::program::swap_accounts::<ServerAdministrator>(
-                    server_admin,
-                    server_admin_last,
-                )?;
+        let server_admin_last_key = crate::program::create_index_with_bump(
+            &crate::id(),
+            ServerAdministrator::SEED,
+            server.key,
+            server_state.extension.bump.get(),
+            server_state.administrators.error_decrement()?,
+        )?;
 
-                server_state.administrators = server_state.administrators.error_decrement()?;
-                server_state.serialize_const(&mut server_data)?;
+        if server_admin_last_key == *server_admin_last.key && server_admin_key == *server_admin.key
+        {
+            crate::program::swap_accounts::<ServerAdministrator>(server_admin, server_admin_last)?;
 
-                Ok(())
-            } else {
-                Err(Error::InvalidDerivedServerAdministratorAddress.into())
-            }
+            server_state.administrators = server_state.administrators.error_decrement()?;
+            server_state.serialize_const(&mut server_data)?;
+
+            AuditEvent::emit(
+                server.key,
+                &server_state.owner,
+                AuditAction::RemoveAdmin,
+                &server_admin_state.dweller,
+            )
         } else {
-            Err(ProgramError::MissingRequiredSignature)
+            Err(Error::InvalidDerivedServerAdministratorAddress.into())
         }
     }
 
@@ -401,7 +504,7 @@ impl Processor {
         member_status: &AccountInfo<'a>,
         member_status_last: &AccountInfo<'a>,
     ) -> ProgramResult {
-        require_admin(
+        let actor = require_admin(
             program_id,
             dweller_administrator,
             server,
@@ -411,17 +514,19 @@ impl Processor {
         let (mut server_data, mut server_state) = server.read_data_with_borsh_mut::<Server>()?;
         let member_status_state = member_status.read_data_with_borsh::<ServerMemberStatus>()?;
 
-        let member_status_last_key = create_index_with_seed(
+        let member_status_last_key = create_index_with_bump(
             program_id,
             ServerMemberStatus::SEED,
             server.key,
+            server_state.extension.bump.get(),
             server_state.member_statuses.error_decrement()?,
         )?;
 
-        let member_status_key = create_index_with_seed(
+        let member_status_key = create_index_with_bump(
             program_id,
             ServerMemberStatus::SEED,
             server.key,
+            server_state.extension.bump.get(),
             member_status_state.index,
         )?;
 
@@ -432,7 +537,12 @@ impl Processor {
 
             server_state.member_statuses = server_state.member_statuses.error_decrement()?;
             server_state.serialize_const(&mut server_data)?;
-            Ok(())
+            AuditEvent::emit(
+                server.key,
+                &actor,
+                AuditAction::RevokeInviteServer,
+                &member_status_state.dweller,
+            )
         } else {
             Err(Error::InvalidDerivedServerMemberStatusAddress.into())
         }
@@ -446,7 +556,7 @@ impl Processor {
         dweller: &AccountInfo<'a>,
         member_status: &AccountInfo<'a>,
     ) -> ProgramResult {
-        require_admin(
+        let actor = require_admin(
             program_id,
             dweller_administrator,
             server,
@@ -455,10 +565,11 @@ impl Processor {
 
         let (mut server_data, mut server_state) = server.read_data_with_borsh_mut::<Server>()?;
 
-        let member_status_key = create_index_with_seed(
+        let member_status_key = create_index_with_bump(
             program_id,
             ServerMemberStatus::SEED,
             server.key,
+            server_state.extension.bump.get(),
             server_state.member_statuses,
         )?;
 
@@ -476,7 +587,7 @@ impl Processor {
             server_state.serialize_const(&mut server_data)?;
             member_status_state.serialize_const(&mut member_status_data)?;
 
-            Ok(())
+            AuditEvent::emit(server.key, &actor, AuditAction::InviteToServer, dweller.key)
         } else {
             Err(Error::InvalidDerivedServerMemberStatusAddress.into())
         }
@@ -498,11 +609,13 @@ impl Processor {
         if system_program::id() != *_system_program.key {
             return Err(ProgramError::InvalidSeeds);
         }
+        let bump = container_base_bump(program_id, owner_account_info, input)?;
         match input {
             AddressTypeInput::DwellerServer(index) => create_seeded_rent_except_account(
                 DwellerServer::SEED,
                 owner_account_info,
                 index,
+                bump,
                 base_account_info,
                 account_to_create_info,
                 payer_account_info,
@@ -514,6 +627,7 @@ impl Processor {
                 ServerMemberStatus::SEED,
                 owner_account_info,
                 index,
+                bump,
                 base_account_info,
                 account_to_create_info,
                 payer_account_info,
@@ -525,6 +639,7 @@ impl Processor {
                 ServerAdministrator::SEED,
                 owner_account_info,
                 index,
+                bump,
                 base_account_info,
                 account_to_create_info,
                 payer_account_info,
@@ -536,6 +651,7 @@ impl Processor {
                 ServerMember::SEED,
                 owner_account_info,
                 index,
+                bump,
                 base_account_info,
                 account_to_create_info,
                 payer_account_info,
@@ -547,6 +663,7 @@ impl Processor {
                 ServerChannel::SEED,
                 owner_account_info,
                 index,
+                bump,
                 base_account_info,
                 account_to_create_info,
                 payer_account_info,
@@ -558,6 +675,7 @@ impl Processor {
                 ServerGroup::SEED,
                 owner_account_info,
                 index,
+                bump,
                 base_account_info,
                 account_to_create_info,
                 payer_account_info,
@@ -569,6 +687,7 @@ impl Processor {
                 GroupChannel::SEED,
                 owner_account_info,
                 index,
+                bump,
                 base_account_info,
                 account_to_create_info,
                 payer_account_info,
@@ -576,141 +695,977 @@ impl Processor {
                 GroupChannel::LEN,
                 program_id,
             ),
+            AddressTypeInput::PinnedMessage(index) => create_seeded_rent_except_account(
+                PinnedMessage::SEED,
+                owner_account_info,
+                index,
+                bump,
+                base_account_info,
+                account_to_create_info,
+                payer_account_info,
+                rent,
+                PinnedMessage::LEN,
+                program_id,
+            ),
+            AddressTypeInput::ServerDirectory => {
+                if owner_account_info.key != program_id {
+                    return Err(ProgramError::InvalidSeeds);
+                }
+                create_seeded_rent_except_account(
+                    ServerDirectory::SEED,
+                    owner_account_info,
+                    &0,
+                    bump,
+                    base_account_info,
+                    account_to_create_info,
+                    payer_account_info,
+                    rent,
+                    ServerDirectory::LEN,
+                    program_id,
+                )
+            }
+            AddressTypeInput::ServerListing(index) => create_seeded_rent_except_account(
+                ServerListing::SEED,
+                owner_account_info,
+                index,
+                bump,
+                base_account_info,
+                account_to_create_info,
+                payer_account_info,
+                rent,
+                ServerListing::LEN,
+                program_id,
+            ),
+            AddressTypeInput::DirectChannel(index) => create_seeded_rent_except_account(
+                DirectChannel::SEED,
+                owner_account_info,
+                index,
+                bump,
+                base_account_info,
+                account_to_create_info,
+                payer_account_info,
+                rent,
+                DirectChannel::LEN,
+                program_id,
+            ),
         }
     }
 
-    /// Processes an instruction
-    pub fn process_instruction(
+    #[allow(clippy::too_many_arguments)]
+    fn resize_account<'a>(
         program_id: &Pubkey,
-        accounts: &[AccountInfo],
-        input: &[u8],
+        payer: &AccountInfo<'a>,
+        account: &AccountInfo<'a>,
+        authority: &AccountInfo<'a>,
+        rent: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+        containers: &[AccountInfo<'a>],
+        input: &ResizeAccountInput,
+        bump: u8,
     ) -> ProgramResult {
-        let instruction = Instruction::try_from_slice(&input[0..1])?;
-        match instruction {
-            Instruction::CreateDerivedAccount => {
-                msg!("Instruction: CreateDerivedAccount");
-                match accounts {
-                    [payer_account_info, owner_account_info, base_account_info, account_to_create_info, rent_account_info, system_program, ..] =>
-                    {
-                        let input =
-                            super::instruction::AddressTypeInput::deserialize_const(&input[1..])?;
-                        Self::create_derived_address(
-                            program_id,
-                            payer_account_info,
-                            owner_account_info,
-                            base_account_info,
-                            account_to_create_info,
-                            rent_account_info,
-                            system_program,
-                            &input,
-                        )
-                    }
-                    _ => Err(ProgramError::NotEnoughAccountKeys),
+        if account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let (len, migrated, container_bump) = match input {
+            ResizeAccountInput::Dweller => {
+                let state: Dweller = account.read_data_with_borsh()?;
+                if !state.version.is_initialized() {
+                    return Err(ProgramError::UninitializedAccount);
                 }
+                require_dweller(
+                    program_id,
+                    account.key,
+                    authority,
+                    DwellerDelegate::SCOPE_PROFILE,
+                    ProgramError::MissingRequiredSignature,
+                )?;
+                (
+                    Dweller::LEN,
+                    state.extension.bump.get().is_some(),
+                    BaseBump::default(),
+                )
             }
-            Instruction::InitializeDweller => {
-                msg!("Instruction: InitializeDweller");
-                match accounts {
-                    [dweller, ..] => {
-                        let input = super::instruction::InitializeDwellerInput::deserialize_const(
-                            &input[1..],
-                        )?;
-
-                        Self::initialize_dweller(program_id, dweller, &input)
-                    }
-                    _ => Err(ProgramError::NotEnoughAccountKeys),
+            ResizeAccountInput::Server => {
+                let state: Server = account.read_data_with_borsh()?;
+                if !state.version.is_initialized() {
+                    return Err(ProgramError::UninitializedAccount);
                 }
+                require_owner(program_id, &state, authority)?;
+                (
+                    Server::LEN,
+                    state.extension.bump.get().is_some(),
+                    BaseBump::default(),
+                )
             }
-            Instruction::InitializeServer => {
-                msg!("Instruction: InitializeServer");
-                match accounts {
-                    [dweller_owner, server, dweller_server, server_member, ..] => {
-                        let input = super::instruction::InitializeServerInput::deserialize_const(
-                            &input[1..],
-                        )?;
-
-                        Self::initialize_server(
-                            program_id,
-                            dweller_owner,
-                            server,
-                            dweller_server,
-                            server_member,
-                            &input,
-                        )
-                    }
-                    _ => Err(ProgramError::NotEnoughAccountKeys),
+            ResizeAccountInput::ServerChannel => {
+                let server = containers
+                    .first()
+                    .ok_or(ProgramError::NotEnoughAccountKeys)?;
+                let server_state: Server = server.read_data_with_borsh()?;
+                let state: ServerChannel = account.read_data_with_borsh()?;
+                require_server_channel(program_id, server, account, &state)?;
+                require_owner(program_id, &server_state, authority)?;
+                (
+                    ServerChannel::LEN,
+                    state.extension.bump.get().is_some(),
+                    server_state.extension.bump,
+                )
+            }
+            ResizeAccountInput::ServerGroup => {
+                let server = containers
+                    .first()
+                    .ok_or(ProgramError::NotEnoughAccountKeys)?;
+                let server_state: Server = server.read_data_with_borsh()?;
+                let state: ServerGroup = account.read_data_with_borsh()?;
+                require_server_group(program_id, server, account, &state)?;
+                require_owner(program_id, &server_state, authority)?;
+                (
+                    ServerGroup::LEN,
+                    state.extension.bump.get().is_some(),
+                    server_state.extension.bump,
+                )
+            }
+            ResizeAccountInput::GroupChannel => {
+                let (server_group, server) = match containers {
+                    [server_group, server, ..] => (server_group, server),
+                    _ => return Err(ProgramError::NotEnoughAccountKeys),
+                };
+                let server_state: Server = server.read_data_with_borsh()?;
+                let server_group_state: ServerGroup = server_group.read_data_with_borsh()?;
+                require_server_group(program_id, server, server_group, &server_group_state)?;
+                let state: GroupChannel = account.read_data_with_borsh()?;
+                let group_channel_key = create_index_with_bump(
+                    program_id,
+                    GroupChannel::SEED,
+                    server_group.key,
+                    server_group_state.extension.bump.get(),
+                    state.index,
+                )?;
+                if group_channel_key != *account.key || !state.version.is_initialized() {
+                    return Err(Error::InvalidDerivedGroupChannelAddress.into());
                 }
+                require_owner(program_id, &server_state, authority)?;
+                (
+                    GroupChannel::LEN,
+                    state.extension.container_bump.get().is_some(),
+                    server_group_state.extension.bump,
+                )
             }
-            Instruction::SetDwellerName => {
-                msg!("Instruction: SetDwellerName");
-                match accounts {
-                    [dweller, ..] => {
-                        let input =
-                            super::instruction::SetNameInput::deserialize_const(&input[1..])?;
+        };
 
-                        Self::set_dweller_name(program_id, dweller, &input)
-                    }
-                    _ => Err(ProgramError::NotEnoughAccountKeys),
-                }
+        // accounts resized before bump was stored only get bump
+        let resized = account.data_len() as u64 >= len;
+        if resized && migrated {
+            return Err(Error::AccountAlreadyResized.into());
+        }
+
+        if !resized {
+            let required_lamports = Rent::from_account_info(rent)?
+                .minimum_balance(len as usize)
+                .saturating_sub(account.lamports());
+            if required_lamports > 0 {
+                solana_program::program::invoke(
+                    &solana_program::system_instruction::transfer(
+                        payer.key,
+                        account.key,
+                        required_lamports,
+                    ),
+                    &[payer.clone(), account.clone(), system_program.clone()],
+                )?;
             }
-            Instruction::SetDwellerPhoto => {
-                msg!("Instruction: SetDwellerPhoto");
-                match accounts {
-                    [dweller, ..] => {
-                        let input =
-                            super::instruction::SetHashInput::deserialize_const(&input[1..])?;
 
-                        Self::set_dweller_photo(program_id, dweller, &input)
-                    }
-                    _ => Err(ProgramError::NotEnoughAccountKeys),
+            account.realloc(len as usize, true)?;
+        }
+
+        let mut data = account.try_borrow_mut_data()?;
+        match input {
+            ResizeAccountInput::Dweller => {
+                let mut state = Dweller::deserialize_const(&data)?;
+                state.version = StateVersion::V2;
+                state.extension.bump = check_base_bump(program_id, account.key, bump)?;
+                state.serialize_const(&mut data)?;
+            }
+            ResizeAccountInput::Server => {
+                let mut state = Server::deserialize_const(&data)?;
+                state.version = StateVersion::V2;
+                state.extension.bump = check_base_bump(program_id, account.key, bump)?;
+                state.serialize_const(&mut data)?;
+            }
+            // entities were shown in order of index before positions were stored
+            ResizeAccountInput::ServerChannel => {
+                let mut state = ServerChannel::deserialize_const(&data)?;
+                if state.version != StateVersion::V2 {
+                    state.version = StateVersion::V2;
+                    state.extension.position = state.index;
                 }
+                state.extension.container_bump = container_bump;
+                state.extension.bump = check_base_bump(program_id, account.key, bump)?;
+                state.serialize_const(&mut data)?;
             }
-            Instruction::SetDwellerStatus => {
-                msg!("Instruction: SetDwellerStatus");
-                match accounts {
-                    [dweller, ..] => {
-                        let input = super::instruction::SetDwellerStatusInput::deserialize_const(
-                            &input[1..],
-                        )?;
-
-                        Self::set_dweller_status(program_id, dweller, &input)
-                    }
-                    _ => Err(ProgramError::NotEnoughAccountKeys),
+            ResizeAccountInput::ServerGroup => {
+                let mut state = ServerGroup::deserialize_const(&data)?;
+                if state.version != StateVersion::V2 {
+                    state.version = StateVersion::V2;
+                    state.extension.position = state.index;
                 }
+                state.extension.container_bump = container_bump;
+                state.extension.bump = check_base_bump(program_id, account.key, bump)?;
+                state.serialize_const(&mut data)?;
             }
-            Instruction::AddChannel => {
-                msg!("Instruction: AddChannel");
-                match accounts {
-                    [dweller_administrator, server_administrator, server, server_channel, ..] => {
-                        let input =
-                            super::instruction::AddChannelInput::deserialize_const(&input[1..])?;
-
-                        Self::add_channel(
-                            program_id,
-                            dweller_administrator,
-                            server_administrator,
-                            server,
-                            server_channel,
-                            &input,
-                        )
-                    }
-                    _ => Err(ProgramError::NotEnoughAccountKeys),
+            ResizeAccountInput::GroupChannel => {
+                let mut state = GroupChannel::deserialize_const(&data)?;
+                if state.version != StateVersion::V2 {
+                    state.version = StateVersion::V2;
+                    state.extension.position = state.index;
                 }
+                state.extension.container_bump = container_bump;
+                state.serialize_const(&mut data)?;
             }
-            Instruction::CreateGroup => {
-                msg!("Instruction: CreateGroup");
-                match accounts {
-                    [dweller, server_administrator, server, server_group, ..] => {
-                        let input =
-                            super::instruction::CreateGroupInput::deserialize_const(&input[1..])?;
+        }
 
-                        Self::create_group(
-                            program_id,
-                            dweller,
+        Ok(())
+    }
+
+    fn add_delegate<'a>(
+        program_id: &Pubkey,
+        dweller: &AccountInfo<'a>,
+        delegate: &AccountInfo<'a>,
+        input: &AddDelegateInput,
+    ) -> ProgramResult {
+        if !dweller.is_signer || !delegate.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        if dweller.owner != program_id || delegate.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let (mut dweller_data, mut dweller_state) =
+            dweller.read_data_with_borsh_mut::<Dweller>()?;
+        if !dweller_state.version.is_initialized() {
+            return Err(ProgramError::UninitializedAccount);
+        }
+        if dweller_state.version != StateVersion::V2 {
+            return Err(Error::AccountNotResized.into());
+        }
+
+        if input.expiry <= Clock::get()?.unix_timestamp {
+            return Err(Error::DelegateExpired.into());
+        }
+        if input.scope == 0 || input.scope & !DwellerDelegate::SCOPE_ALL != 0 {
+            return Err(Error::InvalidDelegateScope.into());
+        }
+
+        let (mut delegate_data, mut delegate_state) =
+            delegate.read_data_with_borsh_mut::<DwellerDelegate>()?;
+        if delegate_state.version.is_initialized() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        delegate_state.version = StateVersion::V1;
+        delegate_state.dweller = *dweller.key;
+        delegate_state.expiry = input.expiry;
+        delegate_state.scope = input.scope;
+        delegate_state.serialize_const(&mut delegate_data)?;
+
+        dweller_state.extension.delegates = dweller_state.extension.delegates.error_increment()?;
+        dweller_state.serialize_const(&mut dweller_data)?;
+
+        Ok(())
+    }
+
+    fn remove_delegate<'a>(
+        program_id: &Pubkey,
+        dweller: &AccountInfo<'a>,
+        authority: &AccountInfo<'a>,
+        delegate: &AccountInfo<'a>,
+        destination: &AccountInfo<'a>,
+    ) -> ProgramResult {
+        if !authority.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        if dweller.owner != program_id || delegate.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let delegate_state: DwellerDelegate = delegate.read_data_with_borsh()?;
+        if !delegate_state.version.is_initialized() {
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        if delegate_state.dweller != *dweller.key
+            || (authority.key != dweller.key && authority.key != delegate.key)
+        {
+            return Err(Error::InvalidDelegate.into());
+        }
+
+        let (mut dweller_data, mut dweller_state) =
+            dweller.read_data_with_borsh_mut::<Dweller>()?;
+        dweller_state.extension.delegates = dweller_state.extension.delegates.error_decrement()?;
+        dweller_state.serialize_const(&mut dweller_data)?;
+
+        close_account(delegate, destination)
+    }
+
+    fn close_dweller<'a>(
+        program_id: &Pubkey,
+        dweller: &AccountInfo<'a>,
+        destination: &AccountInfo<'a>,
+        delegates: &[AccountInfo<'a>],
+    ) -> ProgramResult {
+        if !dweller.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        if dweller.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let dweller_state: Dweller = dweller.read_data_with_borsh()?;
+        if !dweller_state.version.is_initialized() {
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        if dweller_state.servers > 0 {
+            return Err(Error::DwellerHasServers.into());
+        }
+
+        if dweller_state.extension.direct_channels > 0 {
+            return Err(Error::DwellerHasDirectChannels.into());
+        }
+
+        for delegate in delegates {
+            Self::remove_delegate(program_id, dweller, dweller, delegate, destination)?;
+        }
+
+        let dweller_state: Dweller = dweller.read_data_with_borsh()?;
+        if dweller_state.extension.delegates > 0 {
+            return Err(Error::DwellerHasDelegates.into());
+        }
+
+        close_account(dweller, destination)
+    }
+
+    fn pin_message<'a>(
+        program_id: &Pubkey,
+        dweller_administrator: &AccountInfo<'a>,
+        server_administrator: &AccountInfo<'a>,
+        server: &AccountInfo<'a>,
+        server_channel: &AccountInfo<'a>,
+        pinned_message: &AccountInfo<'a>,
+        input: &PinMessageInput,
+    ) -> ProgramResult {
+        let actor = require_admin(
+            program_id,
+            dweller_administrator,
+            server,
+            server_administrator,
+        )?;
+        let server_administrator_state: ServerAdministrator =
+            server_administrator.read_data_with_borsh()?;
+
+        let (mut channel_data, mut channel_state) =
+            server_channel.read_data_with_borsh_mut::<ServerChannel>()?;
+        require_server_channel(program_id, server, server_channel, &channel_state)?;
+
+        let pinned_message_key = create_index_with_bump(
+            program_id,
+            PinnedMessage::SEED,
+            server_channel.key,
+            channel_state.extension.bump.get(),
+            channel_state.extension.pins,
+        )?;
+
+        if pinned_message_key == *pinned_message.key {
+            let (mut pinned_message_data, mut pinned_message_state) =
+                pinned_message.read_data_with_borsh_mut::<PinnedMessage>()?;
+
+            if pinned_message_state.version == StateVersion::Uninitialized {
+                pinned_message_state.version = StateVersion::V1;
+                pinned_message_state.container = *server_channel.key;
+                pinned_message_state.index = channel_state.extension.pins;
+                pinned_message_state.content_hash = input.content_hash;
+                pinned_message_state.author = input.author;
+                pinned_message_state.pinned_by = server_administrator_state.dweller;
+                pinned_message_state.serialize_const(&mut pinned_message_data)?;
+
+                channel_state.extension.pins = channel_state.extension.pins.error_increment()?;
+                channel_state.serialize_const(&mut channel_data)?;
+
+                AuditEvent::emit(
+                    server.key,
+                    &actor,
+                    AuditAction::PinMessage,
+                    pinned_message.key,
+                )
+            } else {
+                Err(ProgramError::AccountAlreadyInitialized)
+            }
+        } else {
+            Err(Error::InvalidDerivedPinnedMessageAddress.into())
+        }
+    }
+
+    fn unpin_message<'a>(
+        program_id: &Pubkey,
+        dweller_administrator: &AccountInfo<'a>,
+        server_administrator: &AccountInfo<'a>,
+        server: &AccountInfo<'a>,
+        server_channel: &AccountInfo<'a>,
+        pinned_message: &AccountInfo<'a>,
+        pinned_message_last: &AccountInfo<'a>,
+    ) -> ProgramResult {
+        let actor = require_admin(
+            program_id,
+            dweller_administrator,
+            server,
+            server_administrator,
+        )?;
+
+        let (mut channel_data, mut channel_state) =
+            server_channel.read_data_with_borsh_mut::<ServerChannel>()?;
+        require_server_channel(program_id, server, server_channel, &channel_state)?;
+
+        let pinned_message_state: PinnedMessage = pinned_message.read_data_with_borsh()?;
+        let pinned_message_key = create_index_with_bump(
+            program_id,
+            PinnedMessage::SEED,
+            server_channel.key,
+            channel_state.extension.bump.get(),
+            pinned_message_state.index,
+        )?;
+
+        let pinned_message_last_key = create_index_with_bump(
+            program_id,
+            PinnedMessage::SEED,
+            server_channel.key,
+            channel_state.extension.bump.get(),
+            channel_state.extension.pins.error_decrement()?,
+        )?;
+
+        if pinned_message_key == *pinned_message.key
+            && pinned_message_last_key == *pinned_message_last.key
+        {
+            swap_accounts::<PinnedMessage>(pinned_message, pinned_message_last)?;
+
+            channel_state.extension.pins = channel_state.extension.pins.error_decrement()?;
+            channel_state.serialize_const(&mut channel_data)?;
+
+            AuditEvent::emit(
+                server.key,
+                &actor,
+                AuditAction::UnpinMessage,
+                pinned_message.key,
+            )
+        } else {
+            Err(Error::InvalidDerivedPinnedMessageAddress.into())
+        }
+    }
+
+    fn update_channel<'a>(
+        program_id: &Pubkey,
+        dweller_administrator: &AccountInfo<'a>,
+        server_administrator: &AccountInfo<'a>,
+        server: &AccountInfo<'a>,
+        server_channel: &AccountInfo<'a>,
+        input: &UpdateChannelInput,
+    ) -> ProgramResult {
+        let actor = require_admin(
+            program_id,
+            dweller_administrator,
+            server,
+            server_administrator,
+        )?;
+
+        let (mut channel_data, mut channel_state) =
+            server_channel.read_data_with_borsh_mut::<ServerChannel>()?;
+        require_server_channel(program_id, server, server_channel, &channel_state)?;
+
+        if ServerChannel::read_topic(&input.topic).is_none() {
+            return Err(Error::InvalidChannelTopic.into());
+        }
+
+        if input.slow_mode > ServerChannel::MAX_SLOW_MODE {
+            return Err(Error::SlowModeTooLong.into());
+        }
+
+        channel_state.extension.topic = input.topic;
+        channel_state.extension.slow_mode = input.slow_mode;
+        channel_state.extension.nsfw = input.nsfw;
+        channel_state.serialize_const(&mut channel_data)?;
+
+        AuditEvent::emit(
+            server.key,
+            &actor,
+            AuditAction::UpdateChannel,
+            server_channel.key,
+        )
+    }
+
+    fn create_direct_channel<'a>(
+        program_id: &Pubkey,
+        dweller: &AccountInfo<'a>,
+        direct_channel: &AccountInfo<'a>,
+        signer: &AccountInfo<'a>,
+        participants: &[AccountInfo<'a>],
+        input: &SetHashInput,
+    ) -> ProgramResult {
+        require_dweller(
+            program_id,
+            dweller.key,
+            signer,
+            DwellerDelegate::SCOPE_MEMBERSHIP,
+            ProgramError::MissingRequiredSignature,
+        )?;
+
+        let (mut dweller_data, mut dweller_state) =
+            dweller.read_data_with_borsh_mut::<Dweller>()?;
+        if !dweller_state.version.is_initialized() {
+            return Err(ProgramError::UninitializedAccount);
+        }
+        if dweller_state.version != StateVersion::V2 {
+            return Err(Error::AccountNotResized.into());
+        }
+
+        let direct_channel_key = create_index_with_bump(
+            program_id,
+            DirectChannel::SEED,
+            dweller.key,
+            dweller_state.extension.bump.get(),
+            dweller_state.extension.direct_channels,
+        )?;
+        if direct_channel_key != *direct_channel.key {
+            return Err(Error::InvalidDerivedDirectChannelAddress.into());
+        }
+
+        let (mut direct_channel_data, mut direct_channel_state) =
+            direct_channel.read_data_with_borsh_mut::<DirectChannel>()?;
+        if direct_channel_state.version.is_initialized() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        direct_channel_state.add(dweller.key);
+        for participant in participants {
+            require_initialized_dweller(program_id, participant)?;
+            if !direct_channel_state.add(participant.key) {
+                return Err(Error::InvalidDirectChannelParticipants.into());
+            }
+        }
+
+        if direct_channel_state.participants().len() < DirectChannel::MIN_PARTICIPANTS {
+            return Err(Error::InvalidDirectChannelParticipants.into());
+        }
+
+        direct_channel_state.version = StateVersion::V1;
+        direct_channel_state.container = *dweller.key;
+        direct_channel_state.index = dweller_state.extension.direct_channels;
+        direct_channel_state.db_hash = input.hash;
+        direct_channel_state.container_bump = dweller_state.extension.bump;
+        direct_channel_state.serialize_const(&mut direct_channel_data)?;
+
+        dweller_state.extension.direct_channels =
+            dweller_state.extension.direct_channels.error_increment()?;
+        dweller_state.serialize_const(&mut dweller_data)?;
+
+        Ok(())
+    }
+
+    fn add_direct_channel_participant<'a>(
+        program_id: &Pubkey,
+        dweller: &AccountInfo<'a>,
+        direct_channel: &AccountInfo<'a>,
+        participant: &AccountInfo<'a>,
+        signer: &AccountInfo<'a>,
+    ) -> ProgramResult {
+        require_dweller(
+            program_id,
+            dweller.key,
+            signer,
+            DwellerDelegate::SCOPE_MEMBERSHIP,
+            ProgramError::MissingRequiredSignature,
+        )?;
+
+        let (mut direct_channel_data, mut direct_channel_state) =
+            direct_channel.read_data_with_borsh_mut::<DirectChannel>()?;
+        require_direct_channel(program_id, direct_channel, &direct_channel_state)?;
+
+        if !direct_channel_state.contains(dweller.key) {
+            return Err(Error::NotDirectChannelParticipant.into());
+        }
+
+        require_initialized_dweller(program_id, participant)?;
+        if !direct_channel_state.add(participant.key) {
+            return Err(Error::InvalidDirectChannelParticipants.into());
+        }
+
+        direct_channel_state.serialize_const(&mut direct_channel_data)?;
+        Ok(())
+    }
+
+    fn remove_direct_channel_participant<'a>(
+        program_id: &Pubkey,
+        dweller: &AccountInfo<'a>,
+        direct_channel: &AccountInfo<'a>,
+        participant: &AccountInfo<'a>,
+        direct_channel_last: &AccountInfo<'a>,
+        signer: &AccountInfo<'a>,
+    ) -> ProgramResult {
+        require_dweller(
+            program_id,
+            dweller.key,
+            signer,
+            DwellerDelegate::SCOPE_MEMBERSHIP,
+            ProgramError::MissingRequiredSignature,
+        )?;
+
+        let mut direct_channel_state = direct_channel.read_data_with_borsh::<DirectChannel>()?;
+        require_direct_channel(program_id, direct_channel, &direct_channel_state)?;
+
+        if direct_channel_state.container != *dweller.key
+            || !direct_channel_state.contains(dweller.key)
+            || dweller.key == participant.key
+        {
+            return Err(Error::NotDirectChannelCreator.into());
+        }
+
+        if !direct_channel_state.remove(participant.key) {
+            return Err(Error::NotDirectChannelParticipant.into());
+        }
+
+        store_direct_channel(
+            program_id,
+            direct_channel,
+            &direct_channel_state,
+            dweller,
+            direct_channel_last,
+        )
+    }
+
+    fn leave_direct_channel<'a>(
+        program_id: &Pubkey,
+        dweller: &AccountInfo<'a>,
+        direct_channel: &AccountInfo<'a>,
+        creator: &AccountInfo<'a>,
+        direct_channel_last: &AccountInfo<'a>,
+        signer: &AccountInfo<'a>,
+    ) -> ProgramResult {
+        require_dweller(
+            program_id,
+            dweller.key,
+            signer,
+            DwellerDelegate::SCOPE_MEMBERSHIP,
+            ProgramError::MissingRequiredSignature,
+        )?;
+
+        let mut direct_channel_state = direct_channel.read_data_with_borsh::<DirectChannel>()?;
+        require_direct_channel(program_id, direct_channel, &direct_channel_state)?;
+
+        if !direct_channel_state.remove(dweller.key) {
+            return Err(Error::NotDirectChannelParticipant.into());
+        }
+
+        store_direct_channel(
+            program_id,
+            direct_channel,
+            &direct_channel_state,
+            creator,
+            direct_channel_last,
+        )
+    }
+
+    fn list_server<'a>(
+        program_id: &Pubkey,
+        owner: &AccountInfo<'a>,
+        server: &AccountInfo<'a>,
+        server_directory: &AccountInfo<'a>,
+        server_listing: &AccountInfo<'a>,
+    ) -> ProgramResult {
+        let (mut server_data, mut server_state) = server.read_data_with_borsh_mut::<Server>()?;
+        if !server_state.version.is_initialized() {
+            return Err(ProgramError::UninitializedAccount);
+        }
+        require_owner(program_id, &server_state, owner)?;
+
+        if !server_state.extension.public {
+            return Err(Error::ServerNotPublic.into());
+        }
+        if server_state.extension.listed {
+            return Err(Error::ServerAlreadyListed.into());
+        }
+
+        let (mut directory_data, mut directory_state) =
+            server_directory.read_data_with_borsh_mut::<ServerDirectory>()?;
+        if !directory_state.version.is_initialized() {
+            // first listing stores bumps, so next ones do not search for them
+            directory_state.container_bump = BaseBump::new(find_base_bump(program_id, program_id));
+            directory_state.bump = BaseBump::new(find_base_bump(program_id, server_directory.key));
+        }
+        if directory_state.derived_address(program_id)? != *server_directory.key {
+            return Err(Error::InvalidDerivedServerDirectoryAddress.into());
+        }
+
+        let server_listing_key = create_index_with_bump(
+            program_id,
+            ServerListing::SEED,
+            server_directory.key,
+            directory_state.bump.get(),
+            directory_state.listings,
+        )?;
+        if server_listing_key != *server_listing.key {
+            return Err(Error::InvalidDerivedServerListingAddress.into());
+        }
+
+        let (mut listing_data, mut listing_state) =
+            server_listing.read_data_with_borsh_mut::<ServerListing>()?;
+        if listing_state.version.is_initialized() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        listing_state.version = StateVersion::V1;
+        listing_state.container = *server_directory.key;
+        listing_state.index = directory_state.listings;
+        listing_state.server = *server.key;
+        listing_state.serialize_const(&mut listing_data)?;
+
+        directory_state.version = StateVersion::V1;
+        directory_state.listings = directory_state.listings.error_increment()?;
+        directory_state.serialize_const(&mut directory_data)?;
+
+        server_state.extension.listed = true;
+        server_state.serialize_const(&mut server_data)?;
+
+        AuditEvent::emit(
+            server.key,
+            &server_state.owner,
+            AuditAction::ListServer,
+            server_listing.key,
+        )
+    }
+
+    fn unlist_server<'a>(
+        program_id: &Pubkey,
+        owner: &AccountInfo<'a>,
+        server: &AccountInfo<'a>,
+        server_directory: &AccountInfo<'a>,
+        server_listing: &AccountInfo<'a>,
+        server_listing_last: &AccountInfo<'a>,
+    ) -> ProgramResult {
+        let (mut server_data, mut server_state) = server.read_data_with_borsh_mut::<Server>()?;
+        if !server_state.version.is_initialized() {
+            return Err(ProgramError::UninitializedAccount);
+        }
+        require_owner(program_id, &server_state, owner)?;
+
+        if !server_state.extension.listed {
+            return Err(Error::ServerNotListed.into());
+        }
+
+        let (mut directory_data, mut directory_state) =
+            server_directory.read_data_with_borsh_mut::<ServerDirectory>()?;
+        if !directory_state.version.is_initialized()
+            || directory_state.derived_address(program_id)? != *server_directory.key
+        {
+            return Err(Error::InvalidDerivedServerDirectoryAddress.into());
+        }
+
+        let listing_state: ServerListing = server_listing.read_data_with_borsh()?;
+        let server_listing_key = create_index_with_bump(
+            program_id,
+            ServerListing::SEED,
+            server_directory.key,
+            directory_state.bump.get(),
+            listing_state.index,
+        )?;
+        let server_listing_last_key = create_index_with_bump(
+            program_id,
+            ServerListing::SEED,
+            server_directory.key,
+            directory_state.bump.get(),
+            directory_state.listings.error_decrement()?,
+        )?;
+
+        if server_listing_key != *server_listing.key
+            || server_listing_last_key != *server_listing_last.key
+            || listing_state.server != *server.key
+        {
+            return Err(Error::InvalidDerivedServerListingAddress.into());
+        }
+
+        swap_accounts::<ServerListing>(server_listing, server_listing_last)?;
+
+        directory_state.listings = directory_state.listings.error_decrement()?;
+        directory_state.serialize_const(&mut directory_data)?;
+
+        server_state.extension.listed = false;
+        server_state.serialize_const(&mut server_data)?;
+
+        AuditEvent::emit(
+            server.key,
+            &server_state.owner,
+            AuditAction::UnlistServer,
+            server_listing.key,
+        )
+    }
+
+    /// Processes an instruction
+    pub fn process_instruction(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        input: &[u8],
+    ) -> ProgramResult {
+        let instruction = Instruction::try_from_slice(&input[0..1])?;
+        match instruction {
+            Instruction::CreateDerivedAccount => {
+                msg!("Instruction: CreateDerivedAccount");
+                match accounts {
+                    [payer_account_info, owner_account_info, base_account_info, account_to_create_info, rent_account_info, system_program, ..] =>
+                    {
+                        let input =
+                            super::instruction::AddressTypeInput::deserialize_const(&input[1..])?;
+                        Self::create_derived_address(
+                            program_id,
+                            payer_account_info,
+                            owner_account_info,
+                            base_account_info,
+                            account_to_create_info,
+                            rent_account_info,
+                            system_program,
+                            &input,
+                        )
+                    }
+                    _ => Err(ProgramError::NotEnoughAccountKeys),
+                }
+            }
+            Instruction::InitializeDweller => {
+                msg!("Instruction: InitializeDweller");
+                match accounts {
+                    [dweller, ..] => {
+                        let (input, bump) =
+                            <(super::instruction::InitializeDwellerInput, u8)>::deserialize_const(
+                                &input[1..],
+                            )?;
+
+                        Self::initialize_dweller(program_id, dweller, &input, bump)
+                    }
+                    _ => Err(ProgramError::NotEnoughAccountKeys),
+                }
+            }
+            Instruction::InitializeServer => {
+                msg!("Instruction: InitializeServer");
+                match accounts {
+                    [dweller_owner, server, dweller_server, server_member, ..] => {
+                        let (input, bump) =
+                            <(super::instruction::InitializeServerInput, u8)>::deserialize_const(
+                                &input[1..],
+                            )?;
+
+                        Self::initialize_server(
+                            program_id,
+                            dweller_owner,
+                            server,
+                            dweller_server,
+                            server_member,
+                            &input,
+                            bump,
+                        )
+                    }
+                    _ => Err(ProgramError::NotEnoughAccountKeys),
+                }
+            }
+            Instruction::SetDwellerName => {
+                msg!("Instruction: SetDwellerName");
+                match accounts {
+                    [dweller, ..] => {
+                        let input =
+                            super::instruction::SetNameInput::deserialize_const(&input[1..])?;
+
+                        Self::set_dweller_name(
+                            program_id,
+                            dweller,
+                            accounts.get(1).unwrap_or(dweller),
+                            &input,
+                        )
+                    }
+                    _ => Err(ProgramError::NotEnoughAccountKeys),
+                }
+            }
+            Instruction::SetDwellerPhoto => {
+                msg!("Instruction: SetDwellerPhoto");
+                match accounts {
+                    [dweller, ..] => {
+                        let input =
+                            super::instruction::SetHashInput::deserialize_const(&input[1..])?;
+
+                        Self::set_dweller_photo(
+                            program_id,
+                            dweller,
+                            accounts.get(1).unwrap_or(dweller),
+                            &input,
+                        )
+                    }
+                    _ => Err(ProgramError::NotEnoughAccountKeys),
+                }
+            }
+            Instruction::SetDwellerStatus => {
+                msg!("Instruction: SetDwellerStatus");
+                match accounts {
+                    [dweller, ..] => {
+                        let input = super::instruction::SetDwellerStatusInput::deserialize_const(
+                            &input[1..],
+                        )?;
+
+                        Self::set_dweller_status(
+                            program_id,
+                            dweller,
+                            accounts.get(1).unwrap_or(dweller),
+                            &input,
+                        )
+                    }
+                    _ => Err(ProgramError::NotEnoughAccountKeys),
+                }
+            }
+            Instruction::AddChannel => {
+                msg!("Instruction: AddChannel");
+                match accounts {
+                    [dweller_administrator, server_administrator, server, server_channel, ..] => {
+                        let (input, bump) =
+                            <(super::instruction::AddChannelInput, u8)>::deserialize_const(
+                                &input[1..],
+                            )?;
+
+                        Self::add_channel(
+                            program_id,
+                            dweller_administrator,
+                            server_administrator,
+                            server,
+                            server_channel,
+                            &input,
+                            bump,
+                        )
+                    }
+                    _ => Err(ProgramError::NotEnoughAccountKeys),
+                }
+            }
+            Instruction::CreateGroup => {
+                msg!("Instruction: CreateGroup");
+                match accounts {
+                    [dweller, server_administrator, server, server_group, ..] => {
+                        let (input, bump) =
+                            <(super::instruction::CreateGroupInput, u8)>::deserialize_const(
+                                &input[1..],
+                            )?;
+
+                        Self::create_group(
+                            program_id,
+                            dweller,
                             server_administrator,
                             server,
                             server_group,
+                            accounts.get(4),
                             &input,
+                            bump,
                         )
                     }
                     _ => Err(ProgramError::NotEnoughAccountKeys),
@@ -820,6 +1775,7 @@ impl Processor {
                             server_member_status,
                             dweller,
                             dweller_server,
+                            accounts.get(5).unwrap_or(dweller),
                         )
                     }
                     _ => Err(ProgramError::NotEnoughAccountKeys),
@@ -838,6 +1794,7 @@ impl Processor {
                             dweller,
                             dweller_server,
                             dweller_server_last,
+                            accounts.get(6).unwrap_or(dweller),
                         )
                     }
                     _ => Err(ProgramError::NotEnoughAccountKeys),
@@ -862,56 +1819,420 @@ impl Processor {
                 }
             }
 
-            Instruction::RemoveChannelFromGroup => {
-                msg!("Instruction: RemoveChannelFromGroup");
+            Instruction::RemoveChannelFromGroup => {
+                msg!("Instruction: RemoveChannelFromGroup");
+                match accounts {
+                    [server, dweller_administrator, server_administrator, server_group, server_channel, group_channel, group_channel_last, ..] => {
+                        Self::remove_channel_from_group(
+                            program_id,
+                            server,
+                            dweller_administrator,
+                            server_administrator,
+                            server_group,
+                            server_channel,
+                            group_channel,
+                            group_channel_last,
+                        )
+                    }
+                    _ => Err(ProgramError::NotEnoughAccountKeys),
+                }
+            }
+
+            Instruction::DeleteChannel => {
+                msg!("Instruction: DeleteChannel");
+                match accounts {
+                    [dweller, server_administrator, server, server_channel, server_channel_last, ..] => {
+                        Self::delete_channel(
+                            program_id,
+                            dweller,
+                            server_administrator,
+                            server,
+                            server_channel,
+                            server_channel_last,
+                            &accounts[5..],
+                        )
+                    }
+                    _ => Err(ProgramError::NotEnoughAccountKeys),
+                }
+            }
+
+            Instruction::DeleteGroup => {
+                msg!("Instruction: DeleteGroup");
+                match accounts {
+                    [dweller_administrator, server_administrator, server, server_group, server_group_last, ..] =>
+                    {
+                        Self::delete_group(
+                            program_id,
+                            dweller_administrator,
+                            server_administrator,
+                            server,
+                            server_group,
+                            server_group_last,
+                            &accounts[5..],
+                        )
+                    }
+                    _ => Err(ProgramError::NotEnoughAccountKeys),
+                }
+            }
+
+            Instruction::ReorderChannels => {
+                msg!("Instruction: ReorderChannels");
+                match accounts {
+                    [dweller_administrator, server_administrator, server, ..] => {
+                        let input = super::instruction::ReorderChannelsInput::deserialize_const(
+                            &input[1..],
+                        )?;
+
+                        Self::reorder_channels(
+                            program_id,
+                            dweller_administrator,
+                            server_administrator,
+                            server,
+                            &accounts[3..],
+                            &input,
+                        )
+                    }
+                    _ => Err(ProgramError::NotEnoughAccountKeys),
+                }
+            }
+
+            Instruction::ResizeAccount => {
+                msg!("Instruction: ResizeAccount");
+                match accounts {
+                    [payer, account, authority, rent, system_program, containers @ ..] => {
+                        let (input, bump) =
+                            <(super::instruction::ResizeAccountInput, u8)>::deserialize_const(
+                                &input[1..],
+                            )?;
+
+                        Self::resize_account(
+                            program_id,
+                            payer,
+                            account,
+                            authority,
+                            rent,
+                            system_program,
+                            containers,
+                            &input,
+                            bump,
+                        )
+                    }
+                    _ => Err(ProgramError::NotEnoughAccountKeys),
+                }
+            }
+
+            Instruction::AddDelegate => {
+                msg!("Instruction: AddDelegate");
+                match accounts {
+                    [dweller, delegate, ..] => {
+                        let input =
+                            super::instruction::AddDelegateInput::deserialize_const(&input[1..])?;
+
+                        Self::add_delegate(program_id, dweller, delegate, &input)
+                    }
+                    _ => Err(ProgramError::NotEnoughAccountKeys),
+                }
+            }
+
+            Instruction::RemoveDelegate => {
+                msg!("Instruction: RemoveDelegate");
+                match accounts {
+                    [dweller, authority, delegate, destination, ..] => {
+                        Self::remove_delegate(program_id, dweller, authority, delegate, destination)
+                    }
+                    _ => Err(ProgramError::NotEnoughAccountKeys),
+                }
+            }
+
+            Instruction::CloseDweller => {
+                msg!("Instruction: CloseDweller");
+                match accounts {
+                    [dweller, destination, ..] => {
+                        Self::close_dweller(program_id, dweller, destination, &accounts[2..])
+                    }
+                    _ => Err(ProgramError::NotEnoughAccountKeys),
+                }
+            }
+
+            Instruction::PinMessage => {
+                msg!("Instruction: PinMessage");
+                match accounts {
+                    [dweller_administrator, server_administrator, server, server_channel, pinned_message, ..] =>
+                    {
+                        let input =
+                            super::instruction::PinMessageInput::deserialize_const(&input[1..])?;
+
+                        Self::pin_message(
+                            program_id,
+                            dweller_administrator,
+                            server_administrator,
+                            server,
+                            server_channel,
+                            pinned_message,
+                            &input,
+                        )
+                    }
+                    _ => Err(ProgramError::NotEnoughAccountKeys),
+                }
+            }
+
+            Instruction::UnpinMessage => {
+                msg!("Instruction: UnpinMessage");
+                match accounts {
+                    [dweller_administrator, server_administrator, server, server_channel, pinned_message, pinned_message_last, ..] => {
+                        Self::unpin_message(
+                            program_id,
+                            dweller_administrator,
+                            server_administrator,
+                            server,
+                            server_channel,
+                            pinned_message,
+                            pinned_message_last,
+                        )
+                    }
+                    _ => Err(ProgramError::NotEnoughAccountKeys),
+                }
+            }
+
+            Instruction::CreateDirectChannel => {
+                msg!("Instruction: CreateDirectChannel");
+                match accounts {
+                    [dweller, direct_channel, signer, ..] => {
+                        let input =
+                            super::instruction::SetHashInput::deserialize_const(&input[1..])?;
+
+                        Self::create_direct_channel(
+                            program_id,
+                            dweller,
+                            direct_channel,
+                            signer,
+                            &accounts[3..],
+                            &input,
+                        )
+                    }
+                    _ => Err(ProgramError::NotEnoughAccountKeys),
+                }
+            }
+
+            Instruction::AddDirectChannelParticipant => {
+                msg!("Instruction: AddDirectChannelParticipant");
+                match accounts {
+                    [dweller, direct_channel, participant, ..] => {
+                        Self::add_direct_channel_participant(
+                            program_id,
+                            dweller,
+                            direct_channel,
+                            participant,
+                            accounts.get(3).unwrap_or(dweller),
+                        )
+                    }
+                    _ => Err(ProgramError::NotEnoughAccountKeys),
+                }
+            }
+
+            Instruction::RemoveDirectChannelParticipant => {
+                msg!("Instruction: RemoveDirectChannelParticipant");
+                match accounts {
+                    [dweller, direct_channel, participant, direct_channel_last, ..] => {
+                        Self::remove_direct_channel_participant(
+                            program_id,
+                            dweller,
+                            direct_channel,
+                            participant,
+                            direct_channel_last,
+                            accounts.get(4).unwrap_or(dweller),
+                        )
+                    }
+                    _ => Err(ProgramError::NotEnoughAccountKeys),
+                }
+            }
+
+            Instruction::LeaveDirectChannel => {
+                msg!("Instruction: LeaveDirectChannel");
+                match accounts {
+                    [dweller, direct_channel, creator, direct_channel_last, ..] => {
+                        Self::leave_direct_channel(
+                            program_id,
+                            dweller,
+                            direct_channel,
+                            creator,
+                            direct_channel_last,
+                            accounts.get(4).unwrap_or(dweller),
+                        )
+                    }
+                    _ => Err(ProgramError::NotEnoughAccountKeys),
+                }
+            }
+
+            Instruction::SetServerDescription => {
+                msg!("Instruction: SetServerDescription");
+                match accounts {
+                    [dweller_administrator, server_administrator, server, ..] => {
+                        let input =
+                            super::instruction::SetServerDescriptionInput::deserialize_const(
+                                &input[1..],
+                            )?;
+
+                        Self::set_server_extension(
+                            program_id,
+                            dweller_administrator,
+                            server_administrator,
+                            server,
+                            AuditAction::SetServerDescription,
+                            |extension| {
+                                extension.description = input.description;
+                                Ok(())
+                            },
+                        )
+                    }
+                    _ => Err(ProgramError::NotEnoughAccountKeys),
+                }
+            }
+
+            Instruction::SetServerBanner => {
+                msg!("Instruction: SetServerBanner");
+                match accounts {
+                    [dweller_administrator, server_administrator, server, ..] => {
+                        let input =
+                            super::instruction::SetHashInput::deserialize_const(&input[1..])?;
+
+                        Self::set_server_extension(
+                            program_id,
+                            dweller_administrator,
+                            server_administrator,
+                            server,
+                            AuditAction::SetServerBanner,
+                            |extension| {
+                                extension.banner_hash = input.hash;
+                                Ok(())
+                            },
+                        )
+                    }
+                    _ => Err(ProgramError::NotEnoughAccountKeys),
+                }
+            }
+
+            Instruction::SetServerTags => {
+                msg!("Instruction: SetServerTags");
+                match accounts {
+                    [dweller_administrator, server_administrator, server, ..] => {
+                        let input =
+                            super::instruction::SetServerTagsInput::deserialize_const(&input[1..])?;
+
+                        Self::set_server_extension(
+                            program_id,
+                            dweller_administrator,
+                            server_administrator,
+                            server,
+                            AuditAction::SetServerTags,
+                            |extension| {
+                                extension.tags = input.tags;
+                                Ok(())
+                            },
+                        )
+                    }
+                    _ => Err(ProgramError::NotEnoughAccountKeys),
+                }
+            }
+
+            Instruction::SetServerPublic => {
+                msg!("Instruction: SetServerPublic");
+                match accounts {
+                    [dweller_administrator, server_administrator, server, ..] => {
+                        let input = super::instruction::SetServerPublicInput::deserialize_const(
+                            &input[1..],
+                        )?;
+
+                        Self::set_server_extension(
+                            program_id,
+                            dweller_administrator,
+                            server_administrator,
+                            server,
+                            AuditAction::SetServerPublic,
+                            |extension| {
+                                if extension.listed && !input.public {
+                                    return Err(Error::ServerListed.into());
+                                }
+                                extension.public = input.public;
+                                Ok(())
+                            },
+                        )
+                    }
+                    _ => Err(ProgramError::NotEnoughAccountKeys),
+                }
+            }
+
+            Instruction::ListServer => {
+                msg!("Instruction: ListServer");
                 match accounts {
-                    [server, dweller_administrator, server_administrator, server_group, group_channel, group_channel_last, ..] => {
-                        Self::remove_channel_from_group(
+                    [owner, server, server_directory, server_listing, ..] => Self::list_server(
+                        program_id,
+                        owner,
+                        server,
+                        server_directory,
+                        server_listing,
+                    ),
+                    _ => Err(ProgramError::NotEnoughAccountKeys),
+                }
+            }
+
+            Instruction::UnlistServer => {
+                msg!("Instruction: UnlistServer");
+                match accounts {
+                    [owner, server, server_directory, server_listing, server_listing_last, ..] => {
+                        Self::unlist_server(
                             program_id,
+                            owner,
                             server,
-                            dweller_administrator,
-                            server_administrator,
-                            server_group,
-                            group_channel,
-                            group_channel_last,
+                            server_directory,
+                            server_listing,
+                            server_listing_last,
                         )
                     }
                     _ => Err(ProgramError::NotEnoughAccountKeys),
                 }
             }
 
-            Instruction::DeleteChannel => {
-                msg!("Instruction: DeleteChannel");
-                // ISSUE: in original Solidity contract channels are not deleted from groups
+            Instruction::UpdateChannel => {
+                msg!("Instruction: UpdateChannel");
                 match accounts {
-                    [dweller, server_administrator, server, server_channel, server_channel_last, ..] => {
-                        Self::delete_channel(
+                    [dweller_administrator, server_administrator, server, server_channel, ..] => {
+                        let input =
+                            super::instruction::UpdateChannelInput::deserialize_const(&input[1..])?;
+
+                        Self::update_channel(
                             program_id,
-                            dweller,
+                            dweller_administrator,
                             server_administrator,
                             server,
                             server_channel,
-                            server_channel_last,
+                            &input,
                         )
                     }
                     _ => Err(ProgramError::NotEnoughAccountKeys),
                 }
             }
 
-            Instruction::DeleteGroup => {
-                msg!("Instruction: DeleteGroup");
+            Instruction::SetServerGate => {
+                msg!("Instruction: SetServerGate");
                 match accounts {
-                    [dweller_administrator, server_administrator, server, server_group, server_group_last, ..] =>
-                    {
-                        let group_channels = &accounts[5..];
-                        Self::delete_group(
+                    [dweller_administrator, server_administrator, server, ..] => {
+                        let input =
+                            super::instruction::SetServerGateInput::deserialize_const(&input[1..])?;
+
+                        Self::set_server_extension(
                             program_id,
                             dweller_administrator,
                             server_administrator,
                             server,
-                            server_group,
-                            server_group_last,
-                            group_channels,
+                            AuditAction::SetServerGate,
+                            |extension| {
+                                if !input.gate.is_valid() {
+                                    return Err(Error::InvalidTokenGate.into());
+                                }
+                                extension.gate = input.gate;
+                                Ok(())
+                            },
                         )
                     }
                     _ => Err(ProgramError::NotEnoughAccountKeys),
@@ -927,9 +2248,9 @@ impl Processor {
         server: &AccountInfo<'a>,
         server_group: &AccountInfo<'a>,
         server_group_last: &AccountInfo<'a>,
-        group_channels: &[AccountInfo<'a>],
+        accounts: &[AccountInfo<'a>],
     ) -> ProgramResult {
-        require_admin(
+        let actor = require_admin(
             program_id,
             dweller_administrator,
             server,
@@ -939,49 +2260,215 @@ impl Processor {
         let (mut data, mut server_state) = server.read_data_with_borsh_mut::<Server>()?;
 
         let server_group_state = server_group.read_data_with_borsh::<ServerGroup>()?;
-        let server_group_key = create_index_with_seed(
+        let server_group_key = create_index_with_bump(
             program_id,
             ServerGroup::SEED,
             server.key,
+            server_state.extension.bump.get(),
             server_group_state.index,
         )?;
 
-        let server_group_last_key = create_index_with_seed(
+        let server_group_last_key = create_index_with_bump(
             program_id,
             ServerGroup::SEED,
             server.key,
+            server_state.extension.bump.get(),
             server_state.groups.error_decrement()?,
         )?;
 
         if server_group_key == *server_group.key && server_group_last_key == *server_group_last.key
         {
-            for child in group_channels {
-                let child_state = server_group.read_data_with_borsh::<GroupChannel>()?;
-                let child_key = create_index_with_seed(
+            if server_group_state.extension.groups > 0 {
+                return Err(Error::GroupHasNestedGroups.into());
+            }
+
+            let accounts = if server_group_state.extension.parent != Pubkey::default() {
+                let (server_group_parent, accounts) = accounts
+                    .split_first()
+                    .ok_or(ProgramError::NotEnoughAccountKeys)?;
+                if server_group_state.extension.parent != *server_group_parent.key {
+                    return Err(Error::InvalidDerivedServerGroupAddress.into());
+                }
+
+                let (mut parent_data, mut parent_state) =
+                    server_group_parent.read_data_with_borsh_mut::<ServerGroup>()?;
+                parent_state.extension.groups = parent_state.extension.groups.error_decrement()?;
+                parent_state.serialize_const(&mut parent_data)?;
+                accounts
+            } else {
+                accounts
+            };
+
+            let group_channels_len = server_group_state.channels as usize * 2;
+            let group_channels = accounts
+                .get(..group_channels_len)
+                .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+            // pair at each index, so every group channel is erased exactly once
+            for (index, pair) in (0..).zip(group_channels.chunks(2)) {
+                let (child, server_channel) = (&pair[0], &pair[1]);
+                let child_state = child.read_data_with_borsh::<GroupChannel>()?;
+                let child_key = create_index_with_bump(
                     program_id,
                     GroupChannel::SEED,
                     &server_group_key,
+                    server_group_state.extension.bump.get(),
                     child_state.index,
                 )?;
 
-                if child_key == *child.key {
+                if child_state.index == index
+                    && child_key == *child.key
+                    && child_state.channel == *server_channel.key
+                {
+                    let (mut channel_data, mut channel_state) =
+                        server_channel.read_data_with_borsh_mut::<ServerChannel>()?;
+                    channel_state.extension.group = Pubkey::default();
+                    channel_state.serialize_const(&mut channel_data)?;
+
                     swap_accounts::<GroupChannel>(child, child)?;
                 } else {
                     return Err(Error::InvalidDerivedGroupChannelAddress.into());
                 }
             }
 
+            // nested groups, channels and group channels point to last group by address,
+            // so they are rewritten to address it is moved to
+            if server_group.key != server_group_last.key {
+                let accounts = &accounts[group_channels_len..];
+                // read after parent is updated, as it may be last group
+                let server_group_last_state =
+                    server_group_last.read_data_with_borsh::<ServerGroup>()?;
+
+                let nested_len = server_group_last_state.extension.groups as usize;
+                let nested = accounts
+                    .get(..nested_len)
+                    .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+                // ascending indices, so every nested group is rewritten exactly once
+                let mut previous = None;
+                for nested_group in nested {
+                    let (mut nested_data, mut nested_state) =
+                        nested_group.read_data_with_borsh_mut::<ServerGroup>()?;
+                    require_server_group(program_id, server, nested_group, &nested_state)?;
+                    if nested_state.extension.parent != *server_group_last.key
+                        || nested_group.key == server_group.key
+                        || previous >= Some(nested_state.index)
+                    {
+                        return Err(Error::InvalidDerivedServerGroupAddress.into());
+                    }
+                    previous = Some(nested_state.index);
+
+                    nested_state.extension.parent = *server_group.key;
+                    nested_state.serialize_const(&mut nested_data)?;
+                }
+
+                let moved_len = server_group_last_state.channels as usize * 3;
+                let moved = accounts
+                    .get(nested_len..nested_len + moved_len)
+                    .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+                // group channel at each index, so every one is moved exactly once
+                for (index, triple) in (0..).zip(moved.chunks(3)) {
+                    let (child, target, server_channel) = (&triple[0], &triple[1], &triple[2]);
+                    let child_state = child.read_data_with_borsh::<GroupChannel>()?;
+                    if child_state.channel != *server_channel.key {
+                        return Err(Error::InvalidDerivedGroupChannelAddress.into());
+                    }
+
+                    let (mut channel_data, mut channel_state) =
+                        server_channel.read_data_with_borsh_mut::<ServerChannel>()?;
+                    require_server_channel(program_id, server, server_channel, &channel_state)?;
+                    channel_state.extension.group = *server_group.key;
+                    channel_state.serialize_const(&mut channel_data)?;
+
+                    move_child::<GroupChannel>(
+                        program_id,
+                        GroupChannel::SEED,
+                        (
+                            server_group_last.key,
+                            server_group_last_state.extension.bump.get(),
+                        ),
+                        (server_group.key, server_group_state.extension.bump.get()),
+                        index,
+                        child,
+                        target,
+                        |group_channel| {
+                            group_channel.container = *server_group.key;
+                            group_channel.extension.container_bump =
+                                server_group_state.extension.bump;
+                        },
+                    )?;
+                }
+            }
+
+            remove_position(
+                &server_state.version,
+                &mut server_state.extension.next_group_position,
+                server_state.groups,
+            )?;
             swap_accounts::<ServerGroup>(server_group, server_group_last)?;
 
             server_state.groups = server_state.groups.error_decrement()?;
             server_state.serialize_const(&mut data)?;
 
-            return Ok(());
+            return AuditEvent::emit(
+                server.key,
+                &actor,
+                AuditAction::DeleteGroup,
+                server_group.key,
+            );
         }
 
         Err(Error::Failed.into())
     }
 
+    fn reorder_channels<'a>(
+        program_id: &Pubkey,
+        dweller_administrator: &AccountInfo<'a>,
+        server_administrator: &AccountInfo<'a>,
+        server: &AccountInfo<'a>,
+        accounts: &[AccountInfo<'a>],
+        input: &ReorderChannelsInput,
+    ) -> ProgramResult {
+        let actor = require_admin(
+            program_id,
+            dweller_administrator,
+            server,
+            server_administrator,
+        )?;
+
+        match input {
+            ReorderChannelsInput::ServerChannels => reorder_positions::<ServerChannel>(
+                program_id,
+                ServerChannel::SEED,
+                server.key,
+                accounts,
+            ),
+            ReorderChannelsInput::ServerGroups => reorder_positions::<ServerGroup>(
+                program_id,
+                ServerGroup::SEED,
+                server.key,
+                accounts,
+            ),
+            ReorderChannelsInput::GroupChannels => match accounts {
+                [server_group, group_channels @ ..] => {
+                    let server_group_state = server_group.read_data_with_borsh::<ServerGroup>()?;
+                    require_server_group(program_id, server, server_group, &server_group_state)?;
+
+                    reorder_positions::<GroupChannel>(
+                        program_id,
+                        GroupChannel::SEED,
+                        server_group.key,
+                        group_channels,
+                    )
+                }
+                _ => Err(ProgramError::NotEnoughAccountKeys),
+            },
+        }?;
+
+        AuditEvent::emit(server.key, &actor, AuditAction::ReorderChannels, server.key)
+    }
+
     fn delete_channel<'a>(
         program_id: &Pubkey,
         dweller: &AccountInfo<'a>,
@@ -989,35 +2476,111 @@ impl Processor {
         server: &AccountInfo<'a>,
         server_channel: &AccountInfo<'a>,
         server_channel_last: &AccountInfo<'a>,
+        accounts: &[AccountInfo<'a>],
     ) -> ProgramResult {
-        require_admin(program_id, dweller, server, server_administrator)?;
+        let actor = require_admin(program_id, dweller, server, server_administrator)?;
 
         let (mut server_data, mut server_state) = server.read_data_with_borsh_mut::<Server>()?;
         let channel_state = server_channel.read_data_with_borsh::<ServerChannel>()?;
 
-        let server_channel_key = create_index_with_seed(
+        let server_channel_key = create_index_with_bump(
             program_id,
             ServerChannel::SEED,
             server.key,
+            server_state.extension.bump.get(),
             channel_state.index,
         )?;
 
-        let server_channel_last_key = create_index_with_seed(
+        let server_channel_last_key = create_index_with_bump(
             program_id,
             ServerChannel::SEED,
             server.key,
+            server_state.extension.bump.get(),
             server_state.channels.error_decrement()?,
         )?;
 
         if server_channel_key == *server_channel.key
             && server_channel_last_key == *server_channel_last.key
         {
+            if channel_state.extension.group != Pubkey::default() {
+                return Err(Error::ChannelInGroup.into());
+            }
+            if channel_state.extension.pins > 0 {
+                return Err(Error::ChannelHasPins.into());
+            }
+
+            // group channel and pinned messages point to last channel by address,
+            // so they are rewritten to address it is moved to
+            if server_channel.key != server_channel_last.key {
+                let channel_last_state =
+                    server_channel_last.read_data_with_borsh::<ServerChannel>()?;
+
+                let accounts = if channel_last_state.extension.group != Pubkey::default() {
+                    let (group_channel, accounts) = accounts
+                        .split_first()
+                        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+                    let (mut group_channel_data, mut group_channel_state) =
+                        group_channel.read_data_with_borsh_mut::<GroupChannel>()?;
+                    let group_channel_key = create_index_with_bump(
+                        program_id,
+                        GroupChannel::SEED,
+                        &channel_last_state.extension.group,
+                        group_channel_state.extension.container_bump.get(),
+                        group_channel_state.index,
+                    )?;
+                    if group_channel.owner != program_id
+                        || group_channel_key != *group_channel.key
+                        || group_channel_state.channel != *server_channel_last.key
+                    {
+                        return Err(Error::InvalidDerivedGroupChannelAddress.into());
+                    }
+
+                    group_channel_state.channel = *server_channel.key;
+                    group_channel_state.serialize_const(&mut group_channel_data)?;
+                    accounts
+                } else {
+                    accounts
+                };
+
+                let pins_len = channel_last_state.extension.pins as usize * 2;
+                let pins = accounts
+                    .get(..pins_len)
+                    .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+                // pinned message at each index, so every one is moved exactly once
+                for (index, pair) in (0..).zip(pins.chunks(2)) {
+                    move_child::<PinnedMessage>(
+                        program_id,
+                        PinnedMessage::SEED,
+                        (
+                            server_channel_last.key,
+                            channel_last_state.extension.bump.get(),
+                        ),
+                        (server_channel.key, channel_state.extension.bump.get()),
+                        index,
+                        &pair[0],
+                        &pair[1],
+                        |pinned_message| pinned_message.container = *server_channel.key,
+                    )?;
+                }
+            }
+
+            remove_position(
+                &server_state.version,
+                &mut server_state.extension.next_channel_position,
+                server_state.channels,
+            )?;
             swap_accounts::<ServerChannel>(server_channel, server_channel_last)?;
 
             server_state.channels = server_state.channels.error_decrement()?;
             server_state.serialize_const(&mut server_data)?;
 
-            Ok(())
+            AuditEvent::emit(
+                server.key,
+                &actor,
+                AuditAction::DeleteChannel,
+                server_channel.key,
+            )
         } else {
             Err(Error::InvalidDerivedServerChannelAddress.into())
         }
@@ -1030,10 +2593,11 @@ impl Processor {
         dweller_administrator: &AccountInfo<'a>,
         server_administrator: &AccountInfo<'a>,
         server_group: &AccountInfo<'a>,
+        server_channel: &AccountInfo<'a>,
         group_channel: &AccountInfo<'a>,
         group_channel_last: &AccountInfo<'a>,
     ) -> ProgramResult {
-        require_admin(
+        let actor = require_admin(
             program_id,
             dweller_administrator,
             server,
@@ -1041,31 +2605,50 @@ impl Processor {
         )?;
         let (mut group_data, mut group_state) =
             server_group.read_data_with_borsh_mut::<ServerGroup>()?;
+        require_server_group(program_id, server, server_group, &group_state)?;
 
         let group_channel_data: GroupChannel = group_channel.read_data_with_borsh()?;
-        let group_channel_key = create_index_with_seed(
+        let group_channel_key = create_index_with_bump(
             program_id,
             GroupChannel::SEED,
             server_group.key,
+            group_state.extension.bump.get(),
             group_channel_data.index,
         )?;
 
-        let group_channel_last_key = create_index_with_seed(
+        let group_channel_last_key = create_index_with_bump(
             program_id,
             GroupChannel::SEED,
             server_group.key,
+            group_state.extension.bump.get(),
             group_state.channels.error_decrement()?,
         )?;
 
         if group_channel_key == *group_channel.key
             && group_channel_last_key == *group_channel_last.key
+            && group_channel_data.channel == *server_channel.key
         {
+            let (mut channel_data, mut channel_state) =
+                server_channel.read_data_with_borsh_mut::<ServerChannel>()?;
+            channel_state.extension.group = Pubkey::default();
+            channel_state.serialize_const(&mut channel_data)?;
+
+            remove_position(
+                &group_state.version,
+                &mut group_state.extension.next_channel_position,
+                group_state.channels,
+            )?;
             swap_accounts::<GroupChannel>(group_channel, group_channel_last)?;
 
             group_state.channels = group_state.channels.error_decrement()?;
             group_state.serialize_const(&mut group_data)?;
 
-            Ok(())
+            AuditEvent::emit(
+                server.key,
+                &actor,
+                AuditAction::RemoveChannelFromGroup,
+                group_channel.key,
+            )
         } else {
             Err(Error::InvalidDerivedGroupChannelAddress.into())
         }
@@ -1080,7 +2663,7 @@ impl Processor {
         server_group: &AccountInfo<'a>,
         group_channel: &AccountInfo<'a>,
     ) -> ProgramResult {
-        require_admin(
+        let actor = require_admin(
             program_id,
             dweller_administrator,
             server,
@@ -1089,11 +2672,21 @@ impl Processor {
 
         let (mut server_group_data, mut server_group_state) =
             server_group.read_data_with_borsh_mut::<ServerGroup>()?;
+        require_server_group(program_id, server, server_group, &server_group_state)?;
+
+        let (mut channel_data, mut channel_state) =
+            server_channel.read_data_with_borsh_mut::<ServerChannel>()?;
+        require_server_channel(program_id, server, server_channel, &channel_state)?;
+
+        if channel_state.extension.group != Pubkey::default() {
+            return Err(Error::ChannelAlreadyInGroup.into());
+        }
 
-        let group_channel_key = create_index_with_seed(
+        let group_channel_key = create_index_with_bump(
             program_id,
             GroupChannel::SEED,
             server_group.key,
+            server_group_state.extension.bump.get(),
             server_group_state.channels,
         )?;
 
@@ -1102,16 +2695,34 @@ impl Processor {
                 group_channel.read_data_with_borsh_mut::<GroupChannel>()?;
 
             if group_channel_state.version == StateVersion::Uninitialized {
-                group_channel_state.version = StateVersion::V1;
+                group_channel_state.version =
+                    StateVersion::fitting(group_channel_data.len(), GroupChannel::LEN);
                 group_channel_state.container = *server_group.key;
                 group_channel_state.index = server_group_state.channels;
+                group_channel_state.extension.position = push_position(
+                    &server_group_state.version,
+                    &mut server_group_state.extension.next_channel_position,
+                    server_group_state.channels,
+                )?;
+                if group_channel_state.version == StateVersion::V2 {
+                    group_channel_state.extension.container_bump =
+                        server_group_state.extension.bump;
+                }
                 group_channel_state.channel = *server_channel.key;
                 group_channel_state.serialize_const(&mut group_channel_data)?;
 
+                channel_state.extension.group = *server_group.key;
+                channel_state.serialize_const(&mut channel_data)?;
+
                 server_group_state.channels = server_group_state.channels.error_increment()?;
                 server_group_state.serialize_const(&mut server_group_data)?;
 
-                Ok(())
+                AuditEvent::emit(
+                    server.key,
+                    &actor,
+                    AuditAction::AddChannelToGroup,
+                    server_channel.key,
+                )
             } else {
                 Err(ProgramError::AccountAlreadyInitialized)
             }
@@ -1120,6 +2731,7 @@ impl Processor {
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn leave_server<'a>(
         program_id: &Pubkey,
         server: &AccountInfo<'a>,
@@ -1128,14 +2740,22 @@ impl Processor {
         dweller: &AccountInfo<'a>,
         dweller_server: &AccountInfo<'a>,
         dweller_server_last: &AccountInfo<'a>,
+        signer: &AccountInfo<'a>,
     ) -> ProgramResult {
-        if dweller.is_signer {
-            remove_dweller_server(program_id, dweller, dweller_server, dweller_server_last)?;
-            remove_server_member(program_id, server, server_member, server_member_last)?;
-            Ok(())
-        } else {
-            Err(ProgramError::MissingRequiredSignature)
+        require_dweller(
+            program_id,
+            dweller.key,
+            signer,
+            DwellerDelegate::SCOPE_MEMBERSHIP,
+            ProgramError::MissingRequiredSignature,
+        )?;
+        let server_state: Server = server.read_data_with_borsh()?;
+        if server_state.owner == *dweller.key {
+            return Err(Error::OwnerCannotLeaveServer.into());
         }
+        remove_dweller_server(program_id, dweller, dweller_server, dweller_server_last)?;
+        remove_server_member(program_id, server, server_member, server_member_last)?;
+        Ok(())
     }
 
     fn join_server<'a>(
@@ -1145,101 +2765,379 @@ impl Processor {
         server_member_status: &AccountInfo<'a>,
         dweller: &AccountInfo<'a>,
         dweller_server: &AccountInfo<'a>,
+        signer: &AccountInfo<'a>,
     ) -> ProgramResult {
-        if dweller.is_signer {
-            let mut dweller_data = dweller.try_borrow_mut_data()?;
-            let mut dweller_state = Dweller::deserialize_const(&dweller_data)?;
-
-            let dweller_server_key = create_index_with_seed(
-                program_id,
-                DwellerServer::SEED,
-                dweller.key,
-                dweller_state.servers,
-            )?;
-
-            if dweller_server_key == *dweller_server.key {
-                let (mut dweller_server_data, mut dweller_server_state) =
-                    dweller_server.read_data_with_borsh_mut::<DwellerServer>()?;
-
-                if dweller_server_state.version == StateVersion::Uninitialized {
-                    let server_member_status_state: ServerMemberStatus =
-                        server_member_status.read_data_with_borsh()?;
-                    if server_member.owner == program_id
-                        && server_member_status_state.dweller == *dweller.key
-                        && server_member_status_state.container == *server.key
-                    {
-                        let (mut server_data, mut server_state) =
-                            server.read_data_with_borsh_mut::<Server>()?;
-                        let (mut server_member_data, mut server_member_state) =
-                            server_member.read_data_with_borsh_mut::<ServerMember>()?;
-
-                        let server_member_key = create_index_with_seed(
-                            program_id,
-                            ServerMember::SEED,
-                            server.key,
-                            server_state.members,
-                        )?;
-
-                        if server_member_key == *server_member.key {
-                            if server_member_state.version == StateVersion::Uninitialized {
-                                server_member_state.version = StateVersion::V1;
-                                server_member_state.container = *server.key;
-                                server_member_state.index = server_state.members;
-                                server_member_state.dweller = *dweller.key;
-                                server_member_state.serialize_const(&mut server_member_data)?;
+        require_dweller(
+            program_id,
+            dweller.key,
+            signer,
+            DwellerDelegate::SCOPE_MEMBERSHIP,
+            ProgramError::MissingRequiredSignature,
+        )?;
 
-                                dweller_server_state.container = *dweller.key;
-                                dweller_server_state.index = dweller_state.servers;
-                                dweller_server_state.version = StateVersion::V1;
-                                dweller_server_state.server = *server.key;
-                                dweller_server_state.serialize_const(&mut dweller_server_data)?;
+        let mut dweller_data = dweller.try_borrow_mut_data()?;
+        let mut dweller_state = Dweller::deserialize_const(&dweller_data)?;
 
-                                dweller_state.servers = dweller_state.servers.error_increment()?;
-                                dweller_state.serialize_const(&mut dweller_data)?;
+        let dweller_server_key = create_index_with_bump(
+            program_id,
+            DwellerServer::SEED,
+            dweller.key,
+            dweller_state.extension.bump.get(),
+            dweller_state.servers,
+        )?;
 
-                                server_state.members = server_state.members.error_increment()?;
-                                server_state.serialize_const(&mut server_data)?;
+        if dweller_server_key == *dweller_server.key {
+            let (mut dweller_server_data, mut dweller_server_state) =
+                dweller_server.read_data_with_borsh_mut::<DwellerServer>()?;
 
-                                Ok(())
-                            } else {
-                                Err(ProgramError::AccountAlreadyInitialized)
-                            }
+            if dweller_server_state.version == StateVersion::Uninitialized {
+                let (mut server_data, mut server_state) =
+                    server.read_data_with_borsh_mut::<Server>()?;
+                if server_member.owner == program_id
+                    && can_join(
+                        program_id,
+                        server.key,
+                        &server_state,
+                        dweller.key,
+                        server_member_status,
+                    )?
+                {
+                    let (mut server_member_data, mut server_member_state) =
+                        server_member.read_data_with_borsh_mut::<ServerMember>()?;
+
+                    let server_member_key = create_index_with_bump(
+                        program_id,
+                        ServerMember::SEED,
+                        server.key,
+                        server_state.extension.bump.get(),
+                        server_state.members,
+                    )?;
+
+                    if server_member_key == *server_member.key {
+                        if server_member_state.version == StateVersion::Uninitialized {
+                            server_member_state.version = StateVersion::V1;
+                            server_member_state.container = *server.key;
+                            server_member_state.index = server_state.members;
+                            server_member_state.dweller = *dweller.key;
+                            server_member_state.serialize_const(&mut server_member_data)?;
+
+                            dweller_server_state.container = *dweller.key;
+                            dweller_server_state.index = dweller_state.servers;
+                            dweller_server_state.version = StateVersion::V1;
+                            dweller_server_state.server = *server.key;
+                            dweller_server_state.serialize_const(&mut dweller_server_data)?;
+
+                            dweller_state.servers = dweller_state.servers.error_increment()?;
+                            dweller_state.serialize_const(&mut dweller_data)?;
+
+                            server_state.members = server_state.members.error_increment()?;
+                            server_state.serialize_const(&mut server_data)?;
+
+                            Ok(())
                         } else {
-                            Err(Error::InvalidDerivedServerMemberAddress.into())
+                            Err(ProgramError::AccountAlreadyInitialized)
                         }
                     } else {
-                        Err(Error::InvalidDerivedServerMemberStatusAddress.into())
+                        Err(Error::InvalidDerivedServerMemberAddress.into())
                     }
                 } else {
-                    Err(ProgramError::AccountAlreadyInitialized)
+                    Err(Error::InvalidDerivedServerMemberStatusAddress.into())
                 }
             } else {
-                Err(Error::InvalidDerivedDwellerServerAddress.into())
+                Err(ProgramError::AccountAlreadyInitialized)
             }
         } else {
-            Err(ProgramError::MissingRequiredSignature)
+            Err(Error::InvalidDerivedDwellerServerAddress.into())
         }
     }
 }
 
-fn require_owner<'a>(server_state: &Server, owner: &AccountInfo<'a>) -> ProgramResult {
-    if owner.is_signer {
-        if server_state.owner == *owner.key {
-            Ok(())
-        } else {
-            Err(Error::ProvidedDwellerIsNotTheOwnerOfTheServer.into())
+/// bump of base stored by container of derived account, none if container does not store it yet
+fn container_base_bump(
+    program_id: &Pubkey,
+    container: &AccountInfo,
+    input: &AddressTypeInput,
+) -> Result<Option<u8>, ProgramError> {
+    if container.owner != program_id {
+        return Ok(None);
+    }
+
+    let bump = match input {
+        AddressTypeInput::DwellerServer(_) | AddressTypeInput::DirectChannel(_) => {
+            container.read_data_with_borsh::<Dweller>()?.extension.bump
+        }
+        AddressTypeInput::ServerMemberStatus(_)
+        | AddressTypeInput::ServerAdministrator(_)
+        | AddressTypeInput::ServerMember(_)
+        | AddressTypeInput::ServerChannel(_)
+        | AddressTypeInput::ServerGroup(_) => {
+            container.read_data_with_borsh::<Server>()?.extension.bump
+        }
+        AddressTypeInput::GroupChannel(_) => {
+            let group: ServerGroup = container.read_data_with_borsh()?;
+            group.extension.bump
+        }
+        AddressTypeInput::PinnedMessage(_) => {
+            let channel: ServerChannel = container.read_data_with_borsh()?;
+            channel.extension.bump
+        }
+        AddressTypeInput::ServerListing(_) => {
+            container.read_data_with_borsh::<ServerDirectory>()?.bump
+        }
+        // directory is derived from program itself, once
+        AddressTypeInput::ServerDirectory => return Ok(None),
+    };
+    Ok(bump.get())
+}
+
+/// Zeroes account data and moves all its lamports to `destination`,
+/// so runtime garbage collects account after transaction
+fn close_account<'a>(account: &AccountInfo<'a>, destination: &AccountInfo<'a>) -> ProgramResult {
+    account.try_borrow_mut_data()?.fill(0);
+
+    let mut account_lamports = account.try_borrow_mut_lamports()?;
+    let mut destination_lamports = destination.try_borrow_mut_lamports()?;
+    **destination_lamports = destination_lamports
+        .checked_add(**account_lamports)
+        .ok_or(Error::Overflow)?;
+    **account_lamports = 0;
+
+    Ok(())
+}
+
+/// Checks channel is initialized and derived from server
+fn require_server_channel<'a>(
+    program_id: &Pubkey,
+    server: &AccountInfo<'a>,
+    server_channel: &AccountInfo<'a>,
+    server_channel_state: &ServerChannel,
+) -> ProgramResult {
+    let server_channel_key = create_index_with_bump(
+        program_id,
+        ServerChannel::SEED,
+        server.key,
+        server_channel_state.extension.container_bump.get(),
+        server_channel_state.index,
+    )?;
+
+    if server_channel_key != *server_channel.key || !server_channel_state.version.is_initialized() {
+        return Err(Error::InvalidDerivedServerChannelAddress.into());
+    }
+
+    Ok(())
+}
+
+/// Checks group is initialized and derived from server
+fn require_server_group<'a>(
+    program_id: &Pubkey,
+    server: &AccountInfo<'a>,
+    server_group: &AccountInfo<'a>,
+    server_group_state: &ServerGroup,
+) -> ProgramResult {
+    let server_group_key = create_index_with_bump(
+        program_id,
+        ServerGroup::SEED,
+        server.key,
+        server_group_state.extension.container_bump.get(),
+        server_group_state.index,
+    )?;
+
+    if server_group_key != *server_group.key || !server_group_state.version.is_initialized() {
+        return Err(Error::InvalidDerivedServerGroupAddress.into());
+    }
+
+    Ok(())
+}
+
+/// Checks direct channel is initialized and derived from its creator
+fn require_direct_channel(
+    program_id: &Pubkey,
+    direct_channel: &AccountInfo,
+    direct_channel_state: &DirectChannel,
+) -> ProgramResult {
+    let direct_channel_key = create_index_with_bump(
+        program_id,
+        DirectChannel::SEED,
+        &direct_channel_state.container,
+        direct_channel_state.container_bump.get(),
+        direct_channel_state.index,
+    )?;
+
+    if direct_channel_key != *direct_channel.key || !direct_channel_state.version.is_initialized() {
+        return Err(Error::InvalidDerivedDirectChannelAddress.into());
+    }
+
+    Ok(())
+}
+
+/// Writes direct channel or closes it if single participant is left,
+/// moving last direct channel of creator into its place
+fn store_direct_channel<'a>(
+    program_id: &Pubkey,
+    direct_channel: &AccountInfo<'a>,
+    direct_channel_state: &DirectChannel,
+    creator: &AccountInfo<'a>,
+    direct_channel_last: &AccountInfo<'a>,
+) -> ProgramResult {
+    if direct_channel_state.participants().len() >= DirectChannel::MIN_PARTICIPANTS {
+        direct_channel_state.serialize_const(&mut direct_channel.try_borrow_mut_data()?)?;
+        return Ok(());
+    }
+
+    if direct_channel_state.container != *creator.key {
+        return Err(Error::NotDirectChannelCreator.into());
+    }
+
+    let (mut creator_data, mut creator_state) = creator.read_data_with_borsh_mut::<Dweller>()?;
+    let direct_channel_last_key = create_index_with_bump(
+        program_id,
+        DirectChannel::SEED,
+        creator.key,
+        creator_state.extension.bump.get(),
+        creator_state.extension.direct_channels.error_decrement()?,
+    )?;
+    if direct_channel_last_key != *direct_channel_last.key {
+        return Err(Error::InvalidDerivedDirectChannelAddress.into());
+    }
+
+    swap_accounts::<DirectChannel>(direct_channel, direct_channel_last)?;
+
+    creator_state.extension.direct_channels =
+        creator_state.extension.direct_channels.error_decrement()?;
+    creator_state.serialize_const(&mut creator_data)?;
+    Ok(())
+}
+
+/// Checks account is initialized dweller of program
+fn require_initialized_dweller(program_id: &Pubkey, dweller: &AccountInfo) -> ProgramResult {
+    if dweller.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let dweller_state: Dweller = dweller.read_data_with_borsh()?;
+    if !dweller_state.version.is_initialized() {
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    Ok(())
+}
+
+/// Checks `signer` is `dweller` itself or delegate of `dweller` valid for `scope`,
+/// returns `mismatch` if `signer` is neither
+fn require_dweller(
+    program_id: &Pubkey,
+    dweller: &Pubkey,
+    signer: &AccountInfo,
+    scope: u8,
+    mismatch: ProgramError,
+) -> ProgramResult {
+    if !signer.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if signer.key == dweller {
+        return Ok(());
+    }
+
+    if signer.owner != program_id || signer.data_len() as u64 != DwellerDelegate::LEN {
+        return Err(mismatch);
+    }
+
+    let delegate: DwellerDelegate = signer.read_data_with_borsh()?;
+    if !delegate.version.is_initialized() || delegate.dweller != *dweller {
+        return Err(Error::InvalidDelegate.into());
+    }
+
+    if delegate.scope & scope != scope {
+        return Err(Error::DelegateScopeNotAllowed.into());
+    }
+
+    if Clock::get()?.unix_timestamp >= delegate.expiry {
+        return Err(Error::DelegateExpired.into());
+    }
+
+    Ok(())
+}
+
+/// True if `invite_or_token` is invite of dweller,
+/// token account not owned by program must satisfy [TokenGate] of server instead
+fn can_join(
+    program_id: &Pubkey,
+    server: &Pubkey,
+    server_state: &Server,
+    dweller: &Pubkey,
+    invite_or_token: &AccountInfo,
+) -> Result<bool, ProgramError> {
+    if invite_or_token.owner == program_id {
+        let invite: ServerMemberStatus = invite_or_token.read_data_with_borsh()?;
+        // other indexed states share layout, so only address tells invite apart
+        let invite_key = create_index_with_bump(
+            program_id,
+            ServerMemberStatus::SEED,
+            server,
+            server_state.extension.bump.get(),
+            invite.index,
+        )?;
+        return Ok(*invite_or_token.key == invite_key
+            && invite.dweller == *dweller
+            && invite.container == *server);
+    }
+
+    let gate = &server_state.extension.gate;
+    let (mint, owner) = match gate.kind {
+        TokenGateKind::None => return Err(Error::ServerNotTokenGated.into()),
+        TokenGateKind::Nft => {
+            if *invite_or_token.owner != spl_nft_erc_721::id() {
+                return Err(ProgramError::IncorrectProgramId);
+            }
+            let token: spl_nft_erc_721::state::Token = invite_or_token.read_data_with_borsh()?;
+            if token.version != spl_nft_erc_721::state::TokenStatus::Initialized {
+                return Err(ProgramError::UninitializedAccount);
+            }
+            (token.mint, token.owner)
+        }
+        TokenGateKind::SplToken => {
+            if *invite_or_token.owner != spl_token::id() {
+                return Err(ProgramError::IncorrectProgramId);
+            }
+            let token = spl_token::state::Account::unpack(&invite_or_token.try_borrow_data()?)?;
+            if token.amount < gate.amount {
+                return Err(Error::TokenGateInsufficientBalance.into());
+            }
+            (token.mint, token.owner)
         }
+    };
+
+    if mint != gate.mint {
+        Err(Error::TokenGateWrongMint.into())
+    } else if owner != *dweller {
+        Err(Error::TokenGateWrongOwner.into())
     } else {
-        Err(ProgramError::MissingRequiredSignature)
+        Ok(true)
     }
 }
 
+fn require_owner<'a>(
+    program_id: &Pubkey,
+    server_state: &Server,
+    owner: &AccountInfo<'a>,
+) -> ProgramResult {
+    require_dweller(
+        program_id,
+        &server_state.owner,
+        owner,
+        DwellerDelegate::SCOPE_ADMIN,
+        Error::ProvidedDwellerIsNotTheOwnerOfTheServer.into(),
+    )
+}
+
+/// Checks `dweller_administrator` acts as administrator of server, returns administrator dweller
 fn require_admin(
     program_id: &Pubkey,
     dweller_administrator: &AccountInfo,
     server: &AccountInfo,
     server_administrator: &AccountInfo,
-) -> ProgramResult {
+) -> Result<Pubkey, ProgramError> {
     if server_administrator.owner != program_id {
         return Err(ProgramError::IncorrectProgramId);
     }
@@ -1250,11 +3148,14 @@ fn require_admin(
         server_administrator_state.is_initialized()?;
 
         if server_administrator_state.container == *server.key {
-            if server_administrator_state.dweller == *dweller_administrator.key {
-                Ok(())
-            } else {
-                Err(Error::InvalidDerivedServerAdministratorAddress.into())
-            }
+            require_dweller(
+                program_id,
+                &server_administrator_state.dweller,
+                dweller_administrator,
+                DwellerDelegate::SCOPE_ADMIN,
+                Error::InvalidDerivedServerAdministratorAddress.into(),
+            )?;
+            Ok(server_administrator_state.dweller)
         } else {
             Err(Error::InvalidDerivedAddressWrongServer.into())
         }
@@ -1271,18 +3172,20 @@ fn remove_server_member<'a>(
 ) -> ProgramResult {
     let (mut server_data, mut server_state) = server.read_data_with_borsh_mut::<Server>()?;
 
-    let server_member_data: GroupChannel = server_member.read_data_with_borsh()?;
-    let server_member_key = create_index_with_seed(
+    let server_member_data: ServerMember = server_member.read_data_with_borsh()?;
+    let server_member_key = create_index_with_bump(
         program_id,
         ServerMember::SEED,
         server.key,
+        server_state.extension.bump.get(),
         server_member_data.index,
     )?;
 
-    let server_member_last_key = create_index_with_seed(
+    let server_member_last_key = create_index_with_bump(
         program_id,
         ServerMember::SEED,
         server.key,
+        server_state.extension.bump.get(),
         server_state.members.error_decrement()?,
     )?;
 
@@ -1308,17 +3211,19 @@ fn remove_dweller_server<'a>(
     let (mut dweller_data, mut dweller_state) = dweller.read_data_with_borsh_mut::<Dweller>()?;
 
     let dweller_server_data: DwellerServer = dweller_server.read_data_with_borsh()?;
-    let dweller_server_key = create_index_with_seed(
+    let dweller_server_key = create_index_with_bump(
         program_id,
         DwellerServer::SEED,
         dweller.key,
+        dweller_state.extension.bump.get(),
         dweller_server_data.index,
     )?;
 
-    let dweller_server_last_key = create_index_with_seed(
+    let dweller_server_last_key = create_index_with_bump(
         program_id,
         DwellerServer::SEED,
         dweller.key,
+        dweller_state.extension.bump.get(),
         dweller_state.servers.error_decrement()?,
     )?;
 
@@ -1,8 +1,6 @@
 //! In program helpers
 
-use std::mem;
-
-use borsh::BorshSerialize;
+use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::{
     account_info::AccountInfo,
     entrypoint::ProgramResult,
@@ -12,8 +10,15 @@ use solana_program::{
     system_instruction,
 };
 
-/// implements program seed public key address as indexed list pattern
-/// not optimal calling on chain, could store bump in state
+use crate::{
+    borsh::{BorshDeserialiseConst, BorshSerializeConst},
+    error::Error,
+    math::ErrorAdd,
+    state::{BaseBump, Indexed, Ordered, StateVersion},
+};
+
+/// implements program seed public key address as indexed list pattern,
+/// searches for bump so on chain prefer [create_index_with_bump]
 pub fn create_base_index_with_seed(
     program_id: &Pubkey,
     type_name: &str,
@@ -41,6 +46,52 @@ pub fn create_index_with_seed(
     Ok(create)
 }
 
+/// base of indexed lists of `seed_key`,
+/// `bump` stored in state of container avoids searching for it, none if not stored
+pub fn create_base_address(
+    program_id: &Pubkey,
+    seed_key: &Pubkey,
+    bump: Option<u8>,
+) -> Result<Pubkey, PubkeyError> {
+    match bump {
+        Some(bump) => {
+            Pubkey::create_program_address(&[&seed_key.to_bytes()[..32], &[bump]], program_id)
+        }
+        None => Ok(Pubkey::find_program_address(&[&seed_key.to_bytes()[..32]], program_id).0),
+    }
+}
+
+/// bump of base of `seed_key` to store in state of container,
+/// searched for by client, so pass it to instructions instead of calling on chain
+pub fn find_base_bump(program_id: &Pubkey, seed_key: &Pubkey) -> u8 {
+    Pubkey::find_program_address(&[&seed_key.to_bytes()[..32]], program_id).1
+}
+
+/// bump of base of `seed_key` provided by client, valid if it gives program address,
+/// so container stores it without searching on chain
+pub fn check_base_bump(
+    program_id: &Pubkey,
+    seed_key: &Pubkey,
+    bump: u8,
+) -> Result<BaseBump, ProgramError> {
+    create_base_address(program_id, seed_key, Some(bump))
+        .map_err(|_| ProgramError::InvalidSeeds)?;
+    Ok(BaseBump::new(bump))
+}
+
+/// validation shortcut using bump stored in state of container,
+/// gives same address as [create_index_with_seed]
+pub fn create_index_with_bump(
+    program_id: &Pubkey,
+    type_name: &str,
+    seed_key: &Pubkey,
+    bump: Option<u8>,
+    index: u64,
+) -> Result<Pubkey, PubkeyError> {
+    let base = create_base_address(program_id, seed_key, bump)?;
+    Pubkey::create_with_seed(&base, &format!("{}{:?}", type_name, index), program_id)
+}
+
 /// in program invoke to create program signed seeded account
 #[allow(clippy::too_many_arguments)]
 pub fn create_derived_account<'a>(
@@ -68,27 +119,171 @@ pub fn create_derived_account<'a>(
     )
 }
 
-/// swaps two accounts data
+/// moves last account data into current and erases last,
+/// moved entity gets index and own bump of current so its address stays derivable,
+/// both accounts must be of same version size
 /// panics if accounts are borrowedy
-pub fn swap_accounts<'a, T: Default + BorshSerialize>(
+pub fn swap_accounts<'a, T: Default + BorshSerialize + BorshDeserialize + Indexed>(
     current: &AccountInfo<'a>,
     last: &AccountInfo<'a>,
 ) -> Result<(), ProgramError> {
+    if current.data_len() != last.data_len() {
+        return Err(Error::AccountNotResized.into());
+    }
+
     let mut last_data = last.data.try_borrow_mut().unwrap();
     if current.key != last.key {
         let mut current_data = current.data.try_borrow_mut().unwrap();
-        mem::swap(&mut *current_data, &mut *last_data);
+        let mut replaced = T::deserialize_const(&current_data)?;
+        let index = *replaced.index_mut();
+        // bump is of base of address, so one stored for current address is kept
+        let bump = replaced.bump_mut().copied();
+        current_data.copy_from_slice(&last_data);
+        let mut moved = T::deserialize_const(&current_data)?;
+        *moved.index_mut() = index;
+        if let (Some(moved_bump), Some(bump)) = (moved.bump_mut(), bump) {
+            *moved_bump = bump;
+        }
+        moved.serialize_const(&mut current_data)?;
     }
+    // default of versioned state does not write extension
+    last_data.fill(0);
     T::default().serialize(&mut *last_data)?;
     Ok(())
 }
 
-/// helper to create seeded index collection pattern
+/// moves entity derived from container moved by [swap_accounts] into account derived
+/// from container new address at same `index`, so it stays derivable,
+/// `target` must be created and not initialized, `rebase` points moved entity to new container;
+/// containers are given with bumps of their bases, none if not stored
+#[allow(clippy::too_many_arguments)]
+pub fn move_child<'a, T: Default + BorshSerialize + BorshDeserialize + Indexed>(
+    program_id: &Pubkey,
+    type_name: &str,
+    from: (&Pubkey, Option<u8>),
+    to: (&Pubkey, Option<u8>),
+    index: u64,
+    child: &AccountInfo<'a>,
+    target: &AccountInfo<'a>,
+    rebase: impl FnOnce(&mut T),
+) -> ProgramResult {
+    if child.owner != program_id || target.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    let child_key = create_index_with_bump(program_id, type_name, from.0, from.1, index)?;
+    let target_key = create_index_with_bump(program_id, type_name, to.0, to.1, index)?;
+    if child_key != *child.key || target_key != *target.key {
+        return Err(Error::InvalidDerivedAddress.into());
+    }
+    if child.data_len() != target.data_len() {
+        return Err(Error::AccountNotResized.into());
+    }
+
+    let mut child_data = child.try_borrow_mut_data()?;
+    let mut target_data = target.try_borrow_mut_data()?;
+    if target_data.iter().any(|byte| *byte != 0) {
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+    let mut moved = T::deserialize_const(&child_data)?;
+    if *moved.index_mut() != index {
+        return Err(Error::InvalidDerivedAddress.into());
+    }
+    rebase(&mut moved);
+    target_data.copy_from_slice(&child_data);
+    moved.serialize_const(&mut target_data)?;
+
+    child_data.fill(0);
+    T::default().serialize(&mut *child_data)?;
+    Ok(())
+}
+
+/// reads ordered entity, validating its address is derived from container
+fn read_ordered<T: BorshDeserialize + Ordered>(
+    program_id: &Pubkey,
+    type_name: &str,
+    container: &Pubkey,
+    account: &AccountInfo,
+) -> Result<T, ProgramError> {
+    let mut state = T::deserialize_const(&account.try_borrow_data()?)?;
+    let key = create_index_with_bump(
+        program_id,
+        type_name,
+        container,
+        state.container_bump(),
+        *state.index_mut(),
+    )?;
+    if key == *account.key {
+        Ok(state)
+    } else {
+        Err(Error::InvalidDerivedAddress.into())
+    }
+}
+
+/// position of entity added after all others of container with `count` entities,
+/// only [StateVersion::V2] container stores `next`, so older one must be resized first;
+/// `next` of container positioned densely before positions became sparse is 0
+pub fn push_position(
+    version: &StateVersion,
+    next: &mut u64,
+    count: u64,
+) -> Result<u64, ProgramError> {
+    if *version != StateVersion::V2 {
+        return Err(Error::AccountNotResized.into());
+    }
+    let position = (*next).max(count);
+    *next = position.error_increment()?;
+    Ok(position)
+}
+
+/// removal leaves gap in positions, so entities placed after removed one are not rewritten,
+/// `next` of densely positioned container moves past positions before `count` shrinks;
+/// older container has no `next` to keep gap in, so it must be resized first
+pub fn remove_position(version: &StateVersion, next: &mut u64, count: u64) -> ProgramResult {
+    if *version != StateVersion::V2 {
+        return Err(Error::AccountNotResized.into());
+    }
+    *next = (*next).max(count);
+    Ok(())
+}
+
+/// assigns positions held by `accounts` to them in order they are provided
+pub fn reorder_positions<T: BorshDeserialize + BorshSerialize + Ordered>(
+    program_id: &Pubkey,
+    type_name: &str,
+    container: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let mut states = Vec::with_capacity(accounts.len());
+    for account in accounts {
+        let state: T = read_ordered(program_id, type_name, container, account)?;
+        states.push(state);
+    }
+
+    let mut positions: Vec<u64> = states
+        .iter_mut()
+        .map(|state| *state.position_mut())
+        .collect();
+    positions.sort_unstable();
+    if positions.windows(2).any(|pair| pair[0] == pair[1]) {
+        return Err(Error::InvalidPositions.into());
+    }
+
+    for ((account, state), position) in accounts.iter().zip(states.iter_mut()).zip(positions) {
+        *state.position_mut() = position;
+        state.serialize_const(&mut account.try_borrow_mut_data()?)?;
+    }
+
+    Ok(())
+}
+
+/// helper to create seeded index collection pattern,
+/// base is signed with `bump` stored in state of owner, searched for only if none is stored yet
 #[allow(clippy::too_many_arguments)]
 pub fn create_seeded_rent_except_account<'a>(
     seed: &str,
     owner_account_info: &AccountInfo<'a>,
     index: &u64,
+    bump: Option<u8>,
     base_account_info: &AccountInfo<'a>,
     account_to_create_info: &AccountInfo<'a>,
     payer_account_info: &AccountInfo<'a>,
@@ -96,8 +291,10 @@ pub fn create_seeded_rent_except_account<'a>(
     len: u64,
     program_id: &Pubkey,
 ) -> Result<(), ProgramError> {
-    let (address_to_create, program_address, bump_seed, seed) =
-        create_base_index_with_seed(program_id, seed, owner_account_info.key, *index)?;
+    let bump_seed = bump.unwrap_or_else(|| find_base_bump(program_id, owner_account_info.key));
+    let program_address = create_base_address(program_id, owner_account_info.key, Some(bump_seed))?;
+    let seed = format!("{}{:?}", seed, index);
+    let address_to_create = Pubkey::create_with_seed(&program_address, &seed, program_id)?;
     if program_address != *base_account_info.key {
         return Err(ProgramError::InvalidSeeds);
     }
@@ -117,3 +314,67 @@ pub fn create_seeded_rent_except_account<'a>(
     )?;
     Ok(())
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn index_with_bump() {
+        let program_id = crate::id();
+        let container = Pubkey::new_unique();
+        let bump = find_base_bump(&program_id, &container);
+        let expected = create_index_with_seed(&program_id, "Entity", &container, 3).unwrap();
+
+        assert_eq!(
+            create_index_with_bump(&program_id, "Entity", &container, Some(bump), 3).unwrap(),
+            expected
+        );
+        assert_eq!(
+            create_index_with_bump(&program_id, "Entity", &container, None, 3).unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn base_bump_of_client() {
+        let program_id = crate::id();
+        // any bump above found one gives no program address
+        let (container, bump) = std::iter::repeat_with(Pubkey::new_unique)
+            .map(|container| (container, find_base_bump(&program_id, &container)))
+            .find(|(_, bump)| *bump < u8::MAX)
+            .unwrap();
+
+        assert_eq!(
+            check_base_bump(&program_id, &container, bump).unwrap(),
+            BaseBump::new(bump)
+        );
+        assert_eq!(
+            check_base_bump(&program_id, &container, bump + 1),
+            Err(ProgramError::InvalidSeeds)
+        );
+    }
+
+    #[test]
+    fn sparse_positions() {
+        // container positioned densely before has positions 0 and 1
+        let mut next = 0;
+        remove_position(&StateVersion::V2, &mut next, 2).unwrap();
+        assert_eq!(push_position(&StateVersion::V2, &mut next, 1).unwrap(), 2);
+        assert_eq!(next, 3);
+
+        remove_position(&StateVersion::V2, &mut next, 2).unwrap();
+        assert_eq!(push_position(&StateVersion::V2, &mut next, 1).unwrap(), 3);
+
+        let mut next = 0;
+        assert_eq!(
+            push_position(&StateVersion::V1, &mut next, 2),
+            Err(Error::AccountNotResized.into())
+        );
+        assert_eq!(
+            remove_position(&StateVersion::V1, &mut next, 3),
+            Err(Error::AccountNotResized.into())
+        );
+        assert_eq!(next, 0);
+    }
+}
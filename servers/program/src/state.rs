@@ -1,7 +1,15 @@
 ///! Registry types.
+use std::io::{Error as IoError, ErrorKind, Result as IoResult, Write};
+
 use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 use num_derive::{FromPrimitive, ToPrimitive};
-use solana_program::{entrypoint::ProgramResult, program_error::ProgramError, pubkey::Pubkey};
+use solana_program::{
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    pubkey::{Pubkey, PubkeyError},
+};
+
+use crate::program::{create_index_with_bump, create_index_with_seed};
 
 /// flag
 #[repr(C)]
@@ -20,6 +28,11 @@ pub enum StateVersion {
     Uninitialized,
     /// initial
     V1,
+    /// [Dweller], [Server], [ServerChannel], [ServerGroup] and [GroupChannel]
+    /// with extension appended, see [Instruction::ResizeAccount]
+    ///
+    /// [Instruction::ResizeAccount]: crate::instruction::Instruction::ResizeAccount
+    V2,
 }
 
 impl Default for StateVersion {
@@ -28,11 +41,107 @@ impl Default for StateVersion {
     }
 }
 
+impl StateVersion {
+    /// latest version which fits into account of `data_len` for state of `len` size
+    pub fn fitting(data_len: usize, len: u64) -> Self {
+        if data_len as u64 >= len {
+            StateVersion::V2
+        } else {
+            StateVersion::V1
+        }
+    }
+
+    /// true if not [StateVersion::Uninitialized]
+    pub fn is_initialized(&self) -> bool {
+        *self != StateVersion::Uninitialized
+    }
+}
+
+/// writes extension only for [StateVersion::V2],
+/// older version can be written only if extension was not changed
+fn serialize_extension<T: BorshSerialize + Default + PartialEq, W: Write>(
+    version: &StateVersion,
+    extension: &T,
+    writer: &mut W,
+) -> IoResult<()> {
+    if *version == StateVersion::V2 {
+        extension.serialize(writer)
+    } else if *extension == T::default() {
+        Ok(())
+    } else {
+        Err(IoError::new(
+            ErrorKind::InvalidData,
+            "Account must be resized to store extension",
+        ))
+    }
+}
+
+/// reads extension only for [StateVersion::V2], older versions get default
+fn deserialize_extension<T: BorshDeserialize + Default>(
+    version: &StateVersion,
+    buf: &mut &[u8],
+) -> IoResult<T> {
+    if *version == StateVersion::V2 {
+        T::deserialize(buf)
+    } else {
+        Ok(T::default())
+    }
+}
+
+/// Bump of program address of base of indexed lists, stored so handlers do not search for it,
+/// see [crate::program::create_index_with_bump]
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone, Copy, BorshSchema, Default)]
+pub struct BaseBump {
+    /// bump, valid only if `stored`
+    pub bump: u8,
+    /// 0 is valid bump, so flag tells if bump was stored
+    pub stored: bool,
+}
+
+impl BaseBump {
+    /// stored bump
+    pub fn new(bump: u8) -> Self {
+        Self { bump, stored: true }
+    }
+
+    /// bump if stored
+    pub fn get(&self) -> Option<u8> {
+        if self.stored {
+            Some(self.bump)
+        } else {
+            None
+        }
+    }
+}
+
+/// Entity of indexed list pattern, see [crate::program::create_index_with_seed]
+pub trait Indexed {
+    /// index used to derive address
+    fn index_mut(&mut self) -> &mut u64;
+
+    /// bump of base of container entity is derived from, none if not stored
+    fn container_bump(&self) -> Option<u8> {
+        None
+    }
+
+    /// bump of base derived from entity itself, none if entity is not container
+    fn bump_mut(&mut self) -> Option<&mut BaseBump> {
+        None
+    }
+}
+
+/// Indexed entity which users see sorted by position instead of index
+pub trait Ordered: Indexed {
+    /// unique position in container, sparse as removal leaves gap
+    fn position_mut(&mut self) -> &mut u64;
+}
+
 /// address of signer + separate program deployed
 /// https://github.com/Satellite-im/Satellite-Contracts/blob/main/contracts/DwellerID.sol
 /// state
 #[repr(C)]
-#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone, BorshSchema)]
+#[derive(PartialEq, Debug, Clone, BorshSchema)]
 pub struct Dweller {
     /// version
     pub version: StateVersion,
@@ -49,11 +158,66 @@ pub struct Dweller {
 
     /// string
     pub status: [u8; 128],
+
+    /// stored only by [StateVersion::V2]
+    pub extension: DwellerExtension,
 }
 
 impl Dweller {
+    /// data size of [StateVersion::V1]
+    pub const LEN_V1: u64 = 233;
     /// data size
-    pub const LEN: u64 = 233;
+    pub const LEN: u64 = 489;
+}
+
+impl BorshSerialize for Dweller {
+    fn serialize<W: Write>(&self, writer: &mut W) -> IoResult<()> {
+        self.version.serialize(writer)?;
+        self.servers.serialize(writer)?;
+        self.name.serialize(writer)?;
+        self.photo_hash.serialize(writer)?;
+        self.status.serialize(writer)?;
+        serialize_extension(&self.version, &self.extension, writer)
+    }
+}
+
+impl BorshDeserialize for Dweller {
+    fn deserialize(buf: &mut &[u8]) -> IoResult<Self> {
+        let version = StateVersion::deserialize(buf)?;
+        Ok(Self {
+            servers: u64::deserialize(buf)?,
+            name: <[u8; 32]>::deserialize(buf)?,
+            photo_hash: <[u8; 64]>::deserialize(buf)?,
+            status: <[u8; 128]>::deserialize(buf)?,
+            extension: deserialize_extension(&version, buf)?,
+            version,
+        })
+    }
+}
+
+/// Fields added to [Dweller] by [StateVersion::V2]
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone, BorshSchema)]
+pub struct DwellerExtension {
+    /// used to derive DirectChannel, count of open direct channels created by dweller
+    pub direct_channels: u64,
+    /// bump of base of indexed lists of dweller
+    pub bump: BaseBump,
+    /// count of registered [DwellerDelegate], must be 0 to close dweller
+    pub delegates: u64,
+    /// space for new fields
+    pub reserved: [u8; 238],
+}
+
+impl Default for DwellerExtension {
+    fn default() -> Self {
+        Self {
+            direct_channels: 0,
+            bump: BaseBump::default(),
+            delegates: 0,
+            reserved: [0; 238],
+        }
+    }
 }
 
 /// Mapping of `Dweller` to `Server`.
@@ -79,6 +243,36 @@ impl DwellerServer {
     pub const SEED: &'static str = "DwellerServer";
 }
 
+/// Device key allowed to sign for `Dweller`.
+/// Account address is the device key itself, so the account signs on behalf of dweller.
+/// state
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone, BorshSchema, Default)]
+pub struct DwellerDelegate {
+    /// version
+    pub version: StateVersion,
+    /// for
+    pub dweller: Pubkey,
+    /// unix timestamp after which delegate is not valid
+    pub expiry: i64,
+    /// bit set of `SCOPE_*` allowed to delegate
+    pub scope: u8,
+}
+
+impl DwellerDelegate {
+    /// data size
+    pub const LEN: u64 = 42;
+
+    /// edit dweller name, photo and status
+    pub const SCOPE_PROFILE: u8 = 1;
+    /// join and leave servers
+    pub const SCOPE_MEMBERSHIP: u8 = 1 << 1;
+    /// act as server owner or administrator
+    pub const SCOPE_ADMIN: u8 = 1 << 2;
+    /// all scopes
+    pub const SCOPE_ALL: u8 = Self::SCOPE_PROFILE | Self::SCOPE_MEMBERSHIP | Self::SCOPE_ADMIN;
+}
+
 /// Server members whom have joined
 /// Has program derived address from Server
 /// many to many map of `Server` to `DwellerID` (inverse of `DwellerServer`)
@@ -158,7 +352,7 @@ impl ServerAdministrator {
 
 /// state
 #[repr(C)]
-#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone, BorshSchema)]
+#[derive(PartialEq, Debug, Clone, BorshSchema)]
 pub struct Server {
     /// version
     pub version: StateVersion,
@@ -186,16 +380,142 @@ pub struct Server {
     pub channels: u64,
     /// index
     pub groups: u64,
+
+    /// stored only by [StateVersion::V2]
+    pub extension: ServerExtension,
 }
 
 impl Server {
+    /// data size of [StateVersion::V1]
+    pub const LEN_V1: u64 = 233;
     /// data size
-    pub const LEN: u64 = 233;
+    pub const LEN: u64 = 745;
 }
 
-/// state
+impl BorshSerialize for Server {
+    fn serialize<W: Write>(&self, writer: &mut W) -> IoResult<()> {
+        self.version.serialize(writer)?;
+        self.owner.serialize(writer)?;
+        self.name.serialize(writer)?;
+        self.photo_hash.serialize(writer)?;
+        self.db_hash.serialize(writer)?;
+        self.members.serialize(writer)?;
+        self.member_statuses.serialize(writer)?;
+        self.administrators.serialize(writer)?;
+        self.channels.serialize(writer)?;
+        self.groups.serialize(writer)?;
+        serialize_extension(&self.version, &self.extension, writer)
+    }
+}
+
+impl BorshDeserialize for Server {
+    fn deserialize(buf: &mut &[u8]) -> IoResult<Self> {
+        let version = StateVersion::deserialize(buf)?;
+        Ok(Self {
+            owner: Pubkey::deserialize(buf)?,
+            name: <[u8; 32]>::deserialize(buf)?,
+            photo_hash: <[u8; 64]>::deserialize(buf)?,
+            db_hash: <[u8; 64]>::deserialize(buf)?,
+            members: u64::deserialize(buf)?,
+            member_statuses: u64::deserialize(buf)?,
+            administrators: u64::deserialize(buf)?,
+            channels: u64::deserialize(buf)?,
+            groups: u64::deserialize(buf)?,
+            extension: deserialize_extension(&version, buf)?,
+            version,
+        })
+    }
+}
+
+/// Fields added to [Server] by [StateVersion::V2]
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone, BorshSchema)]
+pub struct ServerExtension {
+    /// text shown on discovery pages
+    pub description: [u8; 256],
+    /// Multihash referencing IPFS hash of banner image
+    pub banner_hash: [u8; 64],
+    /// category names, empty are not set
+    pub tags: [[u8; 16]; 4],
+    /// server wants to be shown in public directories
+    pub public: bool,
+    /// server has [ServerListing] in [ServerDirectory]
+    pub listed: bool,
+    /// bump of base of indexed lists of server
+    pub bump: BaseBump,
+    /// token allowing to join without invite
+    pub gate: TokenGate,
+    /// position given to next added [ServerChannel], see [crate::program::push_position]
+    pub next_channel_position: u64,
+    /// position given to next created [ServerGroup], see [crate::program::push_position]
+    pub next_group_position: u64,
+    /// space for new fields
+    pub reserved: [u8; 67],
+}
+
+impl Default for ServerExtension {
+    fn default() -> Self {
+        Self {
+            description: [0; 256],
+            banner_hash: [0; 64],
+            tags: [[0; 16]; 4],
+            public: false,
+            listed: false,
+            bump: BaseBump::default(),
+            gate: TokenGate::default(),
+            next_channel_position: 0,
+            next_group_position: 0,
+            reserved: [0; 67],
+        }
+    }
+}
+
+/// Program of token account required by [TokenGate]
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone, Copy, BorshSchema)]
+pub enum TokenGateKind {
+    /// no gate, joining needs [ServerMemberStatus] invite
+    None,
+    /// `spl-nft-erc-721` token of mint
+    Nft,
+    /// spl token account of mint holding at least [TokenGate::amount]
+    SplToken,
+}
+
+impl Default for TokenGateKind {
+    fn default() -> Self {
+        TokenGateKind::None
+    }
+}
+
+/// Token dweller must own to join [Server] without invite
 #[repr(C)]
 #[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone, BorshSchema, Default)]
+pub struct TokenGate {
+    /// kind
+    pub kind: TokenGateKind,
+    /// nft or spl token mint
+    pub mint: Pubkey,
+    /// minimal balance, used only by [TokenGateKind::SplToken]
+    pub amount: u64,
+}
+
+impl TokenGate {
+    /// token gates need mint, as default one is not of any token,
+    /// spl token gate needs positive amount, as empty token account would pass it,
+    /// and no gate has neither mint nor amount
+    pub fn is_valid(&self) -> bool {
+        match self.kind {
+            TokenGateKind::None => self.mint == Pubkey::default() && self.amount == 0,
+            TokenGateKind::Nft => self.mint != Pubkey::default(),
+            TokenGateKind::SplToken => self.mint != Pubkey::default() && self.amount > 0,
+        }
+    }
+}
+
+/// state
+#[repr(C)]
+#[derive(PartialEq, Debug, Clone, BorshSchema, Default)]
 pub struct ServerChannel {
     /// version
     pub version: StateVersion,
@@ -208,18 +528,124 @@ pub struct ServerChannel {
     pub type_id: u8,
     /// name
     pub name: [u8; 32],
+
+    /// stored only by [StateVersion::V2]
+    pub extension: ServerChannelExtension,
+}
+
+impl BorshSerialize for ServerChannel {
+    fn serialize<W: Write>(&self, writer: &mut W) -> IoResult<()> {
+        self.version.serialize(writer)?;
+        self.container.serialize(writer)?;
+        self.index.serialize(writer)?;
+        self.type_id.serialize(writer)?;
+        self.name.serialize(writer)?;
+        serialize_extension(&self.version, &self.extension, writer)
+    }
+}
+
+impl BorshDeserialize for ServerChannel {
+    fn deserialize(buf: &mut &[u8]) -> IoResult<Self> {
+        let version = StateVersion::deserialize(buf)?;
+        Ok(Self {
+            container: Pubkey::deserialize(buf)?,
+            index: u64::deserialize(buf)?,
+            type_id: u8::deserialize(buf)?,
+            name: <[u8; 32]>::deserialize(buf)?,
+            extension: deserialize_extension(&version, buf)?,
+            version,
+        })
+    }
+}
+
+/// Fields added to [ServerChannel] by [StateVersion::V2]
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone, BorshSchema)]
+pub struct ServerChannelExtension {
+    /// position in channels list shown to users
+    pub position: u64,
+
+    /// [ServerGroup] channel belongs to, default if none
+    pub group: Pubkey,
+
+    /// index used to derive [PinnedMessage]
+    pub pins: u64,
+
+    /// utf8 text shown under name, zero padded
+    pub topic: [u8; 128],
+    /// seconds dweller waits between messages, 0 if slow mode is off
+    pub slow_mode: u32,
+    /// content is age restricted
+    pub nsfw: bool,
+
+    /// bump of base of [Server] lists channel is derived from
+    pub container_bump: BaseBump,
+    /// bump of base of [PinnedMessage] list of channel
+    pub bump: BaseBump,
+
+    /// space for new fields
+    pub reserved: [u8; 71],
+}
+
+impl Default for ServerChannelExtension {
+    fn default() -> Self {
+        Self {
+            position: 0,
+            group: Pubkey::default(),
+            pins: 0,
+            topic: [0; 128],
+            slow_mode: 0,
+            nsfw: false,
+            container_bump: BaseBump::default(),
+            bump: BaseBump::default(),
+            reserved: [0; 71],
+        }
+    }
+}
+
+/// Settings of [ServerChannel] changed by [crate::instruction::Instruction::UpdateChannel]
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct ChannelSettings<'a> {
+    /// topic without zero padding
+    pub topic: &'a str,
+    /// seconds dweller waits between messages, none if slow mode is off
+    pub slow_mode: Option<u32>,
+    /// content is age restricted
+    pub nsfw: bool,
 }
 
 impl ServerChannel {
+    /// data size of [StateVersion::V1]
+    pub const LEN_V1: u64 = 74;
     /// data size
-    pub const LEN: u64 = 74;
+    pub const LEN: u64 = 330;
     /// entity type used for seed
     pub const SEED: &'static str = "ServerChannel";
+    /// max slow mode interval, 6 hours
+    pub const MAX_SLOW_MODE: u32 = 21_600;
+
+    /// topic without zero padding, none if not utf8
+    pub fn read_topic(topic: &[u8; 128]) -> Option<&str> {
+        let len = topic
+            .iter()
+            .position(|byte| *byte == 0)
+            .unwrap_or(topic.len());
+        std::str::from_utf8(&topic[..len]).ok()
+    }
+
+    /// typed settings, topic which is not utf8 reads as empty
+    pub fn settings(&self) -> ChannelSettings {
+        ChannelSettings {
+            topic: Self::read_topic(&self.extension.topic).unwrap_or_default(),
+            slow_mode: Some(self.extension.slow_mode).filter(|seconds| *seconds > 0),
+            nsfw: self.extension.nsfw,
+        }
+    }
 }
 
 /// state
 #[repr(C)]
-#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone, BorshSchema, Default)]
+#[derive(PartialEq, Debug, Clone, BorshSchema, Default)]
 pub struct ServerGroup {
     /// version
     pub version: StateVersion,
@@ -233,18 +659,87 @@ pub struct ServerGroup {
 
     /// index
     pub channels: u64,
+
+    /// stored only by [StateVersion::V2]
+    pub extension: ServerGroupExtension,
 }
 
 impl ServerGroup {
+    /// data size of [StateVersion::V1]
+    pub const LEN_V1: u64 = 81;
     /// data size
-    pub const LEN: u64 = 81;
+    pub const LEN: u64 = 209;
     /// entity type used for seed
     pub const SEED: &'static str = "ServerGroup";
 }
 
+impl BorshSerialize for ServerGroup {
+    fn serialize<W: Write>(&self, writer: &mut W) -> IoResult<()> {
+        self.version.serialize(writer)?;
+        self.container.serialize(writer)?;
+        self.index.serialize(writer)?;
+        self.name.serialize(writer)?;
+        self.channels.serialize(writer)?;
+        serialize_extension(&self.version, &self.extension, writer)
+    }
+}
+
+impl BorshDeserialize for ServerGroup {
+    fn deserialize(buf: &mut &[u8]) -> IoResult<Self> {
+        let version = StateVersion::deserialize(buf)?;
+        Ok(Self {
+            container: Pubkey::deserialize(buf)?,
+            index: u64::deserialize(buf)?,
+            name: <[u8; 32]>::deserialize(buf)?,
+            channels: u64::deserialize(buf)?,
+            extension: deserialize_extension(&version, buf)?,
+            version,
+        })
+    }
+}
+
+/// Fields added to [ServerGroup] by [StateVersion::V2]
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone, BorshSchema)]
+pub struct ServerGroupExtension {
+    /// position in groups list shown to users
+    pub position: u64,
+
+    /// parent [ServerGroup], default if top level
+    pub parent: Pubkey,
+
+    /// count of groups having this one as parent
+    pub groups: u64,
+
+    /// bump of base of [Server] lists group is derived from
+    pub container_bump: BaseBump,
+    /// bump of base of [GroupChannel] list of group
+    pub bump: BaseBump,
+
+    /// position given to next added [GroupChannel], see [crate::program::push_position]
+    pub next_channel_position: u64,
+
+    /// space for new fields
+    pub reserved: [u8; 68],
+}
+
+impl Default for ServerGroupExtension {
+    fn default() -> Self {
+        Self {
+            position: 0,
+            parent: Pubkey::default(),
+            groups: 0,
+            container_bump: BaseBump::default(),
+            bump: BaseBump::default(),
+            next_channel_position: 0,
+            reserved: [0; 68],
+        }
+    }
+}
+
 /// state
 #[repr(C)]
-#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone, BorshSchema, Default)]
+#[derive(PartialEq, Debug, Clone, BorshSchema, Default)]
 pub struct GroupChannel {
     /// version
     pub version: StateVersion,
@@ -255,16 +750,374 @@ pub struct GroupChannel {
 
     /// to
     pub channel: Pubkey,
+
+    /// stored only by [StateVersion::V2]
+    pub extension: GroupChannelExtension,
 }
 
 impl GroupChannel {
+    /// data size of [StateVersion::V1]
+    pub const LEN_V1: u64 = 73;
     /// data size
-    pub const LEN: u64 = 73;
+    pub const LEN: u64 = 105;
 
     /// entity type used for seed
     pub const SEED: &'static str = "GroupChannel";
 }
 
+impl BorshSerialize for GroupChannel {
+    fn serialize<W: Write>(&self, writer: &mut W) -> IoResult<()> {
+        self.version.serialize(writer)?;
+        self.container.serialize(writer)?;
+        self.index.serialize(writer)?;
+        self.channel.serialize(writer)?;
+        serialize_extension(&self.version, &self.extension, writer)
+    }
+}
+
+impl BorshDeserialize for GroupChannel {
+    fn deserialize(buf: &mut &[u8]) -> IoResult<Self> {
+        let version = StateVersion::deserialize(buf)?;
+        Ok(Self {
+            container: Pubkey::deserialize(buf)?,
+            index: u64::deserialize(buf)?,
+            channel: Pubkey::deserialize(buf)?,
+            extension: deserialize_extension(&version, buf)?,
+            version,
+        })
+    }
+}
+
+/// Fields added to [GroupChannel] by [StateVersion::V2]
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone, BorshSchema, Default)]
+pub struct GroupChannelExtension {
+    /// position in group shown to users
+    pub position: u64,
+
+    /// bump of base of [ServerGroup] lists group channel is derived from
+    pub container_bump: BaseBump,
+
+    /// space for new fields
+    pub reserved: [u8; 22],
+}
+
+/// Message pinned in channel.
+/// Message itself lives off chain, clients check its content against hash.
+/// Has program derived address from ServerChannel
+/// state
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone, BorshSchema)]
+pub struct PinnedMessage {
+    /// version
+    pub version: StateVersion,
+    /// channel
+    pub container: Pubkey,
+    /// [ServerChannel::pins] index used to derive address
+    pub index: u64,
+
+    /// Multihash of message content
+    pub content_hash: [u8; 64],
+    /// dweller who wrote message
+    pub author: Pubkey,
+    /// administrator dweller who pinned message
+    pub pinned_by: Pubkey,
+}
+
+impl Default for PinnedMessage {
+    fn default() -> Self {
+        Self {
+            version: StateVersion::default(),
+            container: Pubkey::default(),
+            index: 0,
+            content_hash: [0; 64],
+            author: Pubkey::default(),
+            pinned_by: Pubkey::default(),
+        }
+    }
+}
+
+impl PinnedMessage {
+    /// data size
+    pub const LEN: u64 = 169;
+    /// entity type used for seed
+    pub const SEED: &'static str = "PinnedMessage";
+}
+
+/// Program wide list of public servers.
+/// Has program derived address from program itself, see [ServerDirectory::address]
+/// state
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone, BorshSchema, Default)]
+pub struct ServerDirectory {
+    /// version
+    pub version: StateVersion,
+    /// used to derive ServerListing
+    pub listings: u64,
+    /// bump of base of program, directory is derived from
+    pub container_bump: BaseBump,
+    /// bump of base of [ServerListing] list of directory
+    pub bump: BaseBump,
+}
+
+impl ServerDirectory {
+    /// data size
+    pub const LEN: u64 = 13;
+    /// entity type used for seed
+    pub const SEED: &'static str = "ServerDirectory";
+
+    /// the only directory of program
+    pub fn address(program_id: &Pubkey) -> Result<Pubkey, PubkeyError> {
+        create_index_with_seed(program_id, Self::SEED, program_id, 0)
+    }
+
+    /// address of initialized directory, derived with stored bump
+    pub fn derived_address(&self, program_id: &Pubkey) -> Result<Pubkey, PubkeyError> {
+        create_index_with_bump(
+            program_id,
+            Self::SEED,
+            program_id,
+            self.container_bump.get(),
+            0,
+        )
+    }
+
+    /// addresses of [ServerListing] on page of `page_size` entries,
+    /// last page may be shorter, pages after last are empty
+    pub fn page(
+        &self,
+        program_id: &Pubkey,
+        page: u64,
+        page_size: u64,
+    ) -> Result<Vec<Pubkey>, PubkeyError> {
+        let directory = self.derived_address(program_id)?;
+        let start = page.saturating_mul(page_size).min(self.listings);
+        let end = start.saturating_add(page_size).min(self.listings);
+        (start..end)
+            .map(|index| {
+                create_index_with_bump(
+                    program_id,
+                    ServerListing::SEED,
+                    &directory,
+                    self.bump.get(),
+                    index,
+                )
+            })
+            .collect()
+    }
+}
+
+/// Server shown in directory.
+/// Has program derived address from ServerDirectory
+/// state
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone, BorshSchema, Default)]
+pub struct ServerListing {
+    /// version
+    pub version: StateVersion,
+    /// directory
+    pub container: Pubkey,
+    /// [ServerDirectory::listings] index used to derive address
+    pub index: u64,
+    /// listed
+    pub server: Pubkey,
+}
+
+impl ServerListing {
+    /// data size
+    pub const LEN: u64 = 73;
+    /// entity type used for seed
+    pub const SEED: &'static str = "ServerListing";
+}
+
+/// Direct messages channel between dwellers, not bound to any server.
+/// Has program derived address from creator Dweller,
+/// closed when less than [DirectChannel::MIN_PARTICIPANTS] are left
+/// state
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone, BorshSchema)]
+pub struct DirectChannel {
+    /// version
+    pub version: StateVersion,
+    /// creator dweller, only one who can remove participants
+    pub container: Pubkey,
+    /// [DwellerExtension::direct_channels] index used to derive address
+    pub index: u64,
+
+    /// count of used `participants`
+    pub participants_len: u8,
+    /// dwellers in channel, first `participants_len` are valid
+    pub participants: [Pubkey; 10],
+
+    /// Multihash of encrypted database of channel
+    pub db_hash: [u8; 64],
+
+    /// bump of base of indexed lists of creator dweller
+    pub container_bump: BaseBump,
+}
+
+impl Default for DirectChannel {
+    fn default() -> Self {
+        Self {
+            version: StateVersion::default(),
+            container: Pubkey::default(),
+            index: 0,
+            participants_len: 0,
+            participants: [Pubkey::default(); 10],
+            db_hash: [0; 64],
+            container_bump: BaseBump::default(),
+        }
+    }
+}
+
+impl DirectChannel {
+    /// data size
+    pub const LEN: u64 = 428;
+    /// entity type used for seed
+    pub const SEED: &'static str = "DirectChannel";
+    /// minimal count of participants to create channel
+    pub const MIN_PARTICIPANTS: usize = 2;
+    /// maximal count of participants
+    pub const MAX_PARTICIPANTS: usize = 10;
+
+    /// valid participants
+    pub fn participants(&self) -> &[Pubkey] {
+        &self.participants[..self.participants_len as usize]
+    }
+
+    /// true if dweller is participant
+    pub fn contains(&self, dweller: &Pubkey) -> bool {
+        self.participants().contains(dweller)
+    }
+
+    /// adds participant, returns false if already added or channel is full
+    pub fn add(&mut self, dweller: &Pubkey) -> bool {
+        if self.contains(dweller) || self.participants().len() >= Self::MAX_PARTICIPANTS {
+            return false;
+        }
+
+        self.participants[self.participants_len as usize] = *dweller;
+        self.participants_len += 1;
+        true
+    }
+
+    /// removes participant by swapping it with last one, returns false if not participant
+    pub fn remove(&mut self, dweller: &Pubkey) -> bool {
+        match self.participants().iter().position(|x| x == dweller) {
+            Some(position) => {
+                let last = self.participants_len as usize - 1;
+                self.participants.swap(position, last);
+                self.participants[last] = Pubkey::default();
+                self.participants_len -= 1;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl Indexed for DwellerServer {
+    fn index_mut(&mut self) -> &mut u64 {
+        &mut self.index
+    }
+}
+
+impl Indexed for ServerMember {
+    fn index_mut(&mut self) -> &mut u64 {
+        &mut self.index
+    }
+}
+
+impl Indexed for ServerMemberStatus {
+    fn index_mut(&mut self) -> &mut u64 {
+        &mut self.index
+    }
+}
+
+impl Indexed for ServerAdministrator {
+    fn index_mut(&mut self) -> &mut u64 {
+        &mut self.index
+    }
+}
+
+impl Indexed for ServerChannel {
+    fn index_mut(&mut self) -> &mut u64 {
+        &mut self.index
+    }
+
+    fn container_bump(&self) -> Option<u8> {
+        self.extension.container_bump.get()
+    }
+
+    fn bump_mut(&mut self) -> Option<&mut BaseBump> {
+        Some(&mut self.extension.bump)
+    }
+}
+
+impl Indexed for ServerGroup {
+    fn index_mut(&mut self) -> &mut u64 {
+        &mut self.index
+    }
+
+    fn container_bump(&self) -> Option<u8> {
+        self.extension.container_bump.get()
+    }
+
+    fn bump_mut(&mut self) -> Option<&mut BaseBump> {
+        Some(&mut self.extension.bump)
+    }
+}
+
+impl Indexed for GroupChannel {
+    fn index_mut(&mut self) -> &mut u64 {
+        &mut self.index
+    }
+
+    fn container_bump(&self) -> Option<u8> {
+        self.extension.container_bump.get()
+    }
+}
+
+impl Indexed for DirectChannel {
+    fn index_mut(&mut self) -> &mut u64 {
+        &mut self.index
+    }
+
+    fn container_bump(&self) -> Option<u8> {
+        self.container_bump.get()
+    }
+}
+
+impl Indexed for ServerListing {
+    fn index_mut(&mut self) -> &mut u64 {
+        &mut self.index
+    }
+}
+
+impl Indexed for PinnedMessage {
+    fn index_mut(&mut self) -> &mut u64 {
+        &mut self.index
+    }
+}
+
+impl Ordered for ServerChannel {
+    fn position_mut(&mut self) -> &mut u64 {
+        &mut self.extension.position
+    }
+}
+
+impl Ordered for ServerGroup {
+    fn position_mut(&mut self) -> &mut u64 {
+        &mut self.extension.position
+    }
+}
+
+impl Ordered for GroupChannel {
+    fn position_mut(&mut self) -> &mut u64 {
+        &mut self.extension.position
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -295,6 +1148,10 @@ mod test {
             ServerGroup::LEN,
             solana_program::borsh::get_packed_len::<ServerGroup>() as u64
         );
+        assert_eq!(
+            DwellerDelegate::LEN,
+            solana_program::borsh::get_packed_len::<DwellerDelegate>() as u64
+        );
         assert_eq!(
             ServerMember::LEN,
             solana_program::borsh::get_packed_len::<ServerMember>() as u64
@@ -307,5 +1164,150 @@ mod test {
             GroupChannel::LEN,
             solana_program::borsh::get_packed_len::<GroupChannel>() as u64
         );
+        assert_eq!(
+            PinnedMessage::LEN,
+            solana_program::borsh::get_packed_len::<PinnedMessage>() as u64
+        );
+        assert_eq!(
+            DirectChannel::LEN,
+            solana_program::borsh::get_packed_len::<DirectChannel>() as u64
+        );
+        assert_eq!(
+            ServerDirectory::LEN,
+            solana_program::borsh::get_packed_len::<ServerDirectory>() as u64
+        );
+        assert_eq!(
+            ServerListing::LEN,
+            solana_program::borsh::get_packed_len::<ServerListing>() as u64
+        );
+    }
+
+    #[test]
+    fn directory_page() {
+        let program_id = crate::id();
+        let directory = ServerDirectory {
+            version: StateVersion::V1,
+            listings: 5,
+            ..ServerDirectory::default()
+        };
+        let directory_key = ServerDirectory::address(&program_id).unwrap();
+
+        let page = directory.page(&program_id, 1, 2).unwrap();
+        assert_eq!(
+            page,
+            vec![
+                create_index_with_seed(&program_id, ServerListing::SEED, &directory_key, 2)
+                    .unwrap(),
+                create_index_with_seed(&program_id, ServerListing::SEED, &directory_key, 3)
+                    .unwrap(),
+            ]
+        );
+        assert_eq!(directory.page(&program_id, 2, 2).unwrap().len(), 1);
+        assert!(directory.page(&program_id, 3, 2).unwrap().is_empty());
+        assert!(directory
+            .page(&program_id, u64::MAX, u64::MAX)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn direct_channel_participants() {
+        let mut channel = DirectChannel::default();
+        let dwellers: Vec<_> = (0..=DirectChannel::MAX_PARTICIPANTS)
+            .map(|_| Pubkey::new_unique())
+            .collect();
+
+        for dweller in &dwellers[..DirectChannel::MAX_PARTICIPANTS] {
+            assert!(channel.add(dweller));
+        }
+        assert!(!channel.add(&dwellers[0]));
+        assert!(!channel.add(&dwellers[DirectChannel::MAX_PARTICIPANTS]));
+
+        assert!(channel.remove(&dwellers[1]));
+        assert!(!channel.remove(&dwellers[1]));
+        assert!(!channel.contains(&dwellers[1]));
+        assert_eq!(
+            channel.participants()[1],
+            dwellers[DirectChannel::MAX_PARTICIPANTS - 1]
+        );
+        assert_eq!(
+            channel.participants().len(),
+            DirectChannel::MAX_PARTICIPANTS - 1
+        );
+        assert_eq!(
+            channel.participants[DirectChannel::MAX_PARTICIPANTS - 1],
+            Pubkey::default()
+        );
+    }
+
+    #[test]
+    fn channel_settings() {
+        let mut channel = ServerChannel::default();
+        assert_eq!(
+            channel.settings(),
+            ChannelSettings {
+                topic: "",
+                slow_mode: None,
+                nsfw: false,
+            }
+        );
+
+        channel.extension.topic[..5].copy_from_slice(b"rules");
+        channel.extension.slow_mode = 10;
+        assert_eq!(channel.settings().topic, "rules");
+        assert_eq!(channel.settings().slow_mode, Some(10));
+
+        channel.extension.topic[0] = 0xff;
+        assert_eq!(ServerChannel::read_topic(&channel.extension.topic), None);
+        assert_eq!(channel.settings().topic, "");
+    }
+
+    #[test]
+    fn v1_layout() {
+        let mut data = [0u8; Dweller::LEN_V1 as usize];
+        data[0] = StateVersion::V1 as u8;
+        let dweller = Dweller::try_from_slice(&data).unwrap();
+        assert_eq!(dweller.extension, DwellerExtension::default());
+        assert_eq!(
+            dweller.try_to_vec().unwrap().len(),
+            Dweller::LEN_V1 as usize
+        );
+
+        let mut data = [0u8; Server::LEN_V1 as usize];
+        data[0] = StateVersion::V1 as u8;
+        let mut server = Server::try_from_slice(&data).unwrap();
+        assert_eq!(server.try_to_vec().unwrap().len(), Server::LEN_V1 as usize);
+
+        server.extension.public = true;
+        assert!(server.try_to_vec().is_err());
+        server.version = StateVersion::V2;
+        assert_eq!(server.try_to_vec().unwrap().len(), Server::LEN as usize);
+
+        let mut data = [0u8; ServerChannel::LEN_V1 as usize];
+        data[0] = StateVersion::V1 as u8;
+        let mut channel = ServerChannel::try_from_slice(&data).unwrap();
+        assert_eq!(
+            channel.try_to_vec().unwrap().len(),
+            ServerChannel::LEN_V1 as usize
+        );
+        channel.extension.pins = 1;
+        assert!(channel.try_to_vec().is_err());
+
+        let mut data = [0u8; ServerGroup::LEN_V1 as usize];
+        data[0] = StateVersion::V1 as u8;
+        let group = ServerGroup::try_from_slice(&data).unwrap();
+        assert_eq!(
+            group.try_to_vec().unwrap().len(),
+            ServerGroup::LEN_V1 as usize
+        );
+
+        let mut data = [0u8; GroupChannel::LEN_V1 as usize];
+        data[0] = StateVersion::V1 as u8;
+        let mut group_channel = GroupChannel::try_from_slice(&data).unwrap();
+        group_channel.version = StateVersion::V2;
+        assert_eq!(
+            group_channel.try_to_vec().unwrap().len(),
+            GroupChannel::LEN as usize
+        );
     }
 }
@@ -0,0 +1,1161 @@
+#![cfg(feature = "test-bpf")]
+//! Compute units consumed by every [Instruction] at several collection sizes.
+//!
+//! Runs built program, so use `cargo test-bpf --test compute -- --nocapture` to see report.
+//! Fails if any instruction consumes more than its budget in [BUDGETS].
+
+use std::collections::BTreeMap;
+
+use borsh::BorshDeserialize;
+use solana_program::{pubkey::Pubkey, rent::Rent, system_instruction};
+use solana_program_test::*;
+use solana_sdk::{
+    signature::{Keypair, Signer},
+    transaction::Transaction,
+};
+
+use satellite_servers::{
+    id,
+    instruction::{
+        self, AddChannelInput, AddDelegateInput, AddressTypeInput, CreateGroupInput,
+        InitializeDwellerInput, InitializeServerInput, Instruction, PinMessageInput,
+        ReorderChannelsInput, ResizeAccountInput, SetDwellerStatusInput, SetHashInput,
+        SetNameInput, SetServerDescriptionInput, SetServerGateInput, SetServerPublicInput,
+        SetServerTagsInput, UpdateChannelInput,
+    },
+    program::{create_base_index_with_seed, find_base_bump},
+    state::*,
+};
+
+/// count of entities in each collection benchmarked instructions work on
+const SIZES: &[usize] = &[1, 4, 8];
+
+/// compute units instruction may consume at size of collection it works on,
+/// fixed part plus part per entity, so handlers walking collections are capped at each of [SIZES]
+const BUDGETS: &[(&str, u64, u64)] = &[
+    ("CreateDerivedAccount", 60_000, 0),
+    ("InitializeDweller", 40_000, 0),
+    ("InitializeServer", 80_000, 0),
+    ("ResizeAccount", 60_000, 0),
+    ("SetDwellerName", 50_000, 0),
+    ("SetDwellerPhoto", 50_000, 0),
+    ("SetDwellerStatus", 50_000, 0),
+    ("SetServerName", 80_000, 0),
+    ("SetServerDb", 80_000, 0),
+    ("SetServerDescription", 80_000, 0),
+    ("SetServerBanner", 80_000, 0),
+    ("SetServerTags", 80_000, 0),
+    ("SetServerPublic", 80_000, 0),
+    ("SetServerGate", 80_000, 0),
+    ("AddAdmin", 80_000, 0),
+    ("RemoveAdmin", 100_000, 0),
+    ("InviteToServer", 80_000, 0),
+    ("RevokeInviteServer", 100_000, 0),
+    ("JoinServer", 100_000, 0),
+    ("LeaveServer", 120_000, 0),
+    ("AddChannel", 80_000, 0),
+    ("UpdateChannel", 80_000, 0),
+    ("DeleteChannel", 100_000, 12_000),
+    ("ReorderChannels", 80_000, 8_000),
+    ("CreateGroup", 80_000, 0),
+    ("DeleteGroup", 80_000, 20_000),
+    ("AddChannelToGroup", 100_000, 0),
+    ("RemoveChannelFromGroup", 100_000, 0),
+    ("PinMessage", 80_000, 0),
+    ("UnpinMessage", 100_000, 0),
+    ("ListServer", 80_000, 0),
+    ("UnlistServer", 100_000, 0),
+    ("CreateDirectChannel", 60_000, 5_000),
+    ("AddDirectChannelParticipant", 60_000, 2_000),
+    ("RemoveDirectChannelParticipant", 60_000, 2_000),
+    ("LeaveDirectChannel", 60_000, 2_000),
+    ("AddDelegate", 50_000, 0),
+    ("RemoveDelegate", 50_000, 0),
+    ("CloseDweller", 50_000, 8_000),
+];
+
+fn budget(name: &str, size: usize) -> Option<u64> {
+    BUDGETS
+        .iter()
+        .find(|(instruction, ..)| *instruction == name)
+        .map(|(_, fixed, per_entity)| fixed + per_entity * size as u64)
+}
+
+/// names of all [Instruction] variants
+fn instruction_names() -> Vec<String> {
+    (0..=u8::MAX)
+        .map_while(|tag| Instruction::try_from_slice(&[tag]).ok())
+        .map(|instruction| format!("{:?}", instruction))
+        .collect()
+}
+
+/// parses units consumed by program from transaction logs
+fn consumed(logs: &[String]) -> u64 {
+    let prefix = format!("Program {} consumed ", id());
+    logs.iter()
+        .filter_map(|log| log.strip_prefix(&prefix))
+        .filter_map(|rest| rest.split_whitespace().next())
+        .map(|units| units.parse::<u64>().expect("units"))
+        .sum()
+}
+
+struct Bench {
+    context: ProgramTestContext,
+    rent: Rent,
+    size: usize,
+    /// maximal units consumed by instruction at size
+    report: BTreeMap<(String, usize), u64>,
+}
+
+impl Bench {
+    async fn new(size: usize) -> Self {
+        let mut program_test = ProgramTest::new("satellite_servers", id(), None);
+        program_test.prefer_bpf(true);
+        let mut context = program_test.start_with_context().await;
+        let rent = context.banks_client.get_rent().await.unwrap();
+        Self {
+            context,
+            rent,
+            size,
+            report: BTreeMap::new(),
+        }
+    }
+
+    /// processes instruction in own transaction and records units it consumed
+    async fn run(
+        &mut self,
+        instruction: solana_program::instruction::Instruction,
+        signers: &[&Keypair],
+    ) -> u64 {
+        let name = format!(
+            "{:?}",
+            Instruction::try_from_slice(&instruction.data[..1]).unwrap()
+        );
+        let mut transaction =
+            Transaction::new_with_payer(&[instruction], Some(&self.context.payer.pubkey()));
+        let mut all_signers = vec![&self.context.payer];
+        all_signers.extend_from_slice(signers);
+        transaction.sign(&all_signers, self.context.last_blockhash);
+
+        let result = self
+            .context
+            .banks_client
+            .process_transaction_with_metadata(transaction)
+            .await
+            .unwrap();
+        if let Err(error) = result.result {
+            panic!("{} at size {} failed: {:?}", name, self.size, error);
+        }
+
+        let units = consumed(&result.metadata.expect("metadata").log_messages);
+        let recorded = self.report.entry((name, self.size)).or_default();
+        *recorded = (*recorded).max(units);
+        units
+    }
+
+    /// system account of `len` owned by program
+    async fn create_account(&mut self, account: &Keypair, len: u64) {
+        let mut transaction = Transaction::new_with_payer(
+            &[system_instruction::create_account(
+                &self.context.payer.pubkey(),
+                &account.pubkey(),
+                self.rent.minimum_balance(len as usize),
+                len,
+                &id(),
+            )],
+            Some(&self.context.payer.pubkey()),
+        );
+        transaction.sign(&[&self.context.payer, account], self.context.last_blockhash);
+        self.context
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+    }
+
+    async fn dweller(&mut self, len: u64) -> Keypair {
+        self.initialize_dweller(Keypair::new(), len).await
+    }
+
+    async fn initialize_dweller(&mut self, dweller: Keypair, len: u64) -> Keypair {
+        self.create_account(&dweller, len).await;
+        self.run(
+            instruction::initialize_dweller(
+                &dweller.pubkey(),
+                InitializeDwellerInput {
+                    name: [1; 32],
+                    hash: [2; 64],
+                    status: [3; 128],
+                },
+            )
+            .unwrap(),
+            &[&dweller],
+        )
+        .await;
+        dweller
+    }
+
+    async fn derived(
+        &mut self,
+        owner: &Pubkey,
+        seed: &str,
+        index: u64,
+        input: AddressTypeInput,
+    ) -> Pubkey {
+        let (address, base, ..) = create_base_index_with_seed(&id(), seed, owner, index).unwrap();
+        let payer = self.context.payer.pubkey();
+        self.run(
+            instruction::create_derived_account(&id(), &payer, owner, &base, &address, input)
+                .unwrap(),
+            &[],
+        )
+        .await;
+        address
+    }
+}
+
+/// keypair whose base bump is found after several tries,
+/// so deriving from V1 state, which has no stored bump, costs visibly more
+fn far_bump_keypair() -> Keypair {
+    loop {
+        let keypair = Keypair::new();
+        if find_base_bump(&id(), &keypair.pubkey()) < u8::MAX - 10 {
+            return keypair;
+        }
+    }
+}
+
+/// runs every instruction on collections of `size`
+async fn bench(size: usize) -> BTreeMap<(String, usize), u64> {
+    let mut bench = Bench::new(size).await;
+    let payer = bench.context.payer.pubkey();
+
+    // dwellers
+    let owner = bench.dweller(Dweller::LEN).await;
+    let mut members = Vec::with_capacity(size);
+    for _ in 0..size {
+        members.push(bench.dweller(Dweller::LEN).await);
+    }
+
+    bench
+        .run(
+            instruction::set_dweller_name(&owner.pubkey(), None, &SetNameInput { name: [4; 32] })
+                .unwrap(),
+            &[&owner],
+        )
+        .await;
+    bench
+        .run(
+            instruction::set_dweller_photo(&owner.pubkey(), None, &SetHashInput { hash: [5; 64] })
+                .unwrap(),
+            &[&owner],
+        )
+        .await;
+    bench
+        .run(
+            instruction::set_dweller_status(
+                &owner.pubkey(),
+                None,
+                &SetDwellerStatusInput { status: [6; 128] },
+            )
+            .unwrap(),
+            &[&owner],
+        )
+        .await;
+
+    let dweller_v1 = bench.dweller(Dweller::LEN_V1).await;
+    bench
+        .run(
+            instruction::resize_account(
+                &payer,
+                &dweller_v1.pubkey(),
+                &dweller_v1.pubkey(),
+                &ResizeAccountInput::Dweller,
+            )
+            .unwrap(),
+            &[&dweller_v1],
+        )
+        .await;
+
+    // server
+    let server = Keypair::new();
+    bench.create_account(&server, Server::LEN).await;
+    let owner_server = bench
+        .derived(
+            &owner.pubkey(),
+            DwellerServer::SEED,
+            0,
+            AddressTypeInput::DwellerServer(0),
+        )
+        .await;
+    let mut server_members = Vec::with_capacity(size + 3);
+    for index in 0..=size as u64 + 2 {
+        server_members.push(
+            bench
+                .derived(
+                    &server.pubkey(),
+                    ServerMember::SEED,
+                    index,
+                    AddressTypeInput::ServerMember(index),
+                )
+                .await,
+        );
+    }
+    bench
+        .run(
+            instruction::initialize_server(
+                &owner.pubkey(),
+                &server.pubkey(),
+                &owner_server,
+                &server_members[0],
+                InitializeServerInput { name: [7; 32] },
+            )
+            .unwrap(),
+            &[&owner, &server],
+        )
+        .await;
+
+    let server_administrator = bench
+        .derived(
+            &server.pubkey(),
+            ServerAdministrator::SEED,
+            0,
+            AddressTypeInput::ServerAdministrator(0),
+        )
+        .await;
+    bench
+        .run(
+            instruction::add_admin(
+                &owner.pubkey(),
+                &owner.pubkey(),
+                &server.pubkey(),
+                &server_administrator,
+            )
+            .unwrap(),
+            &[&owner],
+        )
+        .await;
+
+    let admin = (owner.pubkey(), server_administrator, server.pubkey());
+    let setters = vec![
+        instruction::set_server_name(
+            &admin.2,
+            &admin.0,
+            &admin.1,
+            &SetNameInput { name: [8; 32] },
+        ),
+        instruction::set_server_db(
+            &admin.2,
+            &admin.0,
+            &admin.1,
+            &SetHashInput { hash: [9; 64] },
+        ),
+        instruction::set_server_description(
+            &admin.0,
+            &admin.1,
+            &admin.2,
+            &SetServerDescriptionInput {
+                description: [10; 256],
+            },
+        ),
+        instruction::set_server_banner(
+            &admin.0,
+            &admin.1,
+            &admin.2,
+            &SetHashInput { hash: [11; 64] },
+        ),
+        instruction::set_server_tags(
+            &admin.0,
+            &admin.1,
+            &admin.2,
+            &SetServerTagsInput {
+                tags: [[12; 16]; 4],
+            },
+        ),
+        instruction::set_server_public(
+            &admin.0,
+            &admin.1,
+            &admin.2,
+            &SetServerPublicInput { public: true },
+        ),
+        // invites still work for gated server, so joins below are not affected
+        instruction::set_server_gate(
+            &admin.0,
+            &admin.1,
+            &admin.2,
+            &SetServerGateInput {
+                gate: TokenGate {
+                    kind: TokenGateKind::SplToken,
+                    mint: Pubkey::new_unique(),
+                    amount: 1,
+                },
+            },
+        ),
+    ];
+    for setter in setters {
+        bench.run(setter.unwrap(), &[&owner]).await;
+    }
+
+    // members
+    let mut member_statuses = Vec::with_capacity(size + 3);
+    for index in 0..=size as u64 + 2 {
+        member_statuses.push(
+            bench
+                .derived(
+                    &server.pubkey(),
+                    ServerMemberStatus::SEED,
+                    index,
+                    AddressTypeInput::ServerMemberStatus(index),
+                )
+                .await,
+        );
+    }
+    let mut member_servers = Vec::with_capacity(size);
+    for (index, member) in members.iter().enumerate() {
+        let member_server = bench
+            .derived(
+                &member.pubkey(),
+                DwellerServer::SEED,
+                0,
+                AddressTypeInput::DwellerServer(0),
+            )
+            .await;
+        bench
+            .run(
+                instruction::invite_to_server(
+                    &admin.2,
+                    &admin.0,
+                    &admin.1,
+                    &member.pubkey(),
+                    &member_statuses[index],
+                )
+                .unwrap(),
+                &[&owner],
+            )
+            .await;
+        bench
+            .run(
+                instruction::join_server(
+                    &server.pubkey(),
+                    &server_members[index + 1],
+                    &member_statuses[index],
+                    &member.pubkey(),
+                    &member_server,
+                    None,
+                )
+                .unwrap(),
+                &[member],
+            )
+            .await;
+        member_servers.push(member_server);
+    }
+
+    // same join by dweller without and with stored bump
+    let mut joins = Vec::with_capacity(2);
+    for (index, len) in [(size, Dweller::LEN_V1), (size + 1, Dweller::LEN)] {
+        let joiner = bench.initialize_dweller(far_bump_keypair(), len).await;
+        let joiner_server = bench
+            .derived(
+                &joiner.pubkey(),
+                DwellerServer::SEED,
+                0,
+                AddressTypeInput::DwellerServer(0),
+            )
+            .await;
+        bench
+            .run(
+                instruction::invite_to_server(
+                    &admin.2,
+                    &admin.0,
+                    &admin.1,
+                    &joiner.pubkey(),
+                    &member_statuses[index],
+                )
+                .unwrap(),
+                &[&owner],
+            )
+            .await;
+        let units = bench
+            .run(
+                instruction::join_server(
+                    &server.pubkey(),
+                    &server_members[index + 1],
+                    &member_statuses[index],
+                    &joiner.pubkey(),
+                    &joiner_server,
+                    None,
+                )
+                .unwrap(),
+                &[&joiner],
+            )
+            .await;
+        joins.push(units);
+    }
+    assert!(
+        joins[0] > joins[1],
+        "stored bump does not save units: {:?}",
+        joins
+    );
+
+    bench
+        .run(
+            instruction::invite_to_server(
+                &admin.2,
+                &admin.0,
+                &admin.1,
+                &members[0].pubkey(),
+                &member_statuses[size + 2],
+            )
+            .unwrap(),
+            &[&owner],
+        )
+        .await;
+    bench
+        .run(
+            instruction::revoke_invite_server(
+                &admin.2,
+                &admin.0,
+                &admin.1,
+                &member_statuses[0],
+                &member_statuses[size + 2],
+            )
+            .unwrap(),
+            &[&owner],
+        )
+        .await;
+
+    bench
+        .run(
+            instruction::leave_server(
+                &server.pubkey(),
+                &server_members[1],
+                &server_members[size + 2],
+                &members[0].pubkey(),
+                &member_servers[0],
+                &member_servers[0],
+                None,
+            )
+            .unwrap(),
+            &[&members[0]],
+        )
+        .await;
+
+    // channels and groups
+    let mut channels = Vec::with_capacity(size);
+    for index in 0..size as u64 {
+        let channel = bench
+            .derived(
+                &server.pubkey(),
+                ServerChannel::SEED,
+                index,
+                AddressTypeInput::ServerChannel(index),
+            )
+            .await;
+        bench
+            .run(
+                instruction::add_channel(
+                    &admin.0,
+                    &admin.1,
+                    &admin.2,
+                    &channel,
+                    &AddChannelInput {
+                        type_id: 0,
+                        name: [13; 32],
+                    },
+                )
+                .unwrap(),
+                &[&owner],
+            )
+            .await;
+        channels.push(channel);
+    }
+
+    let in_order: Vec<&Pubkey> = channels.iter().rev().collect();
+    bench
+        .run(
+            instruction::reorder_channels(
+                &admin.0,
+                &admin.1,
+                &admin.2,
+                None,
+                &in_order,
+                &ReorderChannelsInput::ServerChannels,
+            )
+            .unwrap(),
+            &[&owner],
+        )
+        .await;
+
+    let group = bench
+        .derived(
+            &server.pubkey(),
+            ServerGroup::SEED,
+            0,
+            AddressTypeInput::ServerGroup(0),
+        )
+        .await;
+    bench
+        .run(
+            instruction::create_group(
+                &admin.0,
+                &admin.1,
+                &admin.2,
+                &group,
+                None,
+                &CreateGroupInput { name: [14; 32] },
+            )
+            .unwrap(),
+            &[&owner],
+        )
+        .await;
+
+    // channel in group at each index, changed as swap with last does
+    let mut group_channels = Vec::with_capacity(size);
+    let mut in_group = Vec::with_capacity(size);
+    for (index, channel) in channels.iter().enumerate() {
+        let group_channel = bench
+            .derived(
+                &group,
+                GroupChannel::SEED,
+                index as u64,
+                AddressTypeInput::GroupChannel(index as u64),
+            )
+            .await;
+        bench
+            .run(
+                instruction::add_channel_to_group(
+                    &admin.2,
+                    &admin.0,
+                    &admin.1,
+                    channel,
+                    &group,
+                    &group_channel,
+                )
+                .unwrap(),
+                &[&owner],
+            )
+            .await;
+        group_channels.push(group_channel);
+        in_group.push(*channel);
+    }
+
+    bench
+        .run(
+            instruction::remove_channel_from_group(
+                &admin.2,
+                &admin.0,
+                &admin.1,
+                &group,
+                &channels[0],
+                &group_channels[0],
+                &group_channels[size - 1],
+            )
+            .unwrap(),
+            &[&owner],
+        )
+        .await;
+    in_group.swap_remove(0);
+
+    bench
+        .run(
+            instruction::add_channel_to_group(
+                &admin.2,
+                &admin.0,
+                &admin.1,
+                &channels[0],
+                &group,
+                &group_channels[size - 1],
+            )
+            .unwrap(),
+            &[&owner],
+        )
+        .await;
+    in_group.push(channels[0]);
+
+    // pins
+    let mut pins = Vec::with_capacity(size);
+    for index in 0..size as u64 {
+        let pin = bench
+            .derived(
+                &channels[0],
+                PinnedMessage::SEED,
+                index,
+                AddressTypeInput::PinnedMessage(index),
+            )
+            .await;
+        bench
+            .run(
+                instruction::pin_message(
+                    &admin.0,
+                    &admin.1,
+                    &admin.2,
+                    &channels[0],
+                    &pin,
+                    &PinMessageInput {
+                        content_hash: [15; 64],
+                        author: owner.pubkey(),
+                    },
+                )
+                .unwrap(),
+                &[&owner],
+            )
+            .await;
+        pins.push(pin);
+    }
+    bench
+        .run(
+            instruction::unpin_message(
+                &admin.0,
+                &admin.1,
+                &admin.2,
+                &channels[0],
+                &pins[0],
+                &pins[size - 1],
+            )
+            .unwrap(),
+            &[&owner],
+        )
+        .await;
+    bench
+        .run(
+            instruction::update_channel(
+                &admin.0,
+                &admin.1,
+                &admin.2,
+                &channels[0],
+                &UpdateChannelInput {
+                    topic: [b'a'; 128],
+                    slow_mode: ServerChannel::MAX_SLOW_MODE,
+                    nsfw: true,
+                },
+            )
+            .unwrap(),
+            &[&owner],
+        )
+        .await;
+
+    // group still has `size` channels, so each of them is closed with group
+    let pairs: Vec<(&Pubkey, &Pubkey)> = group_channels.iter().zip(in_group.iter()).collect();
+    bench
+        .run(
+            instruction::delete_group(
+                &admin.0,
+                &admin.1,
+                &admin.2,
+                &group,
+                &group,
+                None,
+                &pairs,
+                &[],
+                &[],
+            )
+            .unwrap(),
+            &[&owner],
+        )
+        .await;
+
+    // channel at position 0 after reorder, other positions are kept
+    bench
+        .run(
+            instruction::delete_channel(
+                &admin.0,
+                &admin.1,
+                &admin.2,
+                &channels[size - 1],
+                &channels[size - 1],
+                None,
+                &[],
+            )
+            .unwrap(),
+            &[&owner],
+        )
+        .await;
+
+    // last channel with `size` pins is moved into deleted one, so is last group with `size` channels
+    bench
+        .run(
+            instruction::add_channel(
+                &admin.0,
+                &admin.1,
+                &admin.2,
+                &channels[size - 1],
+                &AddChannelInput {
+                    type_id: 0,
+                    name: [13; 32],
+                },
+            )
+            .unwrap(),
+            &[&owner],
+        )
+        .await;
+    let channel_last = bench
+        .derived(
+            &server.pubkey(),
+            ServerChannel::SEED,
+            size as u64,
+            AddressTypeInput::ServerChannel(size as u64),
+        )
+        .await;
+    bench
+        .run(
+            instruction::add_channel(
+                &admin.0,
+                &admin.1,
+                &admin.2,
+                &channel_last,
+                &AddChannelInput {
+                    type_id: 0,
+                    name: [13; 32],
+                },
+            )
+            .unwrap(),
+            &[&owner],
+        )
+        .await;
+
+    let mut moved_pins = Vec::with_capacity(size);
+    for index in 0..size as u64 {
+        let pin_last = bench
+            .derived(
+                &channel_last,
+                PinnedMessage::SEED,
+                index,
+                AddressTypeInput::PinnedMessage(index),
+            )
+            .await;
+        bench
+            .run(
+                instruction::pin_message(
+                    &admin.0,
+                    &admin.1,
+                    &admin.2,
+                    &channel_last,
+                    &pin_last,
+                    &PinMessageInput {
+                        content_hash: [15; 64],
+                        author: owner.pubkey(),
+                    },
+                )
+                .unwrap(),
+                &[&owner],
+            )
+            .await;
+        // at size 1 deleted channel is first one, whose pin accounts exist
+        let pin = if size == 1 {
+            pins[index as usize]
+        } else {
+            bench
+                .derived(
+                    &channels[size - 1],
+                    PinnedMessage::SEED,
+                    index,
+                    AddressTypeInput::PinnedMessage(index),
+                )
+                .await
+        };
+        moved_pins.push((pin_last, pin));
+    }
+
+    bench
+        .run(
+            instruction::create_group(
+                &admin.0,
+                &admin.1,
+                &admin.2,
+                &group,
+                None,
+                &CreateGroupInput { name: [14; 32] },
+            )
+            .unwrap(),
+            &[&owner],
+        )
+        .await;
+    let group_last = bench
+        .derived(
+            &server.pubkey(),
+            ServerGroup::SEED,
+            1,
+            AddressTypeInput::ServerGroup(1),
+        )
+        .await;
+    bench
+        .run(
+            instruction::create_group(
+                &admin.0,
+                &admin.1,
+                &admin.2,
+                &group_last,
+                None,
+                &CreateGroupInput { name: [14; 32] },
+            )
+            .unwrap(),
+            &[&owner],
+        )
+        .await;
+
+    // group channels are moved into accounts of deleted group, last channel is moved with group
+    let mut moved_group_channels = Vec::with_capacity(size);
+    for (index, channel) in channels[..size - 1]
+        .iter()
+        .chain(Some(&channel_last))
+        .enumerate()
+    {
+        let group_channel_last = bench
+            .derived(
+                &group_last,
+                GroupChannel::SEED,
+                index as u64,
+                AddressTypeInput::GroupChannel(index as u64),
+            )
+            .await;
+        bench
+            .run(
+                instruction::add_channel_to_group(
+                    &admin.2,
+                    &admin.0,
+                    &admin.1,
+                    channel,
+                    &group_last,
+                    &group_channel_last,
+                )
+                .unwrap(),
+                &[&owner],
+            )
+            .await;
+        moved_group_channels.push((group_channel_last, group_channels[index], *channel));
+    }
+    let moved: Vec<(&Pubkey, &Pubkey, &Pubkey)> = moved_group_channels
+        .iter()
+        .map(|(group_channel_last, group_channel, channel)| {
+            (group_channel_last, group_channel, channel)
+        })
+        .collect();
+    bench
+        .run(
+            instruction::delete_group(
+                &admin.0,
+                &admin.1,
+                &admin.2,
+                &group,
+                &group_last,
+                None,
+                &[],
+                &[],
+                &moved,
+            )
+            .unwrap(),
+            &[&owner],
+        )
+        .await;
+
+    let moved: Vec<(&Pubkey, &Pubkey)> = moved_pins
+        .iter()
+        .map(|(pin_last, pin)| (pin_last, pin))
+        .collect();
+    bench
+        .run(
+            instruction::delete_channel(
+                &admin.0,
+                &admin.1,
+                &admin.2,
+                &channels[size - 1],
+                &channel_last,
+                Some(&group_channels[size - 1]),
+                &moved,
+            )
+            .unwrap(),
+            &[&owner],
+        )
+        .await;
+
+    // directory
+    let directory = bench
+        .derived(
+            &id(),
+            ServerDirectory::SEED,
+            0,
+            AddressTypeInput::ServerDirectory,
+        )
+        .await;
+    let listing = bench
+        .derived(
+            &directory,
+            ServerListing::SEED,
+            0,
+            AddressTypeInput::ServerListing(0),
+        )
+        .await;
+    bench
+        .run(
+            instruction::list_server(&owner.pubkey(), &server.pubkey(), &directory, &listing)
+                .unwrap(),
+            &[&owner],
+        )
+        .await;
+    bench
+        .run(
+            instruction::unlist_server(
+                &owner.pubkey(),
+                &server.pubkey(),
+                &directory,
+                &listing,
+                &listing,
+            )
+            .unwrap(),
+            &[&owner],
+        )
+        .await;
+
+    // direct channels
+    let direct_channel = bench
+        .derived(
+            &owner.pubkey(),
+            DirectChannel::SEED,
+            0,
+            AddressTypeInput::DirectChannel(0),
+        )
+        .await;
+    let participants: Vec<Pubkey> = members.iter().map(|member| member.pubkey()).collect();
+    let participants: Vec<&Pubkey> = participants.iter().collect();
+    bench
+        .run(
+            instruction::create_direct_channel(
+                &owner.pubkey(),
+                &direct_channel,
+                None,
+                &participants,
+                &SetHashInput { hash: [16; 64] },
+            )
+            .unwrap(),
+            &[&owner],
+        )
+        .await;
+    let guest = bench.dweller(Dweller::LEN).await;
+    bench
+        .run(
+            instruction::add_direct_channel_participant(
+                &owner.pubkey(),
+                &direct_channel,
+                &guest.pubkey(),
+                None,
+            )
+            .unwrap(),
+            &[&owner],
+        )
+        .await;
+    bench
+        .run(
+            instruction::remove_direct_channel_participant(
+                &owner.pubkey(),
+                &direct_channel,
+                &guest.pubkey(),
+                &direct_channel,
+                None,
+            )
+            .unwrap(),
+            &[&owner],
+        )
+        .await;
+    bench
+        .run(
+            instruction::leave_direct_channel(
+                &members[0].pubkey(),
+                &direct_channel,
+                &owner.pubkey(),
+                &direct_channel,
+                None,
+            )
+            .unwrap(),
+            &[&members[0]],
+        )
+        .await;
+
+    // delegates
+    let closing = bench.dweller(Dweller::LEN).await;
+    let mut delegates = Vec::with_capacity(size + 1);
+    for _ in 0..=size {
+        let delegate = Keypair::new();
+        bench.create_account(&delegate, DwellerDelegate::LEN).await;
+        bench
+            .run(
+                instruction::add_delegate(
+                    &closing.pubkey(),
+                    &delegate.pubkey(),
+                    &AddDelegateInput {
+                        expiry: i64::MAX,
+                        scope: DwellerDelegate::SCOPE_ALL,
+                    },
+                )
+                .unwrap(),
+                &[&closing, &delegate],
+            )
+            .await;
+        delegates.push(delegate.pubkey());
+    }
+    bench
+        .run(
+            instruction::remove_delegate(
+                &closing.pubkey(),
+                &closing.pubkey(),
+                &delegates[size],
+                &payer,
+            )
+            .unwrap(),
+            &[&closing],
+        )
+        .await;
+    let delegates: Vec<&Pubkey> = delegates[..size].iter().collect();
+    bench
+        .run(
+            instruction::close_dweller(&closing.pubkey(), &payer, &delegates).unwrap(),
+            &[&closing],
+        )
+        .await;
+
+    bench
+        .run(
+            instruction::remove_admin(
+                &owner.pubkey(),
+                &server.pubkey(),
+                &server_administrator,
+                &server_administrator,
+            )
+            .unwrap(),
+            &[&owner],
+        )
+        .await;
+
+    bench.report
+}
+
+#[tokio::test]
+async fn compute_units() {
+    let mut report = BTreeMap::new();
+    for size in SIZES {
+        report.extend(bench(*size).await);
+    }
+
+    for name in instruction_names() {
+        assert!(
+            report.keys().any(|(benched, _)| *benched == name),
+            "{} is not benchmarked",
+            name
+        );
+        assert!(budget(&name, 0).is_some(), "{} has no budget", name);
+    }
+
+    println!(
+        "{:<32}{:>8}{:>12}{:>12}",
+        "instruction", "size", "units", "budget"
+    );
+    for ((name, size), units) in &report {
+        let limit = budget(name, *size).expect("budget");
+        println!("{:<32}{:>8}{:>12}{:>12}", name, size, units, limit);
+    }
+
+    let exceeded: Vec<_> = report
+        .iter()
+        .filter(|((name, size), units)| Some(**units) > budget(name, *size))
+        .collect();
+    assert!(exceeded.is_empty(), "over budget: {:?}", exceeded);
+}
@@ -0,0 +1,1049 @@
+//! Property test of swap with last removal keeping indexed lists dense.
+//!
+//! Random sequences of joins and leaves, and of channel, group and pin changes, are applied
+//! both to in process accounts and to plain vectors,
+//! after each step counters and every entity of indexed lists must match.
+//!
+//! `cargo test --test swap`, `SWAP_SEED=<seed>` replays single failed case.
+
+use std::collections::HashMap;
+
+use arbitrary::{Arbitrary, Unstructured};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::AccountInfo,
+    entrypoint::{ProgramResult, SUCCESS},
+    instruction::Instruction,
+    program_stubs::{set_syscall_stubs, SyscallStubs},
+    pubkey::Pubkey,
+};
+
+use satellite_servers::{
+    id,
+    instruction::{self, AddChannelInput, CreateGroupInput, PinMessageInput},
+    processor::Processor,
+    program::{create_index_with_bump, find_base_bump},
+    state::*,
+};
+
+const SERVERS: usize = 3;
+const DWELLERS: usize = 5;
+const CASES: u64 = 64;
+const CASE_BYTES: usize = 1024;
+
+const CHANNELS: usize = 6;
+const GROUPS: usize = 3;
+const PINS: usize = 3;
+
+#[derive(Arbitrary, Debug)]
+enum Op {
+    Join {
+        server: u8,
+        dweller: u8,
+    },
+    /// `slot` chooses which of dweller servers to leave
+    Leave {
+        dweller: u8,
+        slot: u8,
+    },
+    /// same as [Op::Leave] but passes entity before last as last, must fail without changes
+    LeaveWrongLast {
+        dweller: u8,
+        slot: u8,
+    },
+}
+
+#[derive(Arbitrary, Debug)]
+enum LayoutOp {
+    AddChannel,
+    /// deleting channel in group or with pins must fail,
+    /// moved last channel takes its group channel and pins along
+    DeleteChannel {
+        slot: u8,
+    },
+    CreateGroup,
+    /// moved last group takes its group channels along
+    DeleteGroup {
+        slot: u8,
+    },
+    /// same as [LayoutOp::DeleteGroup] but passes first group channel in place of all,
+    /// must fail without changes
+    DeleteGroupRepeatedChannel {
+        slot: u8,
+    },
+    /// adding channel which is already in group must fail
+    AddChannelToGroup {
+        channel: u8,
+        group: u8,
+    },
+    RemoveChannelFromGroup {
+        group: u8,
+        slot: u8,
+    },
+    Pin {
+        channel: u8,
+    },
+    Unpin {
+        channel: u8,
+        slot: u8,
+    },
+}
+
+/// audit events read clock, which default off chain stubs do not provide
+struct ClockStubs;
+
+impl SyscallStubs for ClockStubs {
+    fn sol_get_clock_sysvar(&self, _var_addr: *mut u8) -> u64 {
+        SUCCESS
+    }
+
+    fn sol_log_data(&self, _fields: &[&[u8]]) {}
+}
+
+/// in process accounts, all owned by program and created zeroed on first use
+#[derive(Default)]
+struct Ledger {
+    accounts: HashMap<Pubkey, (u64, Vec<u8>)>,
+}
+
+impl Ledger {
+    fn insert<T: BorshSerialize>(&mut self, key: Pubkey, state: &T, len: u64) {
+        let mut data = vec![0; len as usize];
+        state.serialize(&mut &mut data[..]).unwrap();
+        self.accounts.insert(key, (1, data));
+    }
+
+    fn read<T: BorshDeserialize>(&self, key: &Pubkey) -> Option<T> {
+        self.accounts
+            .get(key)
+            .map(|(_, data)| T::deserialize(&mut &data[..]).unwrap())
+    }
+
+    /// runs instruction, on error changes are dropped as runtime does
+    fn process(&mut self, instruction: &Instruction, lens: &[u64]) -> ProgramResult {
+        for (meta, len) in instruction.accounts.iter().zip(lens) {
+            self.accounts
+                .entry(meta.pubkey)
+                .or_insert_with(|| (1, vec![0; *len as usize]));
+        }
+
+        let snapshot = self.accounts.clone();
+        let program_id = id();
+        let result = {
+            let unique: Vec<AccountInfo> = self
+                .accounts
+                .iter_mut()
+                .filter(|(key, _)| instruction.accounts.iter().any(|m| m.pubkey == **key))
+                .map(|(key, (lamports, data))| {
+                    let is_signer = instruction
+                        .accounts
+                        .iter()
+                        .any(|m| m.pubkey == *key && m.is_signer);
+                    AccountInfo::new(key, is_signer, true, lamports, data, &program_id, false, 0)
+                })
+                .collect();
+            let accounts: Vec<AccountInfo> = instruction
+                .accounts
+                .iter()
+                .map(|m| unique.iter().find(|a| *a.key == m.pubkey).unwrap().clone())
+                .collect();
+            Processor::process_instruction(&program_id, &accounts, &instruction.data)
+        };
+
+        if result.is_err() {
+            self.accounts = snapshot;
+        }
+        result
+    }
+}
+
+/// expected state, swap with last is [Vec::swap_remove]
+struct Model {
+    servers: Vec<Pubkey>,
+    dwellers: Vec<Pubkey>,
+    members: Vec<Vec<usize>>,
+    dweller_servers: Vec<Vec<usize>>,
+    bumps: HashMap<Pubkey, u8>,
+}
+
+impl Model {
+    fn address(&self, seed: &str, container: &Pubkey, index: usize) -> Pubkey {
+        create_index_with_bump(
+            &id(),
+            seed,
+            container,
+            Some(self.bumps[container]),
+            index as u64,
+        )
+        .unwrap()
+    }
+
+    fn member_status(&self, server: &Pubkey, dweller: usize) -> Pubkey {
+        self.address(ServerMemberStatus::SEED, server, dweller)
+    }
+}
+
+fn setup() -> (Ledger, Model) {
+    let servers: Vec<Pubkey> = (0..SERVERS).map(|_| Pubkey::new_unique()).collect();
+    let dwellers: Vec<Pubkey> = (0..DWELLERS).map(|_| Pubkey::new_unique()).collect();
+    let bumps = servers
+        .iter()
+        .chain(&dwellers)
+        .map(|key| (*key, find_base_bump(&id(), key)))
+        .collect();
+    let model = Model {
+        servers,
+        dwellers,
+        members: vec![Vec::new(); SERVERS],
+        dweller_servers: vec![Vec::new(); DWELLERS],
+        bumps,
+    };
+
+    let mut ledger = Ledger::default();
+    for dweller in &model.dwellers {
+        let state = Dweller {
+            version: StateVersion::V2,
+            servers: 0,
+            name: [0; 32],
+            photo_hash: [0; 64],
+            status: [0; 128],
+            extension: DwellerExtension {
+                bump: BaseBump::new(model.bumps[dweller]),
+                ..DwellerExtension::default()
+            },
+        };
+        ledger.insert(*dweller, &state, Dweller::LEN);
+    }
+
+    for server in &model.servers {
+        let state = Server {
+            version: StateVersion::V2,
+            owner: model.dwellers[0],
+            name: [0; 32],
+            photo_hash: [0; 64],
+            db_hash: [0; 64],
+            members: 0,
+            member_statuses: DWELLERS as u64,
+            administrators: 0,
+            channels: 0,
+            groups: 0,
+            extension: ServerExtension {
+                bump: BaseBump::new(model.bumps[server]),
+                ..ServerExtension::default()
+            },
+        };
+        ledger.insert(*server, &state, Server::LEN);
+
+        // invites are written directly, inviting emits audit which needs clock sysvar
+        for (index, dweller) in model.dwellers.iter().enumerate() {
+            let status = ServerMemberStatus {
+                version: StateVersion::V1,
+                container: *server,
+                index: index as u64,
+                dweller: *dweller,
+            };
+            ledger.insert(
+                model.member_status(server, index),
+                &status,
+                ServerMemberStatus::LEN,
+            );
+        }
+    }
+
+    (ledger, model)
+}
+
+fn join(ledger: &mut Ledger, model: &mut Model, server: usize, dweller: usize) {
+    let server_key = model.servers[server];
+    let dweller_key = model.dwellers[dweller];
+    let instruction = instruction::join_server(
+        &server_key,
+        &model.address(ServerMember::SEED, &server_key, model.members[server].len()),
+        &model.member_status(&server_key, dweller),
+        &dweller_key,
+        &model.address(
+            DwellerServer::SEED,
+            &dweller_key,
+            model.dweller_servers[dweller].len(),
+        ),
+        None,
+    )
+    .unwrap();
+    let lens = [0, ServerMember::LEN, 0, 0, DwellerServer::LEN];
+
+    ledger.process(&instruction, &lens).unwrap();
+    model.members[server].push(dweller);
+    model.dweller_servers[dweller].push(server);
+}
+
+fn leave(ledger: &mut Ledger, model: &mut Model, dweller: usize, slot: usize, wrong_last: bool) {
+    let dweller_servers = model.dweller_servers[dweller].len();
+    if dweller_servers == 0 {
+        return;
+    }
+    let dweller_server = slot % dweller_servers;
+    let server = model.dweller_servers[dweller][dweller_server];
+    let members = model.members[server].len();
+    let server_member = model.members[server]
+        .iter()
+        .position(|member| *member == dweller)
+        .unwrap();
+
+    let last = |len: usize| {
+        if wrong_last {
+            len.saturating_sub(2)
+        } else {
+            len - 1
+        }
+    };
+    if wrong_last && (members < 2 || dweller_servers < 2) {
+        return;
+    }
+
+    let server_key = model.servers[server];
+    let dweller_key = model.dwellers[dweller];
+    let instruction = instruction::leave_server(
+        &server_key,
+        &model.address(ServerMember::SEED, &server_key, server_member),
+        &model.address(ServerMember::SEED, &server_key, last(members)),
+        &dweller_key,
+        &model.address(DwellerServer::SEED, &dweller_key, dweller_server),
+        &model.address(DwellerServer::SEED, &dweller_key, last(dweller_servers)),
+        None,
+    )
+    .unwrap();
+
+    let result = ledger.process(&instruction, &[0; 6]);
+    if dweller == 0 {
+        // dweller 0 owns all servers
+        assert!(result.is_err(), "server owner left server");
+    } else if wrong_last {
+        assert!(result.is_err(), "wrong last account accepted");
+    } else {
+        result.unwrap();
+        model.dweller_servers[dweller].swap_remove(dweller_server);
+        model.members[server].swap_remove(server_member);
+    }
+}
+
+fn check(ledger: &Ledger, model: &Model) {
+    for (server, members) in model.members.iter().enumerate() {
+        let server_key = &model.servers[server];
+        let state: Server = ledger.read(server_key).unwrap();
+        assert_eq!(state.members, members.len() as u64);
+
+        for (index, dweller) in members.iter().enumerate() {
+            let member: ServerMember = ledger
+                .read(&model.address(ServerMember::SEED, server_key, index))
+                .unwrap();
+            assert_eq!(member.version, StateVersion::V1);
+            assert_eq!(member.container, *server_key);
+            assert_eq!(member.index, index as u64);
+            assert_eq!(member.dweller, model.dwellers[*dweller]);
+        }
+
+        let after_last: Option<ServerMember> =
+            ledger.read(&model.address(ServerMember::SEED, server_key, members.len()));
+        assert_eq!(after_last.unwrap_or_default(), ServerMember::default());
+    }
+
+    for (dweller, servers) in model.dweller_servers.iter().enumerate() {
+        let dweller_key = &model.dwellers[dweller];
+        let state: Dweller = ledger.read(dweller_key).unwrap();
+        assert_eq!(state.servers, servers.len() as u64);
+
+        for (index, server) in servers.iter().enumerate() {
+            let dweller_server: DwellerServer = ledger
+                .read(&model.address(DwellerServer::SEED, dweller_key, index))
+                .unwrap();
+            assert_eq!(dweller_server.version, StateVersion::V1);
+            assert_eq!(dweller_server.container, *dweller_key);
+            assert_eq!(dweller_server.index, index as u64);
+            assert_eq!(dweller_server.server, model.servers[*server]);
+        }
+
+        let after_last: Option<DwellerServer> =
+            ledger.read(&model.address(DwellerServer::SEED, dweller_key, servers.len()));
+        assert_eq!(after_last.unwrap_or_default(), DwellerServer::default());
+    }
+}
+
+struct LayoutChannel {
+    label: u32,
+    group: Option<u32>,
+    pins: Vec<u32>,
+}
+
+struct LayoutGroup {
+    label: u32,
+    /// labels of channels in order of group channel indices
+    channels: Vec<u32>,
+    /// labels of channels in order of positions
+    positions: Vec<u32>,
+}
+
+/// expected channels, groups and pins of single server, entities are labeled by names
+struct Layout {
+    server: Pubkey,
+    administrator: Pubkey,
+    server_administrator: Pubkey,
+    bump: u8,
+    next_label: u32,
+    channels: Vec<LayoutChannel>,
+    /// labels of channels in order of positions
+    channel_positions: Vec<u32>,
+    groups: Vec<LayoutGroup>,
+    /// labels of groups in order of positions
+    group_positions: Vec<u32>,
+}
+
+impl Layout {
+    fn label(&mut self) -> u32 {
+        self.next_label += 1;
+        self.next_label
+    }
+
+    fn channel(&self, index: usize) -> Pubkey {
+        create_index_with_bump(
+            &id(),
+            ServerChannel::SEED,
+            &self.server,
+            Some(self.bump),
+            index as u64,
+        )
+        .unwrap()
+    }
+
+    fn group(&self, index: usize) -> Pubkey {
+        create_index_with_bump(
+            &id(),
+            ServerGroup::SEED,
+            &self.server,
+            Some(self.bump),
+            index as u64,
+        )
+        .unwrap()
+    }
+
+    fn group_channel(&self, group: usize, index: usize) -> Pubkey {
+        let group = self.group(group);
+        let bump = find_base_bump(&id(), &group);
+        create_index_with_bump(&id(), GroupChannel::SEED, &group, Some(bump), index as u64).unwrap()
+    }
+
+    fn pin(&self, channel: usize, index: usize) -> Pubkey {
+        let channel = self.channel(channel);
+        let bump = find_base_bump(&id(), &channel);
+        create_index_with_bump(
+            &id(),
+            PinnedMessage::SEED,
+            &channel,
+            Some(bump),
+            index as u64,
+        )
+        .unwrap()
+    }
+
+    fn channel_index(&self, label: u32) -> usize {
+        self.channels
+            .iter()
+            .position(|channel| channel.label == label)
+            .unwrap()
+    }
+
+    fn group_index(&self, label: u32) -> usize {
+        self.groups
+            .iter()
+            .position(|group| group.label == label)
+            .unwrap()
+    }
+
+    /// group channel pointing to channel at `index`, if it is in group
+    fn group_channel_of(&self, index: usize) -> Option<Pubkey> {
+        let label = self.channels[index].label;
+        self.channels[index].group.map(|group| {
+            let group = self.group_index(group);
+            let child = self.groups[group]
+                .channels
+                .iter()
+                .position(|channel| *channel == label)
+                .unwrap();
+            self.group_channel(group, child)
+        })
+    }
+
+    /// group channels of group at `index`, each with channel it points to
+    fn group_channels(&self, index: usize) -> Vec<(Pubkey, Pubkey)> {
+        self.groups[index]
+            .channels
+            .iter()
+            .enumerate()
+            .map(|(child, channel)| {
+                (
+                    self.group_channel(index, child),
+                    self.channel(self.channel_index(*channel)),
+                )
+            })
+            .collect()
+    }
+
+    /// group channels of `last` group moved into group at `index`,
+    /// each with account it is moved to and channel it points to
+    fn moved_group_channels(&self, index: usize, last: usize) -> Vec<(Pubkey, Pubkey, Pubkey)> {
+        if index == last {
+            return Vec::new();
+        }
+        self.group_channels(last)
+            .into_iter()
+            .enumerate()
+            .map(|(child, (group_channel_last, channel))| {
+                (
+                    group_channel_last,
+                    self.group_channel(index, child),
+                    channel,
+                )
+            })
+            .collect()
+    }
+}
+
+fn name(label: u32) -> [u8; 32] {
+    let mut name = [0; 32];
+    name[..4].copy_from_slice(&label.to_le_bytes());
+    name
+}
+
+fn content_hash(label: u32) -> [u8; 64] {
+    let mut hash = [0; 64];
+    hash[..4].copy_from_slice(&label.to_le_bytes());
+    hash
+}
+
+/// positions of labeled entities listed in expected order are unique and ascending,
+/// removal leaves gaps
+fn assert_ordered(order: &[u32], positions: &[(u32, u64)]) {
+    let positions: Vec<u64> = order
+        .iter()
+        .map(|label| positions.iter().find(|(x, _)| x == label).unwrap().1)
+        .collect();
+    assert!(
+        positions.windows(2).all(|pair| pair[0] < pair[1]),
+        "positions {:?}",
+        positions
+    );
+}
+
+fn setup_layout() -> (Ledger, Layout) {
+    let server = Pubkey::new_unique();
+    let layout = Layout {
+        server,
+        administrator: Pubkey::new_unique(),
+        server_administrator: Pubkey::new_unique(),
+        bump: find_base_bump(&id(), &server),
+        next_label: 0,
+        channels: Vec::new(),
+        channel_positions: Vec::new(),
+        groups: Vec::new(),
+        group_positions: Vec::new(),
+    };
+
+    let mut ledger = Ledger::default();
+    ledger.insert(layout.administrator, &(), 0);
+    let state = Server {
+        version: StateVersion::V2,
+        owner: layout.administrator,
+        name: [0; 32],
+        photo_hash: [0; 64],
+        db_hash: [0; 64],
+        members: 0,
+        member_statuses: 0,
+        administrators: 1,
+        channels: 0,
+        groups: 0,
+        extension: ServerExtension {
+            bump: BaseBump::new(layout.bump),
+            ..ServerExtension::default()
+        },
+    };
+    ledger.insert(server, &state, Server::LEN);
+    let administrator = ServerAdministrator {
+        version: StateVersion::V1,
+        container: server,
+        index: 0,
+        dweller: layout.administrator,
+    };
+    ledger.insert(
+        layout.server_administrator,
+        &administrator,
+        ServerAdministrator::LEN,
+    );
+
+    (ledger, layout)
+}
+
+fn apply_layout(ledger: &mut Ledger, layout: &mut Layout, op: LayoutOp) {
+    let administrator = layout.administrator;
+    let server_administrator = layout.server_administrator;
+    let server = layout.server;
+    match op {
+        LayoutOp::AddChannel => {
+            if layout.channels.len() >= CHANNELS {
+                return;
+            }
+            let label = layout.label();
+            let instruction = instruction::add_channel(
+                &administrator,
+                &server_administrator,
+                &server,
+                &layout.channel(layout.channels.len()),
+                &AddChannelInput {
+                    type_id: 0,
+                    name: name(label),
+                },
+            )
+            .unwrap();
+            ledger
+                .process(&instruction, &[0, 0, 0, ServerChannel::LEN])
+                .unwrap();
+            layout.channels.push(LayoutChannel {
+                label,
+                group: None,
+                pins: Vec::new(),
+            });
+            layout.channel_positions.push(label);
+        }
+        LayoutOp::DeleteChannel { slot } => {
+            if layout.channels.is_empty() {
+                return;
+            }
+            let index = slot as usize % layout.channels.len();
+            let last = layout.channels.len() - 1;
+            let label = layout.channels[index].label;
+            let valid =
+                layout.channels[index].group.is_none() && layout.channels[index].pins.is_empty();
+            let (moved_group_channel, moved_pins) = if index != last {
+                let moved_pins: Vec<(Pubkey, Pubkey)> = (0..layout.channels[last].pins.len())
+                    .map(|pin| (layout.pin(last, pin), layout.pin(index, pin)))
+                    .collect();
+                (layout.group_channel_of(last), moved_pins)
+            } else {
+                (None, Vec::new())
+            };
+            let moved_pins: Vec<(&Pubkey, &Pubkey)> = moved_pins
+                .iter()
+                .map(|(pin_last, pin)| (pin_last, pin))
+                .collect();
+            let instruction = instruction::delete_channel(
+                &administrator,
+                &server_administrator,
+                &server,
+                &layout.channel(index),
+                &layout.channel(last),
+                moved_group_channel.as_ref(),
+                &moved_pins,
+            )
+            .unwrap();
+
+            // pinned messages are moved into accounts created for deleted channel
+            let lens: Vec<u64> = instruction
+                .accounts
+                .iter()
+                .map(|meta| {
+                    if moved_pins.iter().any(|(_, pin)| **pin == meta.pubkey) {
+                        PinnedMessage::LEN
+                    } else {
+                        0
+                    }
+                })
+                .collect();
+            let result = ledger.process(&instruction, &lens);
+            if valid {
+                result.unwrap();
+                layout.channel_positions.retain(|x| *x != label);
+                layout.channels.swap_remove(index);
+            } else {
+                assert!(result.is_err(), "channel with references deleted");
+            }
+        }
+        LayoutOp::CreateGroup => {
+            if layout.groups.len() >= GROUPS {
+                return;
+            }
+            let label = layout.label();
+            let instruction = instruction::create_group(
+                &administrator,
+                &server_administrator,
+                &server,
+                &layout.group(layout.groups.len()),
+                None,
+                &CreateGroupInput { name: name(label) },
+            )
+            .unwrap();
+            ledger
+                .process(&instruction, &[0, 0, 0, ServerGroup::LEN])
+                .unwrap();
+            layout.groups.push(LayoutGroup {
+                label,
+                channels: Vec::new(),
+                positions: Vec::new(),
+            });
+            layout.group_positions.push(label);
+        }
+        LayoutOp::DeleteGroup { slot } => {
+            if layout.groups.is_empty() {
+                return;
+            }
+            let index = slot as usize % layout.groups.len();
+            let last = layout.groups.len() - 1;
+            let label = layout.groups[index].label;
+            let group_channels = layout.group_channels(index);
+            let group_channels: Vec<(&Pubkey, &Pubkey)> = group_channels
+                .iter()
+                .map(|(group_channel, channel)| (group_channel, channel))
+                .collect();
+            let moved = layout.moved_group_channels(index, last);
+            let moved: Vec<(&Pubkey, &Pubkey, &Pubkey)> = moved
+                .iter()
+                .map(|(group_channel_last, group_channel, channel)| {
+                    (group_channel_last, group_channel, channel)
+                })
+                .collect();
+            let instruction = instruction::delete_group(
+                &administrator,
+                &server_administrator,
+                &server,
+                &layout.group(index),
+                &layout.group(last),
+                None,
+                &group_channels,
+                &[],
+                &moved,
+            )
+            .unwrap();
+
+            // group channels are moved into accounts of deleted group, created if it had fewer
+            let lens: Vec<u64> = instruction
+                .accounts
+                .iter()
+                .map(|meta| {
+                    if moved
+                        .iter()
+                        .any(|(_, group_channel, _)| **group_channel == meta.pubkey)
+                    {
+                        GroupChannel::LEN
+                    } else {
+                        0
+                    }
+                })
+                .collect();
+            ledger.process(&instruction, &lens).unwrap();
+            for channel in &mut layout.channels {
+                if channel.group == Some(label) {
+                    channel.group = None;
+                }
+            }
+            layout.group_positions.retain(|x| *x != label);
+            layout.groups.swap_remove(index);
+        }
+        LayoutOp::DeleteGroupRepeatedChannel { slot } => {
+            if layout.groups.is_empty() {
+                return;
+            }
+            let index = slot as usize % layout.groups.len();
+            let last = layout.groups.len() - 1;
+            let channels = layout.groups[index].channels.len();
+            if channels < 2 {
+                return;
+            }
+            let group_channel = layout.group_channel(index, 0);
+            let channel = layout.channel(layout.channel_index(layout.groups[index].channels[0]));
+            let group_channels = vec![(&group_channel, &channel); channels];
+            let moved = layout.moved_group_channels(index, last);
+            let moved: Vec<(&Pubkey, &Pubkey, &Pubkey)> = moved
+                .iter()
+                .map(|(group_channel_last, group_channel, channel)| {
+                    (group_channel_last, group_channel, channel)
+                })
+                .collect();
+            let instruction = instruction::delete_group(
+                &administrator,
+                &server_administrator,
+                &server,
+                &layout.group(index),
+                &layout.group(last),
+                None,
+                &group_channels,
+                &[],
+                &moved,
+            )
+            .unwrap();
+
+            assert!(
+                ledger.process(&instruction, &[]).is_err(),
+                "group channel erased twice"
+            );
+        }
+        LayoutOp::AddChannelToGroup { channel, group } => {
+            if layout.channels.is_empty() || layout.groups.is_empty() {
+                return;
+            }
+            let channel = channel as usize % layout.channels.len();
+            let group = group as usize % layout.groups.len();
+            let valid = layout.channels[channel].group.is_none();
+            let instruction = instruction::add_channel_to_group(
+                &server,
+                &administrator,
+                &server_administrator,
+                &layout.channel(channel),
+                &layout.group(group),
+                &layout.group_channel(group, layout.groups[group].channels.len()),
+            )
+            .unwrap();
+
+            let result = ledger.process(&instruction, &[0, 0, 0, 0, 0, GroupChannel::LEN]);
+            if valid {
+                result.unwrap();
+                let label = layout.channels[channel].label;
+                layout.channels[channel].group = Some(layout.groups[group].label);
+                layout.groups[group].channels.push(label);
+                layout.groups[group].positions.push(label);
+            } else {
+                assert!(result.is_err(), "channel added to second group");
+            }
+        }
+        LayoutOp::RemoveChannelFromGroup { group, slot } => {
+            if layout.groups.is_empty() {
+                return;
+            }
+            let group = group as usize % layout.groups.len();
+            let channels = layout.groups[group].channels.len();
+            if channels == 0 {
+                return;
+            }
+            let index = slot as usize % channels;
+            let label = layout.groups[group].channels[index];
+            let channel = layout.channel_index(label);
+            let instruction = instruction::remove_channel_from_group(
+                &server,
+                &administrator,
+                &server_administrator,
+                &layout.group(group),
+                &layout.channel(channel),
+                &layout.group_channel(group, index),
+                &layout.group_channel(group, channels - 1),
+            )
+            .unwrap();
+
+            ledger.process(&instruction, &[]).unwrap();
+            layout.channels[channel].group = None;
+            layout.groups[group].positions.retain(|x| *x != label);
+            layout.groups[group].channels.swap_remove(index);
+        }
+        LayoutOp::Pin { channel } => {
+            if layout.channels.is_empty() {
+                return;
+            }
+            let channel = channel as usize % layout.channels.len();
+            if layout.channels[channel].pins.len() >= PINS {
+                return;
+            }
+            let label = layout.label();
+            let instruction = instruction::pin_message(
+                &administrator,
+                &server_administrator,
+                &server,
+                &layout.channel(channel),
+                &layout.pin(channel, layout.channels[channel].pins.len()),
+                &PinMessageInput {
+                    content_hash: content_hash(label),
+                    author: administrator,
+                },
+            )
+            .unwrap();
+            ledger
+                .process(&instruction, &[0, 0, 0, 0, PinnedMessage::LEN])
+                .unwrap();
+            layout.channels[channel].pins.push(label);
+        }
+        LayoutOp::Unpin { channel, slot } => {
+            if layout.channels.is_empty() {
+                return;
+            }
+            let channel = channel as usize % layout.channels.len();
+            let pins = layout.channels[channel].pins.len();
+            if pins == 0 {
+                return;
+            }
+            let index = slot as usize % pins;
+            let instruction = instruction::unpin_message(
+                &administrator,
+                &server_administrator,
+                &server,
+                &layout.channel(channel),
+                &layout.pin(channel, index),
+                &layout.pin(channel, pins - 1),
+            )
+            .unwrap();
+            ledger.process(&instruction, &[]).unwrap();
+            layout.channels[channel].pins.swap_remove(index);
+        }
+    }
+}
+
+fn check_layout(ledger: &Ledger, layout: &Layout) {
+    let state: Server = ledger.read(&layout.server).unwrap();
+    assert_eq!(state.channels, layout.channels.len() as u64);
+    assert_eq!(state.groups, layout.groups.len() as u64);
+
+    let mut channel_positions = Vec::with_capacity(layout.channels.len());
+    for (index, channel) in layout.channels.iter().enumerate() {
+        let key = layout.channel(index);
+        let state: ServerChannel = ledger.read(&key).unwrap();
+        assert_eq!(state.version, StateVersion::V2);
+        assert_eq!(state.container, layout.server);
+        assert_eq!(state.index, index as u64);
+        assert_eq!(state.name, name(channel.label));
+        channel_positions.push((channel.label, state.extension.position));
+        let group = channel
+            .group
+            .map(|group| layout.group(layout.group_index(group)))
+            .unwrap_or_default();
+        assert_eq!(state.extension.group, group);
+        assert_eq!(state.extension.pins, channel.pins.len() as u64);
+        assert_eq!(state.extension.container_bump.get(), Some(layout.bump));
+        assert_eq!(
+            state.extension.bump.get(),
+            Some(find_base_bump(&id(), &key))
+        );
+
+        for (pin, label) in channel.pins.iter().enumerate() {
+            let state: PinnedMessage = ledger.read(&layout.pin(index, pin)).unwrap();
+            assert_eq!(state.version, StateVersion::V1);
+            assert_eq!(state.container, key);
+            assert_eq!(state.index, pin as u64);
+            assert_eq!(state.content_hash, content_hash(*label));
+        }
+        let after_last: Option<PinnedMessage> = ledger.read(&layout.pin(index, channel.pins.len()));
+        assert_eq!(after_last.unwrap_or_default(), PinnedMessage::default());
+    }
+    let after_last: Option<ServerChannel> = ledger.read(&layout.channel(layout.channels.len()));
+    assert_eq!(after_last.unwrap_or_default(), ServerChannel::default());
+    assert_ordered(&layout.channel_positions, &channel_positions);
+
+    let mut group_positions = Vec::with_capacity(layout.groups.len());
+    for (index, group) in layout.groups.iter().enumerate() {
+        let key = layout.group(index);
+        let state: ServerGroup = ledger.read(&key).unwrap();
+        assert_eq!(state.version, StateVersion::V2);
+        assert_eq!(state.container, layout.server);
+        assert_eq!(state.index, index as u64);
+        assert_eq!(state.name, name(group.label));
+        group_positions.push((group.label, state.extension.position));
+        assert_eq!(state.channels, group.channels.len() as u64);
+        assert_eq!(state.extension.container_bump.get(), Some(layout.bump));
+        assert_eq!(
+            state.extension.bump.get(),
+            Some(find_base_bump(&id(), &key))
+        );
+
+        let mut positions = Vec::with_capacity(group.channels.len());
+        for (child, label) in group.channels.iter().enumerate() {
+            let state: GroupChannel = ledger.read(&layout.group_channel(index, child)).unwrap();
+            assert_eq!(state.version, StateVersion::V2);
+            assert_eq!(state.container, key);
+            assert_eq!(state.index, child as u64);
+            assert_eq!(state.channel, layout.channel(layout.channel_index(*label)));
+            positions.push((*label, state.extension.position));
+        }
+        let after_last: Option<GroupChannel> =
+            ledger.read(&layout.group_channel(index, group.channels.len()));
+        assert_eq!(after_last.unwrap_or_default(), GroupChannel::default());
+        assert_ordered(&group.positions, &positions);
+    }
+    let after_last: Option<ServerGroup> = ledger.read(&layout.group(layout.groups.len()));
+    assert_eq!(after_last.unwrap_or_default(), ServerGroup::default());
+    assert_ordered(&layout.group_positions, &group_positions);
+}
+
+/// deterministic bytes, so failures are reproducible without fuzzer corpus
+fn case_bytes(seed: u64) -> Vec<u8> {
+    let mut state = seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1;
+    (0..CASE_BYTES)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state as u8
+        })
+        .collect()
+}
+
+fn run(seed: u64) {
+    let bytes = case_bytes(seed);
+    let mut unstructured = Unstructured::new(&bytes);
+
+    let (mut ledger, mut model) = setup();
+    while !unstructured.is_empty() {
+        match unstructured.arbitrary().unwrap() {
+            Op::Join { server, dweller } => join(
+                &mut ledger,
+                &mut model,
+                server as usize % SERVERS,
+                dweller as usize % DWELLERS,
+            ),
+            Op::Leave { dweller, slot } => leave(
+                &mut ledger,
+                &mut model,
+                dweller as usize % DWELLERS,
+                slot as usize,
+                false,
+            ),
+            Op::LeaveWrongLast { dweller, slot } => leave(
+                &mut ledger,
+                &mut model,
+                dweller as usize % DWELLERS,
+                slot as usize,
+                true,
+            ),
+        }
+        check(&ledger, &model);
+    }
+}
+
+fn run_layout(seed: u64) {
+    let bytes = case_bytes(seed);
+    let mut unstructured = Unstructured::new(&bytes);
+
+    let (mut ledger, mut layout) = setup_layout();
+    while !unstructured.is_empty() {
+        apply_layout(&mut ledger, &mut layout, unstructured.arbitrary().unwrap());
+        check_layout(&ledger, &layout);
+    }
+}
+
+#[test]
+fn join_leave_keeps_indices_dense() {
+    let seeds = match std::env::var("SWAP_SEED") {
+        Ok(seed) => vec![seed.parse().unwrap()],
+        Err(_) => (0..CASES).collect(),
+    };
+
+    for seed in seeds {
+        let result = std::panic::catch_unwind(|| run(seed));
+        assert!(result.is_ok(), "failed with SWAP_SEED={}", seed);
+    }
+}
+
+#[test]
+fn channels_groups_pins_keep_indices_dense() {
+    set_syscall_stubs(Box::new(ClockStubs));
+    let seeds = match std::env::var("SWAP_SEED") {
+        Ok(seed) => vec![seed.parse().unwrap()],
+        Err(_) => (0..CASES).collect(),
+    };
+
+    for seed in seeds {
+        let result = std::panic::catch_unwind(|| run_layout(seed));
+        assert!(result.is_ok(), "failed with SWAP_SEED={}", seed);
+    }
+}
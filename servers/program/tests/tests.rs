@@ -1,7 +1,7 @@
 #![cfg(feature = "test-bpf")]
 
-use borsh::BorshDeserialize;
-use solana_program::{pubkey::Pubkey, system_instruction};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{program_pack::Pack, pubkey::Pubkey, system_instruction};
 use solana_program_test::*;
 use solana_sdk::{
     account::Account,
@@ -11,19 +11,25 @@ use solana_sdk::{
 };
 
 use satellite_servers::{
+    audit::{AuditAction, AuditEvent},
     id,
     instruction::{
-        self, AddChannelInput, CreateGroupInput, InitializeDwellerInput, InitializeServerInput,
+        self, AddChannelInput, AddDelegateInput, CreateGroupInput, InitializeDwellerInput,
+        InitializeServerInput, PinMessageInput, ReorderChannelsInput, ResizeAccountInput,
+        SetHashInput, SetNameInput, SetServerDescriptionInput, SetServerGateInput,
+        SetServerPublicInput, SetServerTagsInput, UpdateChannelInput,
     },
     processor,
     state::*,
 };
 
 use sdk::{
-    add_channel_to_group_transaction, add_channel_transaction, add_invite_transaction,
-    create_group_transaction, delete_channel_transaction, delete_group_transaction,
-    join_server_transaction, leave_server_transaction, remove_admin_transaction,
-    remove_channel_from_group_transaction, revoke_invite_server_transaction,
+    add_channel_to_group_transaction, add_channel_transaction, add_delegate_transaction,
+    add_invite_transaction, create_group_transaction, delete_channel_transaction,
+    delete_group_transaction, join_server_transaction, leave_server_transaction,
+    pin_message_transaction, remove_admin_transaction, remove_channel_from_group_transaction,
+    reorder_channels_transaction, resize_account_transaction, revoke_invite_server_transaction,
+    set_server_db_transaction, set_server_name_transaction, unpin_message_transaction,
 };
 
 pub fn program_test() -> ProgramTest {
@@ -105,6 +111,103 @@ async fn dweller_flow() {
     );
 }
 
+#[tokio::test]
+async fn server_name_and_db_flow() {
+    let mut blockchain = program_test().start_with_context().await;
+    let rent = blockchain.banks_client.get_rent().await.unwrap();
+
+    let owner = Keypair::new();
+    test_initialize_dweller(
+        &blockchain.payer,
+        &owner,
+        rent,
+        blockchain.last_blockhash,
+        &mut blockchain.banks_client,
+    )
+    .await;
+
+    let server = Keypair::new();
+    let owner_server = create_derived_account_index(
+        &mut blockchain,
+        &owner.pubkey(),
+        DwellerServer::SEED,
+        0,
+        instruction::AddressTypeInput::DwellerServer(0),
+    )
+    .await;
+    let server_member = create_derived_account_index(
+        &mut blockchain,
+        &server.pubkey(),
+        ServerMember::SEED,
+        0,
+        instruction::AddressTypeInput::ServerMember(0),
+    )
+    .await;
+    test_initialize_server(
+        &blockchain.payer,
+        &owner,
+        &server,
+        &owner_server,
+        &server_member,
+        rent,
+        blockchain.last_blockhash,
+        &mut blockchain.banks_client,
+    )
+    .await;
+
+    let server_administrator = create_derived_account_index(
+        &mut blockchain,
+        &server.pubkey(),
+        ServerAdministrator::SEED,
+        0,
+        instruction::AddressTypeInput::ServerAdministrator(0),
+    )
+    .await;
+    test_add_administrator(
+        &blockchain.payer,
+        &owner,
+        &owner.pubkey(),
+        &server.pubkey(),
+        &server_administrator,
+        blockchain.last_blockhash,
+        &mut blockchain.banks_client,
+    )
+    .await;
+
+    // only administrator dweller signs, server keypair is not needed
+    let trx = set_server_name_transaction(
+        &blockchain.payer,
+        &server.pubkey(),
+        &owner,
+        &server_administrator,
+        &SetNameInput { name: [21; 32] },
+        blockchain.last_blockhash,
+    );
+    blockchain
+        .banks_client
+        .process_transaction(trx)
+        .await
+        .unwrap();
+
+    let trx = set_server_db_transaction(
+        &blockchain.payer,
+        &server.pubkey(),
+        &owner,
+        &server_administrator,
+        &SetHashInput { hash: [22; 64] },
+        blockchain.last_blockhash,
+    );
+    blockchain
+        .banks_client
+        .process_transaction(trx)
+        .await
+        .unwrap();
+
+    let account_state: Server = get_account_data(&mut blockchain, &server.pubkey()).await;
+    assert_eq!(account_state.name, [21; 32]);
+    assert_eq!(account_state.db_hash, [22; 64]);
+}
+
 #[tokio::test]
 async fn positive_add_remove_flow() {
     let mut blockchain = program_test().start_with_context().await;
@@ -341,6 +444,21 @@ async fn positive_add_remove_flow() {
         assert_eq!(account_state.container, Pubkey::default(),);
     }
 
+    let mut group_channels_nested = Vec::new();
+    for index in 0u64..1 {
+        let address_type = instruction::AddressTypeInput::GroupChannel(index);
+        let seed = GroupChannel::SEED;
+        let address_to_create = create_derived_account_index(
+            &mut blockchain,
+            &server_groups[1],
+            seed,
+            index,
+            address_type,
+        )
+        .await;
+        group_channels_nested.push(address_to_create);
+    }
+
     let mut server_channels = Vec::new();
     for index in 0u64..3 {
         let address_type = instruction::AddressTypeInput::ServerChannel(index);
@@ -365,6 +483,7 @@ async fn positive_add_remove_flow() {
         &server_administrators[0],
         &server.pubkey(),
         &server_groups[0],
+        None,
         &CreateGroupInput { name: [66; 32] },
         blockchain.last_blockhash,
     );
@@ -374,6 +493,27 @@ async fn positive_add_remove_flow() {
         .await
         .unwrap();
 
+    let trx = create_group_transaction(
+        &blockchain.payer,
+        &dweller_admin_1,
+        &server_administrators[0],
+        &server.pubkey(),
+        &server_groups[1],
+        Some(&server_groups[0]),
+        &CreateGroupInput { name: [67; 32] },
+        blockchain.last_blockhash,
+    );
+    blockchain
+        .banks_client
+        .process_transaction(trx)
+        .await
+        .unwrap();
+
+    let account_state: ServerGroup = get_account_data(&mut blockchain, &server_groups[1]).await;
+    assert_eq!(account_state.extension.parent, server_groups[0]);
+    let account_state: ServerGroup = get_account_data(&mut blockchain, &server_groups[0]).await;
+    assert_eq!(account_state.extension.groups, 1);
+
     let trx = add_channel_transaction(
         &blockchain.payer,
         &dweller_admin_1,
@@ -386,20 +526,1675 @@ async fn positive_add_remove_flow() {
         },
         blockchain.last_blockhash,
     );
-    blockchain
+    let result = blockchain
+        .banks_client
+        .process_transaction_with_metadata(trx)
+        .await
+        .unwrap();
+    result.result.unwrap();
+
+    let events = AuditEvent::decode_all(&result.metadata.unwrap().log_messages);
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].server, server.pubkey());
+    assert_eq!(events[0].actor, dweller_admin_1.pubkey());
+    assert_eq!(events[0].action, AuditAction::AddChannel);
+    assert_eq!(events[0].target, server_channels[0]);
+
+    let trx = add_channel_transaction(
+        &blockchain.payer,
+        &dweller_admin_1,
+        &server_administrators[0],
+        &server.pubkey(),
+        &server_channels[1],
+        &AddChannelInput {
+            name: [67; 32],
+            type_id: 17,
+        },
+        blockchain.last_blockhash,
+    );
+    blockchain
+        .banks_client
+        .process_transaction(trx)
+        .await
+        .unwrap();
+
+    let account_state: ServerChannel = get_account_data(&mut blockchain, &server_channels[1]).await;
+    assert_eq!(account_state.extension.position, 1);
+
+    let trx = reorder_channels_transaction(
+        &blockchain.payer,
+        &dweller_admin_1,
+        &server_administrators[0],
+        &server.pubkey(),
+        None,
+        &[&server_channels[1], &server_channels[0]],
+        &ReorderChannelsInput::ServerChannels,
+        blockchain.last_blockhash,
+    );
+    blockchain
+        .banks_client
+        .process_transaction(trx)
+        .await
+        .unwrap();
+
+    let account_state: ServerChannel = get_account_data(&mut blockchain, &server_channels[0]).await;
+    assert_eq!(account_state.extension.position, 1);
+    let account_state: ServerChannel = get_account_data(&mut blockchain, &server_channels[1]).await;
+    assert_eq!(account_state.extension.position, 0);
+
+    // pins
+
+    let pinned_message = create_derived_account_index(
+        &mut blockchain,
+        &server_channels[0],
+        PinnedMessage::SEED,
+        0,
+        instruction::AddressTypeInput::PinnedMessage(0),
+    )
+    .await;
+
+    let trx = pin_message_transaction(
+        &blockchain.payer,
+        &dweller_admin_1,
+        &server_administrators[0],
+        &server.pubkey(),
+        &server_channels[0],
+        &pinned_message,
+        &PinMessageInput {
+            content_hash: [7; 64],
+            author: dweller_1.pubkey(),
+        },
+        blockchain.last_blockhash,
+    );
+    blockchain
+        .banks_client
+        .process_transaction(trx)
+        .await
+        .unwrap();
+
+    let account_state: PinnedMessage = get_account_data(&mut blockchain, &pinned_message).await;
+    assert_eq!(account_state.content_hash, [7; 64]);
+    assert_eq!(account_state.author, dweller_1.pubkey());
+    assert_eq!(account_state.pinned_by, dweller_admin_1.pubkey());
+    let account_state: ServerChannel = get_account_data(&mut blockchain, &server_channels[0]).await;
+    assert_eq!(account_state.extension.pins, 1);
+
+    let trx = delete_channel_transaction(
+        &blockchain.payer,
+        &dweller_admin_1,
+        &server_administrators[0],
+        &server.pubkey(),
+        &server_channels[0],
+        &server_channels[1],
+        blockchain.last_blockhash,
+    );
+    assert!(blockchain
+        .banks_client
+        .process_transaction(trx)
+        .await
+        .is_err());
+
+    let trx = unpin_message_transaction(
+        &blockchain.payer,
+        &dweller_admin_1,
+        &server_administrators[0],
+        &server.pubkey(),
+        &server_channels[0],
+        &pinned_message,
+        &pinned_message,
+        blockchain.last_blockhash,
+    );
+    blockchain
+        .banks_client
+        .process_transaction(trx)
+        .await
+        .unwrap();
+
+    let account_state: ServerChannel = get_account_data(&mut blockchain, &server_channels[0]).await;
+    assert_eq!(account_state.extension.pins, 0);
+
+    let mut topic = [0; 128];
+    topic[..7].copy_from_slice(b"general");
+    let update_channel = |slow_mode| {
+        let mut trx = Transaction::new_with_payer(
+            &[instruction::update_channel(
+                &dweller_admin_1.pubkey(),
+                &server_administrators[0],
+                &server.pubkey(),
+                &server_channels[0],
+                &UpdateChannelInput {
+                    topic,
+                    slow_mode,
+                    nsfw: true,
+                },
+            )
+            .unwrap()],
+            Some(&blockchain.payer.pubkey()),
+        );
+        trx.sign(
+            &[&blockchain.payer, &dweller_admin_1],
+            blockchain.last_blockhash,
+        );
+        trx
+    };
+    let too_long = update_channel(ServerChannel::MAX_SLOW_MODE + 1);
+    let trx = update_channel(30);
+    assert!(blockchain
+        .banks_client
+        .process_transaction(too_long)
+        .await
+        .is_err());
+    blockchain
+        .banks_client
+        .process_transaction(trx)
+        .await
+        .unwrap();
+
+    let account_state: ServerChannel = get_account_data(&mut blockchain, &server_channels[0]).await;
+    let settings = account_state.settings();
+    assert_eq!(settings.topic, "general");
+    assert_eq!(settings.slow_mode, Some(30));
+    assert!(settings.nsfw);
+
+    let mut trx = Transaction::new_with_payer(
+        &[
+            instruction::set_server_description(
+                &dweller_admin_1.pubkey(),
+                &server_administrators[0],
+                &server.pubkey(),
+                &SetServerDescriptionInput {
+                    description: [3; 256],
+                },
+            )
+            .unwrap(),
+            instruction::set_server_banner(
+                &dweller_admin_1.pubkey(),
+                &server_administrators[0],
+                &server.pubkey(),
+                &SetHashInput { hash: [4; 64] },
+            )
+            .unwrap(),
+            instruction::set_server_tags(
+                &dweller_admin_1.pubkey(),
+                &server_administrators[0],
+                &server.pubkey(),
+                &SetServerTagsInput {
+                    tags: [[5; 16], [6; 16], [0; 16], [0; 16]],
+                },
+            )
+            .unwrap(),
+            instruction::set_server_public(
+                &dweller_admin_1.pubkey(),
+                &server_administrators[0],
+                &server.pubkey(),
+                &SetServerPublicInput { public: true },
+            )
+            .unwrap(),
+        ],
+        Some(&blockchain.payer.pubkey()),
+    );
+    trx.sign(
+        &[&blockchain.payer, &dweller_admin_1],
+        blockchain.last_blockhash,
+    );
+    blockchain
+        .banks_client
+        .process_transaction(trx)
+        .await
+        .unwrap();
+
+    let account_state: Server = get_account_data(&mut blockchain, &server.pubkey()).await;
+    assert_eq!(account_state.extension.description, [3; 256]);
+    assert_eq!(account_state.extension.banner_hash, [4; 64]);
+    assert_eq!(account_state.extension.tags[1], [6; 16]);
+    assert!(account_state.extension.public);
+
+    let server_directory = create_derived_account_index(
+        &mut blockchain,
+        &id(),
+        ServerDirectory::SEED,
+        0,
+        instruction::AddressTypeInput::ServerDirectory,
+    )
+    .await;
+    assert_eq!(server_directory, ServerDirectory::address(&id()).unwrap());
+    let server_listing = create_derived_account_index(
+        &mut blockchain,
+        &server_directory,
+        ServerListing::SEED,
+        0,
+        instruction::AddressTypeInput::ServerListing(0),
+    )
+    .await;
+
+    let mut trx = Transaction::new_with_payer(
+        &[instruction::list_server(
+            &dweller_owner.pubkey(),
+            &server.pubkey(),
+            &server_directory,
+            &server_listing,
+        )
+        .unwrap()],
+        Some(&blockchain.payer.pubkey()),
+    );
+    trx.sign(
+        &[&blockchain.payer, &dweller_owner],
+        blockchain.last_blockhash,
+    );
+    blockchain
+        .banks_client
+        .process_transaction(trx)
+        .await
+        .unwrap();
+
+    let directory_state: ServerDirectory =
+        get_account_data(&mut blockchain, &server_directory).await;
+    assert_eq!(
+        directory_state.page(&id(), 0, 10).unwrap(),
+        vec![server_listing]
+    );
+    let account_state: ServerListing = get_account_data(&mut blockchain, &server_listing).await;
+    assert_eq!(account_state.server, server.pubkey());
+
+    let mut trx = Transaction::new_with_payer(
+        &[instruction::set_server_public(
+            &dweller_admin_1.pubkey(),
+            &server_administrators[0],
+            &server.pubkey(),
+            &SetServerPublicInput { public: false },
+        )
+        .unwrap()],
+        Some(&blockchain.payer.pubkey()),
+    );
+    trx.sign(
+        &[&blockchain.payer, &dweller_admin_1],
+        blockchain.last_blockhash,
+    );
+    assert!(blockchain
+        .banks_client
+        .process_transaction(trx)
+        .await
+        .is_err());
+
+    let mut trx = Transaction::new_with_payer(
+        &[instruction::unlist_server(
+            &dweller_owner.pubkey(),
+            &server.pubkey(),
+            &server_directory,
+            &server_listing,
+            &server_listing,
+        )
+        .unwrap()],
+        Some(&blockchain.payer.pubkey()),
+    );
+    trx.sign(
+        &[&blockchain.payer, &dweller_owner],
+        blockchain.last_blockhash,
+    );
+    blockchain
+        .banks_client
+        .process_transaction(trx)
+        .await
+        .unwrap();
+
+    let directory_state: ServerDirectory =
+        get_account_data(&mut blockchain, &server_directory).await;
+    assert_eq!(directory_state.listings, 0);
+    let account_state: Server = get_account_data(&mut blockchain, &server.pubkey()).await;
+    assert!(!account_state.extension.listed);
+
+    let trx = add_channel_to_group_transaction(
+        &blockchain.payer,
+        &server.pubkey(),
+        &dweller_admin_1,
+        &server_administrators[0],
+        &server_channels[0],
+        &server_groups[0],
+        &group_channels[0],
+        blockchain.last_blockhash,
+    );
+    blockchain
+        .banks_client
+        .process_transaction(trx)
+        .await
+        .unwrap();
+
+    let account_state: ServerGroup = get_account_data(&mut blockchain, &server_groups[0]).await;
+    assert_eq!(account_state.channels, 1);
+    let account_state: ServerChannel = get_account_data(&mut blockchain, &server_channels[0]).await;
+    assert_eq!(account_state.extension.group, server_groups[0]);
+
+    let trx = add_channel_to_group_transaction(
+        &blockchain.payer,
+        &server.pubkey(),
+        &dweller_admin_1,
+        &server_administrators[0],
+        &server_channels[0],
+        &server_groups[1],
+        &group_channels_nested[0],
+        blockchain.last_blockhash,
+    );
+    assert!(blockchain
+        .banks_client
+        .process_transaction(trx)
+        .await
+        .is_err());
+
+    // removing/deleting
+
+    // groups/channels
+
+    let trx = remove_channel_from_group_transaction(
+        &blockchain.payer,
+        &server.pubkey(),
+        &dweller_admin_1,
+        &server_administrators[0],
+        &server_groups[0],
+        &server_channels[0],
+        &group_channels[0],
+        &group_channels[0],
+        blockchain.last_blockhash,
+    );
+    blockchain
+        .banks_client
+        .process_transaction(trx)
+        .await
+        .unwrap();
+
+    let account_state: ServerChannel = get_account_data(&mut blockchain, &server_channels[0]).await;
+    assert_eq!(account_state.extension.group, Pubkey::default());
+
+    // last group with channel is moved into deleted slot along with its group channel
+    let trx = create_group_transaction(
+        &blockchain.payer,
+        &dweller_admin_1,
+        &server_administrators[0],
+        &server.pubkey(),
+        &server_groups[2],
+        None,
+        &CreateGroupInput { name: [68; 32] },
+        blockchain.last_blockhash,
+    );
+    blockchain
+        .banks_client
+        .process_transaction(trx)
+        .await
+        .unwrap();
+
+    let group_channel_last = create_derived_account_index(
+        &mut blockchain,
+        &server_groups[2],
+        GroupChannel::SEED,
+        0,
+        instruction::AddressTypeInput::GroupChannel(0),
+    )
+    .await;
+    let trx = add_channel_to_group_transaction(
+        &blockchain.payer,
+        &server.pubkey(),
+        &dweller_admin_1,
+        &server_administrators[0],
+        &server_channels[0],
+        &server_groups[2],
+        &group_channel_last,
+        blockchain.last_blockhash,
+    );
+    blockchain
+        .banks_client
+        .process_transaction(trx)
+        .await
+        .unwrap();
+
+    let trx = delete_group_transaction(
+        &blockchain.payer,
+        &dweller_admin_1,
+        &server_administrators[0],
+        &server.pubkey(),
+        &server_groups[1],
+        &server_groups[2],
+        Some(&server_groups[0]),
+        &[],
+        &[],
+        blockchain.last_blockhash,
+    );
+    assert!(blockchain
+        .banks_client
+        .process_transaction(trx)
+        .await
+        .is_err());
+
+    let blockhash = blockchain
+        .banks_client
+        .get_new_latest_blockhash(&blockchain.last_blockhash)
+        .await
+        .unwrap();
+    let trx = delete_group_transaction(
+        &blockchain.payer,
+        &dweller_admin_1,
+        &server_administrators[0],
+        &server.pubkey(),
+        &server_groups[1],
+        &server_groups[2],
+        Some(&server_groups[0]),
+        &[],
+        &[(
+            &group_channel_last,
+            &group_channels_nested[0],
+            &server_channels[0],
+        )],
+        blockhash,
+    );
+    blockchain
+        .banks_client
+        .process_transaction(trx)
+        .await
+        .unwrap();
+
+    let account_state: ServerGroup = get_account_data(&mut blockchain, &server_groups[1]).await;
+    assert_eq!(account_state.name, [68; 32]);
+    assert_eq!(account_state.index, 1);
+    assert_eq!(account_state.channels, 1);
+    // gap is left in positions, moved group keeps its own
+    assert_eq!(account_state.extension.position, 2);
+    let account_state: Server = get_account_data(&mut blockchain, &server.pubkey()).await;
+    assert_eq!(account_state.extension.next_group_position, 3);
+    let account_state: ServerGroup = get_account_data(&mut blockchain, &server_groups[0]).await;
+    assert_eq!(account_state.extension.groups, 0);
+    let account_state: GroupChannel =
+        get_account_data(&mut blockchain, &group_channels_nested[0]).await;
+    assert_eq!(account_state.container, server_groups[1]);
+    assert_eq!(account_state.channel, server_channels[0]);
+    let account_state: GroupChannel = get_account_data(&mut blockchain, &group_channel_last).await;
+    assert_eq!(account_state, GroupChannel::default());
+    let account_state: ServerChannel = get_account_data(&mut blockchain, &server_channels[0]).await;
+    assert_eq!(account_state.extension.group, server_groups[1]);
+
+    let trx = delete_group_transaction(
+        &blockchain.payer,
+        &dweller_admin_1,
+        &server_administrators[0],
+        &server.pubkey(),
+        &server_groups[1],
+        &server_groups[1],
+        None,
+        &[(&group_channels_nested[0], &server_channels[0])],
+        &[],
+        blockchain.last_blockhash,
+    );
+
+    blockchain
+        .banks_client
+        .process_transaction(trx)
+        .await
+        .unwrap();
+
+    let account_state: ServerGroup = get_account_data(&mut blockchain, &server_groups[0]).await;
+    assert_eq!(account_state.extension.groups, 0);
+
+    let trx = delete_group_transaction(
+        &blockchain.payer,
+        &dweller_admin_1,
+        &server_administrators[0],
+        &server.pubkey(),
+        &server_groups[0],
+        &server_groups[0],
+        None,
+        &[],
+        &[],
+        blockchain.last_blockhash,
+    );
+
+    blockchain
+        .banks_client
+        .process_transaction(trx)
+        .await
+        .unwrap();
+
+    let account_state: Server = get_account_data(&mut blockchain, &server.pubkey()).await;
+    assert_eq!(account_state.groups, 0);
+
+    let trx = delete_channel_transaction(
+        &blockchain.payer,
+        &dweller_admin_1,
+        &server_administrators[0],
+        &server.pubkey(),
+        &server_channels[0],
+        &server_channels[1],
+        blockchain.last_blockhash,
+    );
+
+    blockchain
+        .banks_client
+        .process_transaction(trx)
+        .await
+        .unwrap();
+
+    let account_state: ServerChannel = get_account_data(&mut blockchain, &server_channels[0]).await;
+    assert_eq!(account_state.index, 0);
+    assert_eq!(account_state.extension.position, 0);
+    assert_eq!(account_state.name, [67; 32]);
+
+    let trx = delete_channel_transaction(
+        &blockchain.payer,
+        &dweller_admin_1,
+        &server_administrators[0],
+        &server.pubkey(),
+        &server_channels[0],
+        &server_channels[0],
+        blockchain.last_blockhash,
+    );
+
+    blockchain
+        .banks_client
+        .process_transaction(trx)
+        .await
+        .unwrap();
+
+    let account_state: Server = get_account_data(&mut blockchain, &server.pubkey()).await;
+    assert_eq!(account_state.channels, 0);
+
+    // members/admin
+
+    let trx = leave_server_transaction(
+        &blockchain.payer,
+        &server.pubkey(),
+        &server_members[0],
+        &server_members[1],
+        &dweller_owner,
+        &dweller_servers[0],
+        &dweller_servers[0],
+        blockchain.last_blockhash,
+    );
+    assert!(blockchain
+        .banks_client
+        .process_transaction(trx)
+        .await
+        .is_err());
+
+    let trx = leave_server_transaction(
+        &blockchain.payer,
+        &server.pubkey(),
+        &server_members[1],
+        &server_members[1],
+        &dweller_1,
+        &dweller_servers[4],
+        &dweller_servers[4],
+        blockchain.last_blockhash,
+    );
+
+    blockchain
+        .banks_client
+        .process_transaction(trx)
+        .await
+        .unwrap();
+
+    let account_state: Server = get_account_data(&mut blockchain, &server.pubkey()).await;
+    assert_eq!(account_state.members, 1);
+
+    let trx = revoke_invite_server_transaction(
+        &blockchain.payer,
+        &server.pubkey(),
+        &dweller_admin_1,
+        &server_administrators[0],
+        &server_member_statuses[0],
+        &server_member_statuses[0],
+        blockchain.last_blockhash,
+    );
+
+    blockchain
+        .banks_client
+        .process_transaction(trx)
+        .await
+        .unwrap();
+
+    let account_state: Server = get_account_data(&mut blockchain, &server.pubkey()).await;
+    assert_eq!(account_state.member_statuses, 0);
+
+    let trx = remove_admin_transaction(
+        &blockchain.payer,
+        &dweller_owner,
+        &server.pubkey(),
+        &server_administrators[0],
+        &server_administrators[0],
+        blockchain.last_blockhash,
+    );
+
+    blockchain
+        .banks_client
+        .process_transaction(trx)
+        .await
+        .unwrap();
+
+    let account_state: Server = get_account_data(&mut blockchain, &server.pubkey()).await;
+    assert_eq!(account_state.administrators, 0);
+}
+
+#[tokio::test]
+async fn resize_flow() {
+    let mut blockchain = program_test().start_with_context().await;
+    let rent = blockchain.banks_client.get_rent().await.unwrap();
+
+    let dweller = Keypair::new();
+    let mut transaction = Transaction::new_with_payer(
+        &[
+            system_instruction::create_account(
+                &blockchain.payer.pubkey(),
+                &dweller.pubkey(),
+                rent.minimum_balance(Dweller::LEN_V1 as usize),
+                Dweller::LEN_V1,
+                &satellite_servers::id(),
+            ),
+            instruction::initialize_dweller(
+                &dweller.pubkey(),
+                InitializeDwellerInput {
+                    name: [42; 32],
+                    hash: [42; 64],
+                    status: [42; 128],
+                },
+            )
+            .unwrap(),
+        ],
+        Some(&blockchain.payer.pubkey()),
+    );
+    transaction.sign(&[&blockchain.payer, &dweller], blockchain.last_blockhash);
+    blockchain
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap();
+
+    let account_state: Dweller = get_account_data(&mut blockchain, &dweller.pubkey()).await;
+    assert_eq!(account_state.version, StateVersion::V1);
+
+    let trx = resize_account_transaction(
+        &blockchain.payer,
+        &dweller.pubkey(),
+        &dweller,
+        &ResizeAccountInput::Dweller,
+        blockchain.last_blockhash,
+    );
+    blockchain
+        .banks_client
+        .process_transaction(trx)
+        .await
+        .unwrap();
+
+    let account = get_account(&mut blockchain, &dweller.pubkey()).await;
+    assert_eq!(account.data.len(), Dweller::LEN as usize);
+    assert!(rent.is_exempt(account.lamports, Dweller::LEN as usize));
+
+    let account_state: Dweller = get_account_data(&mut blockchain, &dweller.pubkey()).await;
+    assert_eq!(account_state.version, StateVersion::V2);
+    assert_eq!(account_state.name, [42; 32]);
+    assert_eq!(
+        account_state.extension.bump.get(),
+        Some(satellite_servers::program::find_base_bump(
+            &id(),
+            &dweller.pubkey()
+        ))
+    );
+    assert_eq!(account_state.status, [42; 128]);
+}
+
+#[tokio::test]
+async fn resize_channels_flow() {
+    let owner = Keypair::new();
+    let server = Pubkey::new_unique();
+    let channel =
+        satellite_servers::program::create_index_with_seed(&id(), ServerChannel::SEED, &server, 1)
+            .unwrap();
+    let group =
+        satellite_servers::program::create_index_with_seed(&id(), ServerGroup::SEED, &server, 0)
+            .unwrap();
+    let group_channel =
+        satellite_servers::program::create_index_with_seed(&id(), GroupChannel::SEED, &group, 0)
+            .unwrap();
+
+    // accounts created before channels and groups were versioned
+    let mut program_test = program_test();
+    let mut add_v1 = |key, data: Vec<u8>| {
+        program_test.add_account(
+            key,
+            Account {
+                lamports: 1_000_000_000,
+                data,
+                owner: id(),
+                ..Account::default()
+            },
+        )
+    };
+    let mut server_state = Server::try_from_slice(&[0; Server::LEN_V1 as usize]).unwrap();
+    server_state.version = StateVersion::V1;
+    server_state.owner = owner.pubkey();
+    server_state.channels = 2;
+    server_state.groups = 1;
+    add_v1(server, server_state.try_to_vec().unwrap());
+    add_v1(
+        channel,
+        ServerChannel {
+            version: StateVersion::V1,
+            container: server,
+            index: 1,
+            name: [7; 32],
+            ..ServerChannel::default()
+        }
+        .try_to_vec()
+        .unwrap(),
+    );
+    add_v1(
+        group,
+        ServerGroup {
+            version: StateVersion::V1,
+            container: server,
+            channels: 1,
+            ..ServerGroup::default()
+        }
+        .try_to_vec()
+        .unwrap(),
+    );
+    add_v1(
+        group_channel,
+        GroupChannel {
+            version: StateVersion::V1,
+            container: group,
+            channel,
+            ..GroupChannel::default()
+        }
+        .try_to_vec()
+        .unwrap(),
+    );
+
+    let mut blockchain = program_test.start_with_context().await;
+    let resize = |payer: &Keypair,
+                  account: &Pubkey,
+                  server_group: Option<&Pubkey>,
+                  input: &ResizeAccountInput,
+                  blockhash| {
+        let mut trx = Transaction::new_with_payer(
+            &[instruction::resize_server_account(
+                &payer.pubkey(),
+                account,
+                &owner.pubkey(),
+                &server,
+                server_group,
+                input,
+            )
+            .unwrap()],
+            Some(&payer.pubkey()),
+        );
+        trx.sign(&[payer, &owner], blockhash);
+        trx
+    };
+    let payer = &blockchain.payer;
+    let blockhash = blockchain.last_blockhash;
+    let trxs = [
+        resize(
+            payer,
+            &channel,
+            None,
+            &ResizeAccountInput::ServerChannel,
+            blockhash,
+        ),
+        resize(
+            payer,
+            &group,
+            None,
+            &ResizeAccountInput::ServerGroup,
+            blockhash,
+        ),
+        resize(
+            payer,
+            &group_channel,
+            Some(&group),
+            &ResizeAccountInput::GroupChannel,
+            blockhash,
+        ),
+    ];
+    for trx in trxs {
+        blockchain
+            .banks_client
+            .process_transaction(trx)
+            .await
+            .unwrap();
+    }
+
+    let account = get_account(&mut blockchain, &channel).await;
+    assert_eq!(account.data.len(), ServerChannel::LEN as usize);
+    let account_state: ServerChannel = get_account_data(&mut blockchain, &channel).await;
+    assert_eq!(account_state.version, StateVersion::V2);
+    assert_eq!(account_state.name, [7; 32]);
+    assert_eq!(account_state.extension.position, 1);
+
+    let account = get_account(&mut blockchain, &group).await;
+    assert_eq!(account.data.len(), ServerGroup::LEN as usize);
+    let account_state: ServerGroup = get_account_data(&mut blockchain, &group).await;
+    assert_eq!(account_state.version, StateVersion::V2);
+    assert_eq!(account_state.channels, 1);
+
+    let account = get_account(&mut blockchain, &group_channel).await;
+    assert_eq!(account.data.len(), GroupChannel::LEN as usize);
+    let account_state: GroupChannel = get_account_data(&mut blockchain, &group_channel).await;
+    assert_eq!(account_state.version, StateVersion::V2);
+    assert_eq!(account_state.channel, channel);
+
+    let blockhash = blockchain
+        .banks_client
+        .get_new_latest_blockhash(&blockchain.last_blockhash)
+        .await
+        .unwrap();
+    let trx = resize(
+        &blockchain.payer,
+        &channel,
+        None,
+        &ResizeAccountInput::ServerChannel,
+        blockhash,
+    );
+    assert!(blockchain
+        .banks_client
+        .process_transaction(trx)
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn positions_of_v1_server_flow() {
+    let owner = Keypair::new();
+    let server = Pubkey::new_unique();
+    let server_administrator = satellite_servers::program::create_index_with_seed(
+        &id(),
+        ServerAdministrator::SEED,
+        &server,
+        0,
+    )
+    .unwrap();
+    let channels: Vec<_> = (0..3)
+        .map(|index| {
+            satellite_servers::program::create_index_with_seed(
+                &id(),
+                ServerChannel::SEED,
+                &server,
+                index,
+            )
+            .unwrap()
+        })
+        .collect();
+
+    // server created before positions were stored, with channels resized already
+    let mut program_test = program_test();
+    let mut add = |key, data: Vec<u8>| {
+        program_test.add_account(
+            key,
+            Account {
+                lamports: 1_000_000_000,
+                data,
+                owner: id(),
+                ..Account::default()
+            },
+        )
+    };
+    let mut server_state = Server::try_from_slice(&[0; Server::LEN_V1 as usize]).unwrap();
+    server_state.version = StateVersion::V1;
+    server_state.owner = owner.pubkey();
+    server_state.administrators = 1;
+    server_state.channels = 2;
+    add(server, server_state.try_to_vec().unwrap());
+    add(
+        server_administrator,
+        ServerAdministrator {
+            version: StateVersion::V1,
+            container: server,
+            index: 0,
+            dweller: owner.pubkey(),
+        }
+        .try_to_vec()
+        .unwrap(),
+    );
+    let server_bump = satellite_servers::program::find_base_bump(&id(), &server);
+    for (index, channel) in channels[..2].iter().enumerate() {
+        let mut channel_state = ServerChannel {
+            version: StateVersion::V2,
+            container: server,
+            index: index as u64,
+            ..ServerChannel::default()
+        };
+        channel_state.extension.position = index as u64;
+        channel_state.extension.container_bump = BaseBump::new(server_bump);
+        channel_state.extension.bump =
+            BaseBump::new(satellite_servers::program::find_base_bump(&id(), channel));
+        add(*channel, channel_state.try_to_vec().unwrap());
+    }
+    add(channels[2], vec![0; ServerChannel::LEN as usize]);
+
+    let mut blockchain = program_test.start_with_context().await;
+    let add_channel = |payer: &Keypair, channel: &Pubkey, blockhash| {
+        add_channel_transaction(
+            payer,
+            &owner,
+            &server_administrator,
+            &server,
+            channel,
+            &AddChannelInput {
+                type_id: 0,
+                name: [1; 32],
+            },
+            blockhash,
+        )
+    };
+    let delete_channel = |payer: &Keypair, blockhash| {
+        delete_channel_transaction(
+            payer,
+            &owner,
+            &server_administrator,
+            &server,
+            &channels[0],
+            &channels[1],
+            blockhash,
+        )
+    };
+
+    // removal would leave no gap to add after
+    let blockhash = blockchain.last_blockhash;
+    for trx in [
+        add_channel(&blockchain.payer, &channels[2], blockhash),
+        delete_channel(&blockchain.payer, blockhash),
+    ] {
+        assert!(blockchain
+            .banks_client
+            .process_transaction(trx)
+            .await
+            .is_err());
+    }
+
+    let mut trx = Transaction::new_with_payer(
+        &[instruction::resize_account(
+            &blockchain.payer.pubkey(),
+            &server,
+            &owner.pubkey(),
+            &ResizeAccountInput::Server,
+        )
+        .unwrap()],
+        Some(&blockchain.payer.pubkey()),
+    );
+    trx.sign(&[&blockchain.payer, &owner], blockhash);
+    blockchain
+        .banks_client
+        .process_transaction(trx)
+        .await
+        .unwrap();
+
+    let blockhash = blockchain
+        .banks_client
+        .get_new_latest_blockhash(&blockhash)
+        .await
+        .unwrap();
+    let trx = delete_channel(&blockchain.payer, blockhash);
+    blockchain
+        .banks_client
+        .process_transaction(trx)
+        .await
+        .unwrap();
+    let trx = add_channel(&blockchain.payer, &channels[1], blockhash);
+    blockchain
+        .banks_client
+        .process_transaction(trx)
+        .await
+        .unwrap();
+
+    let account_state: ServerChannel = get_account_data(&mut blockchain, &channels[0]).await;
+    assert_eq!(account_state.extension.position, 1);
+    let account_state: ServerChannel = get_account_data(&mut blockchain, &channels[1]).await;
+    assert_eq!(account_state.name, [1; 32]);
+    assert_eq!(account_state.extension.position, 2);
+    let account_state: Server = get_account_data(&mut blockchain, &server).await;
+    assert_eq!(account_state.version, StateVersion::V2);
+    assert_eq!(account_state.channels, 2);
+    assert_eq!(account_state.extension.next_channel_position, 3);
+}
+
+#[tokio::test]
+async fn move_last_with_children_flow() {
+    let owner = Keypair::new();
+    let server = Pubkey::new_unique();
+    let derive = |seed, container: &Pubkey, index| {
+        satellite_servers::program::create_index_with_seed(&id(), seed, container, index).unwrap()
+    };
+    let bump = |key: &Pubkey| BaseBump::new(satellite_servers::program::find_base_bump(&id(), key));
+    let server_administrator = derive(ServerAdministrator::SEED, &server, 0);
+    let channels = [
+        derive(ServerChannel::SEED, &server, 0),
+        derive(ServerChannel::SEED, &server, 1),
+    ];
+    let groups = [
+        derive(ServerGroup::SEED, &server, 0),
+        derive(ServerGroup::SEED, &server, 1),
+        derive(ServerGroup::SEED, &server, 2),
+    ];
+    let group_channel_last = derive(GroupChannel::SEED, &groups[2], 0);
+    let group_channel = derive(GroupChannel::SEED, &groups[0], 0);
+    let pinned_message_last = derive(PinnedMessage::SEED, &channels[1], 0);
+    let pinned_message = derive(PinnedMessage::SEED, &channels[0], 0);
+
+    // last group is parent of group before it and has channel, last channel has pin
+    let mut program_test = program_test();
+    let mut add = |key, len: u64, state: &dyn Fn(&mut [u8])| {
+        let mut data = vec![0; len as usize];
+        state(&mut data);
+        program_test.add_account(
+            key,
+            Account {
+                lamports: 1_000_000_000,
+                data,
+                owner: id(),
+                ..Account::default()
+            },
+        )
+    };
+    add(server, Server::LEN, &|data| {
+        let mut state = Server::try_from_slice(data).unwrap();
+        state.version = StateVersion::V2;
+        state.owner = owner.pubkey();
+        state.administrators = 1;
+        state.channels = 2;
+        state.groups = 3;
+        state.extension.bump = bump(&server);
+        state.extension.next_channel_position = 2;
+        state.extension.next_group_position = 3;
+        state.serialize(&mut &mut data[..]).unwrap();
+    });
+    add(server_administrator, ServerAdministrator::LEN, &|data| {
+        ServerAdministrator {
+            version: StateVersion::V1,
+            container: server,
+            index: 0,
+            dweller: owner.pubkey(),
+        }
+        .serialize(&mut &mut data[..])
+        .unwrap()
+    });
+    for (index, channel) in channels.iter().enumerate() {
+        add(*channel, ServerChannel::LEN, &|data| {
+            let mut state = ServerChannel {
+                version: StateVersion::V2,
+                container: server,
+                index: index as u64,
+                name: [index as u8; 32],
+                ..ServerChannel::default()
+            };
+            state.extension.position = index as u64;
+            state.extension.container_bump = bump(&server);
+            state.extension.bump = bump(channel);
+            if index == 1 {
+                state.extension.group = groups[2];
+                state.extension.pins = 1;
+            }
+            state.serialize(&mut &mut data[..]).unwrap();
+        });
+    }
+    for (index, group) in groups.iter().enumerate() {
+        add(*group, ServerGroup::LEN, &|data| {
+            let mut state = ServerGroup {
+                version: StateVersion::V2,
+                container: server,
+                index: index as u64,
+                name: [index as u8; 32],
+                ..ServerGroup::default()
+            };
+            state.extension.position = index as u64;
+            state.extension.container_bump = bump(&server);
+            state.extension.bump = bump(group);
+            if index == 1 {
+                state.extension.parent = groups[2];
+            }
+            if index == 2 {
+                state.channels = 1;
+                state.extension.groups = 1;
+                state.extension.next_channel_position = 1;
+            }
+            state.serialize(&mut &mut data[..]).unwrap();
+        });
+    }
+    add(group_channel_last, GroupChannel::LEN, &|data| {
+        let mut state = GroupChannel {
+            version: StateVersion::V2,
+            container: groups[2],
+            index: 0,
+            channel: channels[1],
+            ..GroupChannel::default()
+        };
+        state.extension.container_bump = bump(&groups[2]);
+        state.serialize(&mut &mut data[..]).unwrap();
+    });
+    add(pinned_message_last, PinnedMessage::LEN, &|data| {
+        PinnedMessage {
+            version: StateVersion::V1,
+            container: channels[1],
+            index: 0,
+            content_hash: [9; 64],
+            ..PinnedMessage::default()
+        }
+        .serialize(&mut &mut data[..])
+        .unwrap()
+    });
+    add(group_channel, GroupChannel::LEN, &|_| {});
+    add(pinned_message, PinnedMessage::LEN, &|_| {});
+
+    let mut blockchain = program_test.start_with_context().await;
+    let delete_group = |payer: &Keypair, nested: &[&Pubkey], blockhash| {
+        let mut trx = Transaction::new_with_payer(
+            &[instruction::delete_group(
+                &owner.pubkey(),
+                &server_administrator,
+                &server,
+                &groups[0],
+                &groups[2],
+                None,
+                &[],
+                nested,
+                &[(&group_channel_last, &group_channel, &channels[1])],
+            )
+            .unwrap()],
+            Some(&payer.pubkey()),
+        );
+        trx.sign(&[payer, &owner], blockhash);
+        trx
+    };
+    let delete_channel = |payer: &Keypair, moved_group_channel, blockhash| {
+        let mut trx = Transaction::new_with_payer(
+            &[instruction::delete_channel(
+                &owner.pubkey(),
+                &server_administrator,
+                &server,
+                &channels[0],
+                &channels[1],
+                moved_group_channel,
+                &[(&pinned_message_last, &pinned_message)],
+            )
+            .unwrap()],
+            Some(&payer.pubkey()),
+        );
+        trx.sign(&[payer, &owner], blockhash);
+        trx
+    };
+
+    // nested group would point to erased address
+    let blockhash = blockchain.last_blockhash;
+    let trx = delete_group(&blockchain.payer, &[], blockhash);
+    assert!(blockchain
+        .banks_client
+        .process_transaction(trx)
+        .await
+        .is_err());
+    let trx = delete_group(&blockchain.payer, &[&groups[1]], blockhash);
+    blockchain
+        .banks_client
+        .process_transaction(trx)
+        .await
+        .unwrap();
+
+    let account_state: ServerGroup = get_account_data(&mut blockchain, &groups[0]).await;
+    assert_eq!(account_state.name, [2; 32]);
+    assert_eq!(account_state.index, 0);
+    assert_eq!(account_state.channels, 1);
+    assert_eq!(account_state.extension.groups, 1);
+    let account_state: ServerGroup = get_account_data(&mut blockchain, &groups[1]).await;
+    assert_eq!(account_state.extension.parent, groups[0]);
+    let account_state: GroupChannel = get_account_data(&mut blockchain, &group_channel).await;
+    assert_eq!(account_state.container, groups[0]);
+    assert_eq!(account_state.channel, channels[1]);
+    assert_eq!(account_state.extension.container_bump, bump(&groups[0]));
+    let account_state: GroupChannel = get_account_data(&mut blockchain, &group_channel_last).await;
+    assert_eq!(account_state, GroupChannel::default());
+    let account_state: ServerChannel = get_account_data(&mut blockchain, &channels[1]).await;
+    assert_eq!(account_state.extension.group, groups[0]);
+
+    // group channel would point to erased address
+    let blockhash = blockchain
+        .banks_client
+        .get_new_latest_blockhash(&blockhash)
+        .await
+        .unwrap();
+    let trx = delete_channel(&blockchain.payer, None, blockhash);
+    assert!(blockchain
+        .banks_client
+        .process_transaction(trx)
+        .await
+        .is_err());
+    let trx = delete_channel(&blockchain.payer, Some(&group_channel), blockhash);
+    blockchain
+        .banks_client
+        .process_transaction(trx)
+        .await
+        .unwrap();
+
+    let account_state: ServerChannel = get_account_data(&mut blockchain, &channels[0]).await;
+    assert_eq!(account_state.name, [1; 32]);
+    assert_eq!(account_state.index, 0);
+    assert_eq!(account_state.extension.pins, 1);
+    assert_eq!(account_state.extension.group, groups[0]);
+    let account_state: GroupChannel = get_account_data(&mut blockchain, &group_channel).await;
+    assert_eq!(account_state.channel, channels[0]);
+    let account_state: PinnedMessage = get_account_data(&mut blockchain, &pinned_message).await;
+    assert_eq!(account_state.container, channels[0]);
+    assert_eq!(account_state.content_hash, [9; 64]);
+    let account_state: PinnedMessage =
+        get_account_data(&mut blockchain, &pinned_message_last).await;
+    assert_eq!(account_state, PinnedMessage::default());
+    let account_state: Server = get_account_data(&mut blockchain, &server).await;
+    assert_eq!(account_state.channels, 1);
+    assert_eq!(account_state.groups, 2);
+}
+
+#[tokio::test]
+async fn delegate_flow() {
+    let mut blockchain = program_test().start_with_context().await;
+    let rent = blockchain.banks_client.get_rent().await.unwrap();
+
+    let dweller = Keypair::new();
+    test_initialize_dweller(
+        &blockchain.payer,
+        &dweller,
+        rent,
+        blockchain.last_blockhash,
+        &mut blockchain.banks_client,
+    )
+    .await;
+
+    for input in [
+        AddDelegateInput {
+            expiry: 0,
+            scope: DwellerDelegate::SCOPE_PROFILE,
+        },
+        AddDelegateInput {
+            expiry: i64::MAX,
+            scope: 0,
+        },
+        AddDelegateInput {
+            expiry: i64::MAX,
+            scope: DwellerDelegate::SCOPE_ALL + 1,
+        },
+    ] {
+        let trx = add_delegate_transaction(
+            &blockchain.payer,
+            &dweller,
+            &Keypair::new(),
+            rent,
+            &input,
+            blockchain.last_blockhash,
+        );
+        assert!(blockchain
+            .banks_client
+            .process_transaction(trx)
+            .await
+            .is_err());
+    }
+
+    let delegate = Keypair::new();
+    let trx = add_delegate_transaction(
+        &blockchain.payer,
+        &dweller,
+        &delegate,
+        rent,
+        &AddDelegateInput {
+            expiry: i64::MAX,
+            scope: DwellerDelegate::SCOPE_PROFILE,
+        },
+        blockchain.last_blockhash,
+    );
+    blockchain
+        .banks_client
+        .process_transaction(trx)
+        .await
+        .unwrap();
+
+    let account_state: DwellerDelegate =
+        get_account_data(&mut blockchain, &delegate.pubkey()).await;
+    assert_eq!(account_state.dweller, dweller.pubkey());
+
+    let mut trx = Transaction::new_with_payer(
+        &[instruction::set_dweller_name(
+            &dweller.pubkey(),
+            Some(&delegate.pubkey()),
+            &SetNameInput { name: [13; 32] },
+        )
+        .unwrap()],
+        Some(&blockchain.payer.pubkey()),
+    );
+    trx.sign(&[&blockchain.payer, &delegate], blockchain.last_blockhash);
+    blockchain
+        .banks_client
+        .process_transaction(trx)
+        .await
+        .unwrap();
+
+    let account_state: Dweller = get_account_data(&mut blockchain, &dweller.pubkey()).await;
+    assert_eq!(account_state.name, [13; 32]);
+
+    let trx = resize_account_transaction(
+        &blockchain.payer,
+        &dweller.pubkey(),
+        &delegate,
+        &ResizeAccountInput::Server,
+        blockchain.last_blockhash,
+    );
+    assert!(blockchain
+        .banks_client
+        .process_transaction(trx)
+        .await
+        .is_err());
+
+    let mut trx = Transaction::new_with_payer(
+        &[instruction::remove_delegate(
+            &dweller.pubkey(),
+            &dweller.pubkey(),
+            &delegate.pubkey(),
+            &blockchain.payer.pubkey(),
+        )
+        .unwrap()],
+        Some(&blockchain.payer.pubkey()),
+    );
+    trx.sign(&[&blockchain.payer, &dweller], blockchain.last_blockhash);
+    blockchain
+        .banks_client
+        .process_transaction(trx)
+        .await
+        .unwrap();
+
+    assert!(blockchain
+        .banks_client
+        .get_account(delegate.pubkey())
+        .await
+        .unwrap()
+        .is_none());
+    let account_state: Dweller = get_account_data(&mut blockchain, &dweller.pubkey()).await;
+    assert_eq!(account_state.extension.delegates, 0);
+}
+
+#[tokio::test]
+async fn close_dweller_flow() {
+    let mut blockchain = program_test().start_with_context().await;
+    let rent = blockchain.banks_client.get_rent().await.unwrap();
+
+    let dweller = Keypair::new();
+    test_initialize_dweller(
+        &blockchain.payer,
+        &dweller,
+        rent,
+        blockchain.last_blockhash,
+        &mut blockchain.banks_client,
+    )
+    .await;
+
+    let delegate = Keypair::new();
+    let trx = add_delegate_transaction(
+        &blockchain.payer,
+        &dweller,
+        &delegate,
+        rent,
+        &AddDelegateInput {
+            expiry: i64::MAX,
+            scope: DwellerDelegate::SCOPE_ALL,
+        },
+        blockchain.last_blockhash,
+    );
+    blockchain
+        .banks_client
+        .process_transaction(trx)
+        .await
+        .unwrap();
+
+    let account_state: Dweller = get_account_data(&mut blockchain, &dweller.pubkey()).await;
+    assert_eq!(account_state.extension.delegates, 1);
+
+    let destination = Pubkey::new_unique();
+    let mut trx = Transaction::new_with_payer(
+        &[instruction::close_dweller(&dweller.pubkey(), &destination, &[]).unwrap()],
+        Some(&blockchain.payer.pubkey()),
+    );
+    trx.sign(&[&blockchain.payer, &dweller], blockchain.last_blockhash);
+    assert!(blockchain
+        .banks_client
+        .process_transaction(trx)
+        .await
+        .is_err());
+
+    let mut trx = Transaction::new_with_payer(
+        &[
+            instruction::close_dweller(&dweller.pubkey(), &destination, &[&delegate.pubkey()])
+                .unwrap(),
+        ],
+        Some(&blockchain.payer.pubkey()),
+    );
+    trx.sign(&[&blockchain.payer, &dweller], blockchain.last_blockhash);
+    blockchain
+        .banks_client
+        .process_transaction(trx)
+        .await
+        .unwrap();
+
+    for closed in [dweller.pubkey(), delegate.pubkey()] {
+        assert!(blockchain
+            .banks_client
+            .get_account(closed)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    let account = get_account(&mut blockchain, &destination).await;
+    assert_eq!(
+        account.lamports,
+        rent.minimum_balance(Dweller::LEN as usize)
+            + rent.minimum_balance(DwellerDelegate::LEN as usize)
+    );
+}
+
+#[tokio::test]
+async fn direct_channel_flow() {
+    let mut blockchain = program_test().start_with_context().await;
+    let rent = blockchain.banks_client.get_rent().await.unwrap();
+
+    let dwellers = [Keypair::new(), Keypair::new(), Keypair::new()];
+    for dweller in &dwellers {
+        test_initialize_dweller(
+            &blockchain.payer,
+            dweller,
+            rent,
+            blockchain.last_blockhash,
+            &mut blockchain.banks_client,
+        )
+        .await;
+    }
+
+    let direct_channel = create_derived_account_index(
+        &mut blockchain,
+        &dwellers[0].pubkey(),
+        DirectChannel::SEED,
+        0,
+        instruction::AddressTypeInput::DirectChannel(0),
+    )
+    .await;
+
+    let mut trx = Transaction::new_with_payer(
+        &[instruction::create_direct_channel(
+            &dwellers[0].pubkey(),
+            &direct_channel,
+            None,
+            &[&dwellers[1].pubkey()],
+            &SetHashInput { hash: [7; 64] },
+        )
+        .unwrap()],
+        Some(&blockchain.payer.pubkey()),
+    );
+    trx.sign(
+        &[&blockchain.payer, &dwellers[0]],
+        blockchain.last_blockhash,
+    );
+    blockchain
+        .banks_client
+        .process_transaction(trx)
+        .await
+        .unwrap();
+
+    let account_state: Dweller = get_account_data(&mut blockchain, &dwellers[0].pubkey()).await;
+    assert_eq!(account_state.extension.direct_channels, 1);
+    let account_state: DirectChannel = get_account_data(&mut blockchain, &direct_channel).await;
+    assert_eq!(account_state.db_hash, [7; 64]);
+    assert_eq!(
+        account_state.participants(),
+        &[dwellers[0].pubkey(), dwellers[1].pubkey()]
+    );
+
+    let mut trx = Transaction::new_with_payer(
+        &[instruction::add_direct_channel_participant(
+            &dwellers[1].pubkey(),
+            &direct_channel,
+            &dwellers[2].pubkey(),
+            None,
+        )
+        .unwrap()],
+        Some(&blockchain.payer.pubkey()),
+    );
+    trx.sign(
+        &[&blockchain.payer, &dwellers[1]],
+        blockchain.last_blockhash,
+    );
+    blockchain
+        .banks_client
+        .process_transaction(trx)
+        .await
+        .unwrap();
+
+    let mut trx = Transaction::new_with_payer(
+        &[instruction::remove_direct_channel_participant(
+            &dwellers[1].pubkey(),
+            &direct_channel,
+            &dwellers[2].pubkey(),
+            &direct_channel,
+            None,
+        )
+        .unwrap()],
+        Some(&blockchain.payer.pubkey()),
+    );
+    trx.sign(
+        &[&blockchain.payer, &dwellers[1]],
+        blockchain.last_blockhash,
+    );
+    assert!(blockchain
+        .banks_client
+        .process_transaction(trx)
+        .await
+        .is_err());
+
+    let mut trx = Transaction::new_with_payer(
+        &[instruction::remove_direct_channel_participant(
+            &dwellers[0].pubkey(),
+            &direct_channel,
+            &dwellers[1].pubkey(),
+            &direct_channel,
+            None,
+        )
+        .unwrap()],
+        Some(&blockchain.payer.pubkey()),
+    );
+    trx.sign(
+        &[&blockchain.payer, &dwellers[0]],
+        blockchain.last_blockhash,
+    );
+    blockchain
+        .banks_client
+        .process_transaction(trx)
+        .await
+        .unwrap();
+
+    // creator who left can not remove
+    let mut trx = Transaction::new_with_payer(
+        &[
+            instruction::add_direct_channel_participant(
+                &dwellers[2].pubkey(),
+                &direct_channel,
+                &dwellers[1].pubkey(),
+                None,
+            )
+            .unwrap(),
+            instruction::leave_direct_channel(
+                &dwellers[0].pubkey(),
+                &direct_channel,
+                &dwellers[0].pubkey(),
+                &direct_channel,
+                None,
+            )
+            .unwrap(),
+        ],
+        Some(&blockchain.payer.pubkey()),
+    );
+    trx.sign(
+        &[&blockchain.payer, &dwellers[2], &dwellers[0]],
+        blockchain.last_blockhash,
+    );
+    blockchain
+        .banks_client
+        .process_transaction(trx)
+        .await
+        .unwrap();
+
+    let blockhash = blockchain
+        .banks_client
+        .get_new_latest_blockhash(&blockchain.last_blockhash)
+        .await
+        .unwrap();
+    let mut trx = Transaction::new_with_payer(
+        &[instruction::remove_direct_channel_participant(
+            &dwellers[0].pubkey(),
+            &direct_channel,
+            &dwellers[1].pubkey(),
+            &direct_channel,
+            None,
+        )
+        .unwrap()],
+        Some(&blockchain.payer.pubkey()),
+    );
+    trx.sign(&[&blockchain.payer, &dwellers[0]], blockhash);
+    assert!(blockchain
+        .banks_client
+        .process_transaction(trx)
+        .await
+        .is_err());
+
+    let mut trx = Transaction::new_with_payer(
+        &[instruction::leave_direct_channel(
+            &dwellers[2].pubkey(),
+            &direct_channel,
+            &dwellers[1].pubkey(),
+            &direct_channel,
+            None,
+        )
+        .unwrap()],
+        Some(&blockchain.payer.pubkey()),
+    );
+    trx.sign(
+        &[&blockchain.payer, &dwellers[2]],
+        blockchain.last_blockhash,
+    );
+    assert!(blockchain
         .banks_client
         .process_transaction(trx)
         .await
-        .unwrap();
-
-    let trx = add_channel_to_group_transaction(
-        &blockchain.payer,
-        &server.pubkey(),
-        &dweller_admin_1,
-        &server_administrators[0],
-        &server_channels[0],
-        &server_groups[0],
-        &group_channels[0],
+        .is_err());
+
+    // channel with single participant is closed
+    let mut trx = Transaction::new_with_payer(
+        &[instruction::leave_direct_channel(
+            &dwellers[2].pubkey(),
+            &direct_channel,
+            &dwellers[0].pubkey(),
+            &direct_channel,
+            None,
+        )
+        .unwrap()],
+        Some(&blockchain.payer.pubkey()),
+    );
+    trx.sign(
+        &[&blockchain.payer, &dwellers[2]],
         blockchain.last_blockhash,
     );
     blockchain
@@ -408,118 +2203,250 @@ async fn positive_add_remove_flow() {
         .await
         .unwrap();
 
-    let account_state: ServerGroup = get_account_data(&mut blockchain, &server_groups[0]).await;
-    assert_eq!(account_state.channels, 1);
+    let account_state: DirectChannel = get_account_data(&mut blockchain, &direct_channel).await;
+    assert_eq!(account_state.version, StateVersion::Uninitialized);
+    let account_state: Dweller = get_account_data(&mut blockchain, &dwellers[0].pubkey()).await;
+    assert_eq!(account_state.extension.direct_channels, 0);
+}
 
-    // removing/deleting
+#[tokio::test]
+async fn token_gate_flow() {
+    let mint = Pubkey::new_unique();
+    let dweller = Keypair::new();
+    let mut program_test = program_test();
+    let mut token_account = |mint, owner, amount| {
+        let mut data = vec![0; spl_token::state::Account::LEN];
+        spl_token::state::Account {
+            mint,
+            owner,
+            amount,
+            state: spl_token::state::AccountState::Initialized,
+            ..spl_token::state::Account::default()
+        }
+        .pack_into_slice(&mut data);
+        let key = Pubkey::new_unique();
+        program_test.add_account(
+            key,
+            Account {
+                lamports: 1_000_000_000,
+                data,
+                owner: spl_token::id(),
+                ..Account::default()
+            },
+        );
+        key
+    };
+    let poor = token_account(mint, dweller.pubkey(), 1);
+    let foreign = token_account(mint, Pubkey::new_unique(), 10);
+    let wrong_mint = token_account(Pubkey::new_unique(), dweller.pubkey(), 10);
+    let holder = token_account(mint, dweller.pubkey(), 10);
 
-    // groups/channels
+    let mut blockchain = program_test.start_with_context().await;
+    let rent = blockchain.banks_client.get_rent().await.unwrap();
 
-    let trx = remove_channel_from_group_transaction(
+    let owner = Keypair::new();
+    for keypair in [&owner, &dweller] {
+        test_initialize_dweller(
+            &blockchain.payer,
+            keypair,
+            rent,
+            blockchain.last_blockhash,
+            &mut blockchain.banks_client,
+        )
+        .await;
+    }
+
+    let server = Keypair::new();
+    let owner_server = create_derived_account_index(
+        &mut blockchain,
+        &owner.pubkey(),
+        DwellerServer::SEED,
+        0,
+        instruction::AddressTypeInput::DwellerServer(0),
+    )
+    .await;
+    let mut server_members = Vec::new();
+    for index in 0..2 {
+        server_members.push(
+            create_derived_account_index(
+                &mut blockchain,
+                &server.pubkey(),
+                ServerMember::SEED,
+                index,
+                instruction::AddressTypeInput::ServerMember(index),
+            )
+            .await,
+        );
+    }
+    test_initialize_server(
         &blockchain.payer,
-        &server.pubkey(),
-        &dweller_admin_1,
-        &server_administrators[0],
-        &server_groups[0],
-        &group_channels[0],
-        &group_channels[0],
+        &owner,
+        &server,
+        &owner_server,
+        &server_members[0],
+        rent,
         blockchain.last_blockhash,
-    );
-    blockchain
-        .banks_client
-        .process_transaction(trx)
-        .await
-        .unwrap();
+        &mut blockchain.banks_client,
+    )
+    .await;
 
-    let trx = delete_group_transaction(
+    let server_administrator = create_derived_account_index(
+        &mut blockchain,
+        &server.pubkey(),
+        ServerAdministrator::SEED,
+        0,
+        instruction::AddressTypeInput::ServerAdministrator(0),
+    )
+    .await;
+    test_add_administrator(
         &blockchain.payer,
-        &dweller_admin_1,
-        &server_administrators[0],
+        &owner,
+        &owner.pubkey(),
         &server.pubkey(),
-        &server_groups[0],
-        &server_groups[0],
-        &group_channels[0],
+        &server_administrator,
         blockchain.last_blockhash,
-    );
+        &mut blockchain.banks_client,
+    )
+    .await;
 
-    blockchain
+    let dweller_server = create_derived_account_index(
+        &mut blockchain,
+        &dweller.pubkey(),
+        DwellerServer::SEED,
+        0,
+        instruction::AddressTypeInput::DwellerServer(0),
+    )
+    .await;
+    let join = |payer: &Keypair, token: &Pubkey, blockhash| {
+        join_server_transaction(
+            payer,
+            &server.pubkey(),
+            &server_members[1],
+            token,
+            &dweller,
+            &dweller_server,
+            blockhash,
+        )
+    };
+
+    // not gated yet
+    let trx = join(&blockchain.payer, &holder, blockchain.last_blockhash);
+    assert!(blockchain
         .banks_client
         .process_transaction(trx)
         .await
-        .unwrap();
+        .is_err());
 
-    let account_state: Server = get_account_data(&mut blockchain, &server.pubkey()).await;
-    assert_eq!(account_state.groups, 0);
-
-    let trx = delete_channel_transaction(
+    // administrator of dweller has layout of invite, but not its address
+    let dweller_administrator = create_derived_account_index(
+        &mut blockchain,
+        &server.pubkey(),
+        ServerAdministrator::SEED,
+        1,
+        instruction::AddressTypeInput::ServerAdministrator(1),
+    )
+    .await;
+    test_add_administrator(
         &blockchain.payer,
-        &dweller_admin_1,
-        &server_administrators[0],
+        &owner,
+        &dweller.pubkey(),
         &server.pubkey(),
-        &server_channels[0],
-        &server_channels[0],
+        &dweller_administrator,
+        blockchain.last_blockhash,
+        &mut blockchain.banks_client,
+    )
+    .await;
+    let trx = join(
+        &blockchain.payer,
+        &dweller_administrator,
         blockchain.last_blockhash,
     );
-
-    blockchain
+    assert!(blockchain
         .banks_client
         .process_transaction(trx)
         .await
-        .unwrap();
+        .is_err());
 
-    let account_state: Server = get_account_data(&mut blockchain, &server.pubkey()).await;
-    assert_eq!(account_state.channels, 0);
+    let set_gate = |payer: &Keypair, gate, blockhash| {
+        let mut trx = Transaction::new_with_payer(
+            &[instruction::set_server_gate(
+                &owner.pubkey(),
+                &server_administrator,
+                &server.pubkey(),
+                &SetServerGateInput { gate },
+            )
+            .unwrap()],
+            Some(&payer.pubkey()),
+        );
+        trx.sign(&[payer, &owner], blockhash);
+        trx
+    };
 
-    // members/admin
+    for gate in [
+        TokenGate {
+            kind: TokenGateKind::SplToken,
+            mint,
+            amount: 0,
+        },
+        TokenGate {
+            kind: TokenGateKind::None,
+            mint,
+            amount: 0,
+        },
+        TokenGate {
+            kind: TokenGateKind::None,
+            mint: Pubkey::default(),
+            amount: 5,
+        },
+        TokenGate {
+            kind: TokenGateKind::SplToken,
+            mint: Pubkey::default(),
+            amount: 5,
+        },
+        TokenGate {
+            kind: TokenGateKind::Nft,
+            mint: Pubkey::default(),
+            amount: 0,
+        },
+    ] {
+        let trx = set_gate(&blockchain.payer, gate, blockchain.last_blockhash);
+        assert!(blockchain
+            .banks_client
+            .process_transaction(trx)
+            .await
+            .is_err());
+    }
 
-    let trx = leave_server_transaction(
+    let trx = set_gate(
         &blockchain.payer,
-        &server.pubkey(),
-        &server_members[1],
-        &server_members[1],
-        &dweller_1,
-        &dweller_servers[4],
-        &dweller_servers[4],
+        TokenGate {
+            kind: TokenGateKind::SplToken,
+            mint,
+            amount: 5,
+        },
         blockchain.last_blockhash,
     );
-
     blockchain
         .banks_client
         .process_transaction(trx)
         .await
         .unwrap();
 
-    let account_state: Server = get_account_data(&mut blockchain, &server.pubkey()).await;
-    assert_eq!(account_state.members, 1);
-
-    let trx = revoke_invite_server_transaction(
-        &blockchain.payer,
-        &server.pubkey(),
-        &dweller_admin_1,
-        &server_administrators[0],
-        &server_member_statuses[0],
-        &server_member_statuses[0],
-        blockchain.last_blockhash,
-    );
-
-    blockchain
+    let blockhash = blockchain
         .banks_client
-        .process_transaction(trx)
+        .get_new_latest_blockhash(&blockchain.last_blockhash)
         .await
         .unwrap();
+    for token in [&poor, &foreign, &wrong_mint] {
+        let trx = join(&blockchain.payer, token, blockhash);
+        assert!(blockchain
+            .banks_client
+            .process_transaction(trx)
+            .await
+            .is_err());
+    }
 
-    let account_state: Server = get_account_data(&mut blockchain, &server.pubkey()).await;
-    assert_eq!(account_state.member_statuses, 0);
-
-    let trx = remove_admin_transaction(
-        &blockchain.payer,
-        &dweller_owner,
-        &server.pubkey(),
-        &server_administrators[0],
-        &server_administrators[0],
-        blockchain.last_blockhash,
-    );
-
+    let trx = join(&blockchain.payer, &holder, blockhash);
     blockchain
         .banks_client
         .process_transaction(trx)
@@ -527,7 +2454,10 @@ async fn positive_add_remove_flow() {
         .unwrap();
 
     let account_state: Server = get_account_data(&mut blockchain, &server.pubkey()).await;
-    assert_eq!(account_state.administrators, 0);
+    assert_eq!(account_state.members, 2);
+    assert_eq!(account_state.extension.gate.mint, mint);
+    let account_state: ServerMember = get_account_data(&mut blockchain, &server_members[1]).await;
+    assert_eq!(account_state.dweller, dweller.pubkey());
 }
 
 pub async fn create_derived_account_index(
@@ -668,8 +2598,11 @@ mod sdk {
     };
 
     use crate::instruction::{
-        self, AddChannelInput, CreateGroupInput, SetDwellerStatusInput, SetHashInput, SetNameInput,
+        self, AddChannelInput, AddDelegateInput, CreateGroupInput, PinMessageInput,
+        ReorderChannelsInput, ResizeAccountInput, SetDwellerStatusInput, SetHashInput,
+        SetNameInput,
     };
+    use satellite_servers::state::DwellerDelegate;
 
     /// assumes not program dweller
     pub fn add_invite_transaction(
@@ -713,6 +2646,7 @@ mod sdk {
                 server_member_status,
                 &dweller.pubkey(),
                 dweller_server,
+                None,
             )
             .unwrap()],
             Some(&payer.pubkey()),
@@ -722,12 +2656,14 @@ mod sdk {
     }
 
     /// assumes not program dweller
+    #[allow(clippy::too_many_arguments)]
     pub fn create_group_transaction(
         payer: &Keypair,
         dweller_administrator: &Keypair,
         server_administrator: &Pubkey,
         server: &Pubkey,
         server_group: &Pubkey,
+        server_group_parent: Option<&Pubkey>,
         input: &CreateGroupInput,
         recent_blockhash: solana_program::hash::Hash,
     ) -> Transaction {
@@ -737,6 +2673,7 @@ mod sdk {
                 server_administrator,
                 server,
                 &server_group,
+                server_group_parent,
                 input,
             )
             .unwrap()],
@@ -807,6 +2744,7 @@ mod sdk {
         dweller_administrator: &Keypair,
         server_administrator: &Pubkey,
         server_group: &Pubkey,
+        server_channel: &Pubkey,
         group_channel: &Pubkey,
         group_channel_last: &Pubkey,
         recent_blockhash: solana_program::hash::Hash,
@@ -817,6 +2755,7 @@ mod sdk {
                 &dweller_administrator.pubkey(),
                 server_administrator,
                 server_group,
+                server_channel,
                 group_channel,
                 group_channel_last,
             )
@@ -827,7 +2766,7 @@ mod sdk {
         transaction
     }
 
-    /// assumes not program dweller
+    /// assumes not program dweller and no nested groups in moved last group
     #[allow(clippy::too_many_arguments)]
     pub fn delete_group_transaction(
         payer: &Keypair,
@@ -836,7 +2775,9 @@ mod sdk {
         server: &Pubkey,
         server_group: &Pubkey,
         server_group_last: &Pubkey,
-        group_channels: &Pubkey,
+        server_group_parent: Option<&Pubkey>,
+        group_channels: &[(&Pubkey, &Pubkey)],
+        moved_group_channels: &[(&Pubkey, &Pubkey, &Pubkey)],
         recent_blockhash: solana_program::hash::Hash,
     ) -> Transaction {
         let mut transaction = Transaction::new_with_payer(
@@ -846,7 +2787,10 @@ mod sdk {
                 server,
                 server_group,
                 server_group_last,
-                &[group_channels],
+                server_group_parent,
+                group_channels,
+                &[],
+                moved_group_channels,
             )
             .unwrap()],
             Some(&payer.pubkey()),
@@ -855,7 +2799,7 @@ mod sdk {
         transaction
     }
 
-    /// assumes not program dweller
+    /// assumes not program dweller and no group or pins of moved last channel
     #[allow(clippy::too_many_arguments)]
     pub fn delete_channel_transaction(
         payer: &Keypair,
@@ -873,6 +2817,8 @@ mod sdk {
                 server,
                 server_channel,
                 server_channel_last,
+                None,
+                &[],
             )
             .unwrap()],
             Some(&payer.pubkey()),
@@ -901,6 +2847,7 @@ mod sdk {
                 &dweller.pubkey(),
                 dweller_server,
                 dweller_server_last,
+                None,
             )
             .unwrap()],
             Some(&payer.pubkey()),
@@ -965,7 +2912,7 @@ mod sdk {
         recent_blockhash: solana_program::hash::Hash,
     ) -> Transaction {
         let mut transaction = Transaction::new_with_payer(
-            &[instruction::set_dweller_name(&dweller.pubkey(), input).unwrap()],
+            &[instruction::set_dweller_name(&dweller.pubkey(), None, input).unwrap()],
             Some(&payer.pubkey()),
         );
         transaction.sign(&[payer, dweller], recent_blockhash);
@@ -980,7 +2927,7 @@ mod sdk {
         recent_blockhash: solana_program::hash::Hash,
     ) -> Transaction {
         let mut transaction = Transaction::new_with_payer(
-            &[instruction::set_dweller_photo(&dweller.pubkey(), input).unwrap()],
+            &[instruction::set_dweller_photo(&dweller.pubkey(), None, input).unwrap()],
             Some(&payer.pubkey()),
         );
         transaction.sign(&[payer, dweller], recent_blockhash);
@@ -995,7 +2942,7 @@ mod sdk {
         recent_blockhash: solana_program::hash::Hash,
     ) -> Transaction {
         let mut transaction = Transaction::new_with_payer(
-            &[instruction::set_dweller_status(&dweller.pubkey(), input).unwrap()],
+            &[instruction::set_dweller_status(&dweller.pubkey(), None, input).unwrap()],
             Some(&payer.pubkey()),
         );
         transaction.sign(&[payer, dweller], recent_blockhash);
@@ -1047,4 +2994,133 @@ mod sdk {
         transaction.sign(&[payer, dweller_administrator], recent_blockhash);
         transaction
     }
+
+    /// assumes not program dweller
+    pub fn resize_account_transaction(
+        payer: &Keypair,
+        account: &Pubkey,
+        authority: &Keypair,
+        input: &ResizeAccountInput,
+        recent_blockhash: solana_program::hash::Hash,
+    ) -> Transaction {
+        let mut transaction = Transaction::new_with_payer(
+            &[
+                instruction::resize_account(&payer.pubkey(), account, &authority.pubkey(), input)
+                    .unwrap(),
+            ],
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[payer, authority], recent_blockhash);
+        transaction
+    }
+
+    /// assumes not program dweller
+    #[allow(clippy::too_many_arguments)]
+    pub fn reorder_channels_transaction(
+        payer: &Keypair,
+        dweller_administrator: &Keypair,
+        server_administrator: &Pubkey,
+        server: &Pubkey,
+        server_group: Option<&Pubkey>,
+        in_order: &[&Pubkey],
+        input: &ReorderChannelsInput,
+        recent_blockhash: solana_program::hash::Hash,
+    ) -> Transaction {
+        let mut transaction = Transaction::new_with_payer(
+            &[instruction::reorder_channels(
+                &dweller_administrator.pubkey(),
+                server_administrator,
+                server,
+                server_group,
+                in_order,
+                input,
+            )
+            .unwrap()],
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[payer, dweller_administrator], recent_blockhash);
+        transaction
+    }
+
+    /// creates delegate account owned by program and registers it for dweller
+    pub fn add_delegate_transaction(
+        payer: &Keypair,
+        dweller: &Keypair,
+        delegate: &Keypair,
+        rent: solana_program::rent::Rent,
+        input: &AddDelegateInput,
+        recent_blockhash: solana_program::hash::Hash,
+    ) -> Transaction {
+        let mut transaction = Transaction::new_with_payer(
+            &[
+                solana_program::system_instruction::create_account(
+                    &payer.pubkey(),
+                    &delegate.pubkey(),
+                    rent.minimum_balance(DwellerDelegate::LEN as usize),
+                    DwellerDelegate::LEN,
+                    &satellite_servers::id(),
+                ),
+                instruction::add_delegate(&dweller.pubkey(), &delegate.pubkey(), input).unwrap(),
+            ],
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[payer, dweller, delegate], recent_blockhash);
+        transaction
+    }
+
+    /// assumes not program dweller
+    #[allow(clippy::too_many_arguments)]
+    pub fn pin_message_transaction(
+        payer: &Keypair,
+        dweller_administrator: &Keypair,
+        server_administrator: &Pubkey,
+        server: &Pubkey,
+        server_channel: &Pubkey,
+        pinned_message: &Pubkey,
+        input: &PinMessageInput,
+        recent_blockhash: solana_program::hash::Hash,
+    ) -> Transaction {
+        let mut transaction = Transaction::new_with_payer(
+            &[instruction::pin_message(
+                &dweller_administrator.pubkey(),
+                server_administrator,
+                server,
+                server_channel,
+                pinned_message,
+                input,
+            )
+            .unwrap()],
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[payer, dweller_administrator], recent_blockhash);
+        transaction
+    }
+
+    /// assumes not program dweller
+    #[allow(clippy::too_many_arguments)]
+    pub fn unpin_message_transaction(
+        payer: &Keypair,
+        dweller_administrator: &Keypair,
+        server_administrator: &Pubkey,
+        server: &Pubkey,
+        server_channel: &Pubkey,
+        pinned_message: &Pubkey,
+        pinned_message_last: &Pubkey,
+        recent_blockhash: solana_program::hash::Hash,
+    ) -> Transaction {
+        let mut transaction = Transaction::new_with_payer(
+            &[instruction::unpin_message(
+                &dweller_administrator.pubkey(),
+                server_administrator,
+                server,
+                server_channel,
+                pinned_message,
+                pinned_message_last,
+            )
+            .unwrap()],
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[payer, dweller_administrator], recent_blockhash);
+        transaction
+    }
 }
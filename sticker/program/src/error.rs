@@ -30,6 +30,69 @@ pub enum StickerProgramError {
     /// No more tokens to mint
     #[error("No more tokens to mint")]
     NoTokensToMint,
+    /// Curator already added
+    #[error("Curator already added")]
+    CuratorAlreadyAdded,
+    /// No free curator slots
+    #[error("No free curator slots")]
+    TooManyCurators,
+    /// Curator not found
+    #[error("Curator not found")]
+    CuratorNotFound,
+    /// Artist is not approved
+    #[error("Artist is not approved")]
+    ArtistNotApproved,
+    /// Artist is not pending approval
+    #[error("Artist is not pending approval")]
+    ArtistNotPending,
+    /// Token account mint differs from sticker payment mint
+    #[error("Token account mint differs from sticker payment mint")]
+    WrongPaymentMint,
+    /// Fee is more than whole price
+    #[error("Fee is more than whole price")]
+    InvalidFee,
+    /// Wrong treasury token account
+    #[error("Wrong treasury token account")]
+    WrongTreasury,
+    /// Sticker belongs to other sticker factory
+    #[error("Sticker belongs to other sticker factory")]
+    WrongStickerFactory,
+    /// Royalty is more than whole price
+    #[error("Royalty is more than whole price")]
+    InvalidRoyalty,
+    /// NFT token is not of sticker mint or not owned by seller
+    #[error("NFT token is not of sticker mint or not owned by seller")]
+    WrongNftToken,
+    /// Listing does not match given accounts
+    #[error("Listing does not match given accounts")]
+    WrongListing,
+    /// Wrong sticker pack size
+    #[error("Wrong sticker pack size")]
+    WrongStickerPackSize,
+    /// Sticker is not in pack
+    #[error("Sticker is not in pack")]
+    StickerNotInPack,
+    /// Sale has not started yet
+    #[error("Sale has not started yet")]
+    SaleNotStarted,
+    /// Sale has ended
+    #[error("Sale has ended")]
+    SaleEnded,
+    /// Sale must start before it ends and early bird price must be set and end within sale
+    #[error("Sale must start before it ends and early bird price must be set and end within sale")]
+    InvalidSaleSchedule,
+    /// Sticker is paused
+    #[error("Sticker is paused")]
+    StickerPaused,
+    /// Sticker is retired
+    #[error("Sticker is retired")]
+    StickerRetired,
+    /// Account already has current layout
+    #[error("Account already has current layout")]
+    AccountAlreadyMigrated,
+    /// NFT token is approved to other account
+    #[error("NFT token is approved to other account")]
+    NftTokenDelegated,
 }
 impl From<StickerProgramError> for ProgramError {
     fn from(e: StickerProgramError) -> Self {
@@ -54,6 +117,39 @@ impl PrintProgramError for StickerProgramError {
             StickerProgramError::WrongStickerCreator => msg!("Wrong sticker creator"),
             StickerProgramError::WrongTokenMintAuthority => msg!("Wrong token mint authority"),
             StickerProgramError::NoTokensToMint => msg!("No more tokens to mint"),
+            StickerProgramError::CuratorAlreadyAdded => msg!("Curator already added"),
+            StickerProgramError::TooManyCurators => msg!("No free curator slots"),
+            StickerProgramError::CuratorNotFound => msg!("Curator not found"),
+            StickerProgramError::ArtistNotApproved => msg!("Artist is not approved"),
+            StickerProgramError::ArtistNotPending => msg!("Artist is not pending approval"),
+            StickerProgramError::WrongPaymentMint => {
+                msg!("Token account mint differs from sticker payment mint")
+            }
+            StickerProgramError::InvalidFee => msg!("Fee is more than whole price"),
+            StickerProgramError::WrongTreasury => msg!("Wrong treasury token account"),
+            StickerProgramError::WrongStickerFactory => {
+                msg!("Sticker belongs to other sticker factory")
+            }
+            StickerProgramError::InvalidRoyalty => msg!("Royalty is more than whole price"),
+            StickerProgramError::WrongNftToken => {
+                msg!("NFT token is not of sticker mint or not owned by seller")
+            }
+            StickerProgramError::WrongListing => msg!("Listing does not match given accounts"),
+            StickerProgramError::WrongStickerPackSize => msg!("Wrong sticker pack size"),
+            StickerProgramError::StickerNotInPack => msg!("Sticker is not in pack"),
+            StickerProgramError::SaleNotStarted => msg!("Sale has not started yet"),
+            StickerProgramError::SaleEnded => msg!("Sale has ended"),
+            StickerProgramError::InvalidSaleSchedule => {
+                msg!("Sale must start before it ends and early bird price must be set and end within sale")
+            }
+            StickerProgramError::StickerPaused => msg!("Sticker is paused"),
+            StickerProgramError::StickerRetired => msg!("Sticker is retired"),
+            StickerProgramError::AccountAlreadyMigrated => {
+                msg!("Account already has current layout")
+            }
+            StickerProgramError::NftTokenDelegated => {
+                msg!("NFT token is approved to other account")
+            }
         }
     }
 }
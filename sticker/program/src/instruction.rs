@@ -8,6 +8,8 @@ use solana_program::{
     system_program, sysvar,
 };
 
+use crate::state::SaleSchedule;
+
 /// Arguments to create new artist
 #[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
 pub struct RegisterArtist {
@@ -19,6 +21,17 @@ pub struct RegisterArtist {
     pub description: [u8; 256],
 }
 
+/// Arguments to update artist
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub struct UpdateArtist {
+    /// Name
+    pub name: [u8; 32],
+    /// Signature
+    pub signature: [u8; 256],
+    /// Description
+    pub description: [u8; 256],
+}
+
 /// Arguments to create new sticker
 #[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
 pub struct CreateNewSticker {
@@ -32,6 +45,10 @@ pub struct CreateNewSticker {
     pub symbol: [u8; 8],
     /// Name
     pub name: [u8; 32],
+    /// Mint of tokens sticker is paid with, must be mint of artist's account to receive payments
+    pub payment_mint: Pubkey,
+    /// Royalty paid to artist on resale in basis points
+    pub royalty_bps: u16,
 }
 
 /// Address type
@@ -43,6 +60,17 @@ pub enum AddressType {
     Sticker,
 }
 
+/// Account to migrate
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub enum MigrateAccountInput {
+    /// Sticker factory
+    StickerFactory,
+    /// Artist with given index in its sticker factory
+    Artist(u64),
+    /// Sticker with given index in its sticker factory
+    Sticker(u64),
+}
+
 /// Instruction definition
 #[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
 pub enum StickerInstruction {
@@ -51,7 +79,7 @@ pub enum StickerInstruction {
     ///   0. `[r]` User
     ///   1. `[r]` Account to receive payments
     ///   2. `[w]` Uninitialized artist account
-    ///   3. `[rs]` Sticker factory owner
+    ///   3. `[rs]` Sticker factory owner or curator
     ///   4. `[w]` Sticker factory
     ///   5. `[r]` Rent
     RegisterArtist(RegisterArtist),
@@ -61,7 +89,7 @@ pub enum StickerInstruction {
     ///   0. `[w]` Sticker account
     ///   1. `[w]` Sticker factory
     ///   2. `[r]` NFT mint. Created but not initialized account
-    ///   3. `[r]` Artist
+    ///   3. `[r]` Artist approved in sticker factory
     ///   4. `[rs]` Artist's user
     ///   5. `[r]` Mint authority. Program address
     ///   6. `[r]` NFT 721 token program id
@@ -77,19 +105,21 @@ pub enum StickerInstruction {
 
     /// BuySticker
     ///
-    ///   0. `[r]` Sticker to buy
+    ///   0. `[w]` Sticker to buy
     ///   1. `[r]` Artist account
-    ///   1. `[w]` Artist's token account to receive payments
-    ///   2. `[w]` Buyer's token account
-    ///   3. `[rs]` Buyer's transfer authority
-    ///   4. `[r]` NFT token mint authority
-    ///   4. `[w]` NFT token
-    ///   5. `[w]` NFT token data
-    ///   6. `[r]` NFT token mint
-    ///   7. `[r]` NFT token owner, user's account
-    ///   8. `[r]` Token program id
-    ///   9. `[r]` NFT 721 token program id
-    ///   10. `[r]` Rent
+    ///   2. `[w]` Artist's token account to receive payments
+    ///   3. `[w]` Buyer's token account
+    ///   4. `[rs]` Buyer's transfer authority
+    ///   5. `[r]` NFT token mint authority
+    ///   6. `[w]` NFT token
+    ///   7. `[w]` NFT token data
+    ///   8. `[r]` NFT token mint
+    ///   9. `[r]` NFT token owner, user's account
+    ///   10. `[r]` Token program id
+    ///   11. `[r]` NFT 721 token program id
+    ///   12. `[r]` Rent
+    ///   13. `[r]` Sticker factory
+    ///   14. `[w]` Treasury token account to receive fee, not used if factory has no fee
     BuySticker,
 
     /// ChangeStickerPrice
@@ -107,6 +137,210 @@ pub enum StickerInstruction {
     ///   4. `[r]` Rent
     ///   5. `[r]` System program
     CreateAccount(AddressType),
+
+    /// TransferFactoryOwnership
+    ///
+    ///   0. `[w]` Sticker factory
+    ///   1. `[rs]` Current owner
+    ///   2. `[rs]` New owner
+    TransferFactoryOwnership,
+
+    /// AddCurator
+    ///
+    ///   0. `[w]` Sticker factory
+    ///   1. `[rs]` Owner
+    ///   2. `[r]` Curator to add
+    AddCurator,
+
+    /// RemoveCurator
+    ///
+    ///   0. `[w]` Sticker factory
+    ///   1. `[rs]` Owner
+    ///   2. `[r]` Curator to remove
+    RemoveCurator,
+
+    /// ApplyAsArtist, creates pending artist paid by user
+    ///
+    ///   0. `[sw]` User
+    ///   1. `[r]` Account to receive payments
+    ///   2. `[w]` Artist account to create
+    ///   3. `[w]` Sticker factory
+    ///   4. `[r]` Base
+    ///   5. `[r]` Rent
+    ///   6. `[r]` System program
+    ApplyAsArtist(RegisterArtist),
+
+    /// ApproveArtist, argument is artist index
+    ///
+    ///   0. `[w]` Pending artist
+    ///   1. `[rs]` Sticker factory owner or curator
+    ///   2. `[r]` Sticker factory
+    ApproveArtist(u64),
+
+    /// RejectArtist, argument is artist index. Closes artist account and refunds rent to user
+    ///
+    ///   0. `[w]` Pending artist
+    ///   1. `[w]` Artist's user
+    ///   2. `[rs]` Sticker factory owner or curator
+    ///   3. `[r]` Sticker factory
+    RejectArtist(u64),
+
+    /// UpdateArtist
+    ///
+    ///   0. `[w]` Artist
+    ///   1. `[rs]` Artist's user
+    ///   2. `[r]` Account to receive payments, current or new one of same mint
+    UpdateArtist(UpdateArtist),
+
+    /// SetFactoryFee, argument is fee in basis points
+    ///
+    ///   0. `[w]` Sticker factory
+    ///   1. `[rs]` Owner
+    ///   2. `[r]` Treasury, owner of token accounts receiving fees
+    SetFactoryFee(u16),
+
+    /// ListSticker, argument is price. Approves listing authority to transfer NFT token,
+    /// which must not be approved to other account
+    ///
+    ///   0. `[w]` Listing, created but not initialized account
+    ///   1. `[r]` Sticker
+    ///   2. `[w]` NFT token
+    ///   3. `[rs]` NFT token owner, seller
+    ///   4. `[r]` Seller's token account to receive payments
+    ///   5. `[r]` Listing authority. Program address
+    ///   6. `[r]` NFT 721 token program id
+    ///   7. `[r]` Rent
+    ListSticker(u64),
+
+    /// CancelListing, approves NFT token back to seller, closes listing and returns its rent to seller
+    ///
+    ///   0. `[w]` Listing
+    ///   1. `[ws]` Seller
+    ///   2. `[w]` NFT token
+    ///   3. `[r]` Listing authority. Program address
+    ///   4. `[r]` NFT 721 token program id
+    CancelListing,
+
+    /// BuyListing, pays royalty to artist and rest to seller, closes listing
+    ///
+    ///   0. `[w]` Listing
+    ///   1. `[r]` Sticker
+    ///   2. `[r]` Artist account
+    ///   3. `[w]` Artist's token account to receive royalty
+    ///   4. `[w]` Seller, receives listing rent
+    ///   5. `[w]` Seller's token account to receive payments
+    ///   6. `[w]` Buyer's token account
+    ///   7. `[rs]` Buyer's transfer authority
+    ///   8. `[w]` NFT token
+    ///   9. `[r]` NFT token new owner, user's account
+    ///   10. `[r]` Listing authority. Program address
+    ///   11. `[r]` Token program id
+    ///   12. `[r]` NFT 721 token program id
+    BuyListing,
+
+    /// CreateStickerPack, argument is price
+    ///
+    ///   0. `[w]` Sticker pack, created but not initialized account
+    ///   1. `[rs]` Creator of all stickers
+    ///   2. `[r]` Rent
+    ///   3. ..3+N `[r]` Stickers, from 1 to `StickerPack::MAX_STICKERS`
+    CreateStickerPack(u64),
+
+    /// BuyStickerPack, mints one NFT token of each pack sticker
+    ///
+    ///   0. `[r]` Sticker pack to buy
+    ///   1. `[r]` Artist account
+    ///   2. `[w]` Artist's token account to receive payments
+    ///   3. `[w]` Buyer's token account
+    ///   4. `[rs]` Buyer's transfer authority
+    ///   5. `[r]` NFT token owner, user's account
+    ///   6. `[r]` Token program id
+    ///   7. `[r]` NFT 721 token program id
+    ///   8. `[r]` Rent
+    ///   9. `[r]` Sticker factory
+    ///   10. `[w]` Treasury token account to receive fee, not used if factory has no fee
+    ///   11. ..11+5*N For each pack sticker in pack order:
+    ///     `[w]` Sticker
+    ///     `[r]` NFT token mint authority
+    ///     `[w]` NFT token
+    ///     `[w]` NFT token data
+    ///     `[r]` NFT token mint
+    BuyStickerPack,
+
+    /// SetStickerSale, replaces sale schedule
+    ///
+    ///   0. `[w]` Sticker
+    ///   1. `[rs]` Creator
+    SetStickerSale(SaleSchedule),
+
+    /// PauseSticker
+    ///
+    ///   0. `[w]` Sticker
+    ///   1. `[rs]` Creator
+    PauseSticker,
+
+    /// ResumeSticker
+    ///
+    ///   0. `[w]` Sticker
+    ///   1. `[rs]` Creator
+    ResumeSticker,
+
+    /// RetireSticker, permanently stops sale and caps max supply at supply
+    ///
+    ///   0. `[w]` Sticker
+    ///   1. `[rs]` Creator
+    RetireSticker,
+
+    /// AirdropSticker, mints one NFT token to each owner without payment
+    ///
+    ///   0. `[w]` Sticker
+    ///   1. `[rs]` Creator
+    ///   2. `[r]` NFT token mint authority
+    ///   3. `[r]` NFT token mint
+    ///   4. `[r]` NFT 721 token program id
+    ///   5. `[r]` Rent
+    ///   6. ..6+3*N For each owner:
+    ///     `[r]` NFT token owner, user's account
+    ///     `[w]` NFT token
+    ///     `[w]` NFT token data
+    AirdropSticker,
+
+    /// MigrateAccount, grows account created with `LEN_V1` to current layout.
+    /// Artist becomes approved in given sticker factory and paid with wrapped SOL,
+    /// sticker is paid with wrapped SOL and belongs to given sticker factory
+    ///
+    ///   0. `[sw]` Payer of rent for grown account
+    ///   1. `[w]` Account to migrate
+    ///   2. `[r]` Sticker factory of artist or sticker, not used for sticker factory
+    ///   3. `[r]` Rent
+    ///   4. `[r]` System program
+    MigrateAccount(MigrateAccountInput),
+}
+
+/// Accounts to mint NFT token of one sticker in `BuyStickerPack`
+#[derive(PartialEq, Debug, Clone)]
+pub struct PackStickerAccounts {
+    /// Sticker
+    pub sticker: Pubkey,
+    /// NFT token mint authority
+    pub mint_authority: Pubkey,
+    /// NFT token
+    pub nft_token: Pubkey,
+    /// NFT token data
+    pub nft_token_data: Pubkey,
+    /// NFT token mint
+    pub nft_token_mint: Pubkey,
+}
+
+/// Accounts to mint NFT token to one owner in `AirdropSticker`
+#[derive(PartialEq, Debug, Clone)]
+pub struct AirdropAccounts {
+    /// NFT token owner
+    pub owner: Pubkey,
+    /// NFT token
+    pub nft_token: Pubkey,
+    /// NFT token data
+    pub nft_token_data: Pubkey,
 }
 
 /// Create `CreateAccount` instruction
@@ -229,6 +463,8 @@ pub fn buy_sticker(
     nft_token_data: &Pubkey,
     nft_token_mint: &Pubkey,
     nft_token_owner: &Pubkey,
+    sticker_factory: &Pubkey,
+    treasury_token_acc: &Pubkey,
 ) -> Result<Instruction, ProgramError> {
     let init_data = StickerInstruction::BuySticker;
     let data = init_data.try_to_vec()?;
@@ -246,6 +482,8 @@ pub fn buy_sticker(
         AccountMeta::new_readonly(spl_token::id(), false),
         AccountMeta::new_readonly(spl_nft_erc_721::id(), false),
         AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new_readonly(*sticker_factory, false),
+        AccountMeta::new(*treasury_token_acc, false),
     ];
     Ok(Instruction {
         program_id: *program_id,
@@ -273,3 +511,481 @@ pub fn change_sticker_price(
         data,
     })
 }
+
+/// Create `TransferFactoryOwnership` instruction
+pub fn transfer_factory_ownership(
+    program_id: &Pubkey,
+    sticker_factory: &Pubkey,
+    owner: &Pubkey,
+    new_owner: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let init_data = StickerInstruction::TransferFactoryOwnership;
+    let data = init_data.try_to_vec()?;
+    let accounts = vec![
+        AccountMeta::new(*sticker_factory, false),
+        AccountMeta::new_readonly(*owner, true),
+        AccountMeta::new_readonly(*new_owner, true),
+    ];
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Create `AddCurator` instruction
+pub fn add_curator(
+    program_id: &Pubkey,
+    sticker_factory: &Pubkey,
+    owner: &Pubkey,
+    curator: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let init_data = StickerInstruction::AddCurator;
+    let data = init_data.try_to_vec()?;
+    let accounts = vec![
+        AccountMeta::new(*sticker_factory, false),
+        AccountMeta::new_readonly(*owner, true),
+        AccountMeta::new_readonly(*curator, false),
+    ];
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Create `RemoveCurator` instruction
+pub fn remove_curator(
+    program_id: &Pubkey,
+    sticker_factory: &Pubkey,
+    owner: &Pubkey,
+    curator: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let init_data = StickerInstruction::RemoveCurator;
+    let data = init_data.try_to_vec()?;
+    let accounts = vec![
+        AccountMeta::new(*sticker_factory, false),
+        AccountMeta::new_readonly(*owner, true),
+        AccountMeta::new_readonly(*curator, false),
+    ];
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Create `ApplyAsArtist` instruction
+pub fn apply_as_artist(
+    program_id: &Pubkey,
+    user: &Pubkey,
+    user_token: &Pubkey,
+    artist_to_create: &Pubkey,
+    sticker_factory: &Pubkey,
+    base_address: &Pubkey,
+    args: RegisterArtist,
+) -> Result<Instruction, ProgramError> {
+    let init_data = StickerInstruction::ApplyAsArtist(args);
+    let data = init_data.try_to_vec()?;
+    let accounts = vec![
+        AccountMeta::new(*user, true),
+        AccountMeta::new_readonly(*user_token, false),
+        AccountMeta::new(*artist_to_create, false),
+        AccountMeta::new(*sticker_factory, false),
+        AccountMeta::new_readonly(*base_address, false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Create `ApproveArtist` instruction
+pub fn approve_artist(
+    program_id: &Pubkey,
+    artist: &Pubkey,
+    curator: &Pubkey,
+    sticker_factory: &Pubkey,
+    index: u64,
+) -> Result<Instruction, ProgramError> {
+    let init_data = StickerInstruction::ApproveArtist(index);
+    let data = init_data.try_to_vec()?;
+    let accounts = vec![
+        AccountMeta::new(*artist, false),
+        AccountMeta::new_readonly(*curator, true),
+        AccountMeta::new_readonly(*sticker_factory, false),
+    ];
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Create `RejectArtist` instruction
+pub fn reject_artist(
+    program_id: &Pubkey,
+    artist: &Pubkey,
+    user: &Pubkey,
+    curator: &Pubkey,
+    sticker_factory: &Pubkey,
+    index: u64,
+) -> Result<Instruction, ProgramError> {
+    let init_data = StickerInstruction::RejectArtist(index);
+    let data = init_data.try_to_vec()?;
+    let accounts = vec![
+        AccountMeta::new(*artist, false),
+        AccountMeta::new(*user, false),
+        AccountMeta::new_readonly(*curator, true),
+        AccountMeta::new_readonly(*sticker_factory, false),
+    ];
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Create `UpdateArtist` instruction
+pub fn update_artist(
+    program_id: &Pubkey,
+    artist: &Pubkey,
+    user: &Pubkey,
+    user_token: &Pubkey,
+    args: UpdateArtist,
+) -> Result<Instruction, ProgramError> {
+    let init_data = StickerInstruction::UpdateArtist(args);
+    let data = init_data.try_to_vec()?;
+    let accounts = vec![
+        AccountMeta::new(*artist, false),
+        AccountMeta::new_readonly(*user, true),
+        AccountMeta::new_readonly(*user_token, false),
+    ];
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Create `SetFactoryFee` instruction
+pub fn set_factory_fee(
+    program_id: &Pubkey,
+    sticker_factory: &Pubkey,
+    owner: &Pubkey,
+    treasury: &Pubkey,
+    fee_bps: u16,
+) -> Result<Instruction, ProgramError> {
+    let init_data = StickerInstruction::SetFactoryFee(fee_bps);
+    let data = init_data.try_to_vec()?;
+    let accounts = vec![
+        AccountMeta::new(*sticker_factory, false),
+        AccountMeta::new_readonly(*owner, true),
+        AccountMeta::new_readonly(*treasury, false),
+    ];
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Create `ListSticker` instruction
+#[allow(clippy::too_many_arguments)]
+pub fn list_sticker(
+    program_id: &Pubkey,
+    listing: &Pubkey,
+    sticker: &Pubkey,
+    nft_token: &Pubkey,
+    seller: &Pubkey,
+    seller_token_acc: &Pubkey,
+    listing_authority: &Pubkey,
+    price: u64,
+) -> Result<Instruction, ProgramError> {
+    let init_data = StickerInstruction::ListSticker(price);
+    let data = init_data.try_to_vec()?;
+    let accounts = vec![
+        AccountMeta::new(*listing, false),
+        AccountMeta::new_readonly(*sticker, false),
+        AccountMeta::new(*nft_token, false),
+        AccountMeta::new_readonly(*seller, true),
+        AccountMeta::new_readonly(*seller_token_acc, false),
+        AccountMeta::new_readonly(*listing_authority, false),
+        AccountMeta::new_readonly(spl_nft_erc_721::id(), false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+    ];
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Create `CancelListing` instruction
+pub fn cancel_listing(
+    program_id: &Pubkey,
+    listing: &Pubkey,
+    seller: &Pubkey,
+    nft_token: &Pubkey,
+    listing_authority: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let init_data = StickerInstruction::CancelListing;
+    let data = init_data.try_to_vec()?;
+    let accounts = vec![
+        AccountMeta::new(*listing, false),
+        AccountMeta::new(*seller, true),
+        AccountMeta::new(*nft_token, false),
+        AccountMeta::new_readonly(*listing_authority, false),
+        AccountMeta::new_readonly(spl_nft_erc_721::id(), false),
+    ];
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Create `BuyListing` instruction
+#[allow(clippy::too_many_arguments)]
+pub fn buy_listing(
+    program_id: &Pubkey,
+    listing: &Pubkey,
+    sticker: &Pubkey,
+    artist_account: &Pubkey,
+    artist_token_acc: &Pubkey,
+    seller: &Pubkey,
+    seller_token_acc: &Pubkey,
+    buyer_token_acc: &Pubkey,
+    buyer_transfer_authority: &Pubkey,
+    nft_token: &Pubkey,
+    nft_token_owner: &Pubkey,
+    listing_authority: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let init_data = StickerInstruction::BuyListing;
+    let data = init_data.try_to_vec()?;
+    let accounts = vec![
+        AccountMeta::new(*listing, false),
+        AccountMeta::new_readonly(*sticker, false),
+        AccountMeta::new_readonly(*artist_account, false),
+        AccountMeta::new(*artist_token_acc, false),
+        AccountMeta::new(*seller, false),
+        AccountMeta::new(*seller_token_acc, false),
+        AccountMeta::new(*buyer_token_acc, false),
+        AccountMeta::new_readonly(*buyer_transfer_authority, true),
+        AccountMeta::new(*nft_token, false),
+        AccountMeta::new_readonly(*nft_token_owner, false),
+        AccountMeta::new_readonly(*listing_authority, false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(spl_nft_erc_721::id(), false),
+    ];
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Create `CreateStickerPack` instruction
+pub fn create_sticker_pack(
+    program_id: &Pubkey,
+    sticker_pack: &Pubkey,
+    creator: &Pubkey,
+    stickers: &[Pubkey],
+    price: u64,
+) -> Result<Instruction, ProgramError> {
+    let init_data = StickerInstruction::CreateStickerPack(price);
+    let data = init_data.try_to_vec()?;
+    let mut accounts = vec![
+        AccountMeta::new(*sticker_pack, false),
+        AccountMeta::new_readonly(*creator, true),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+    ];
+    accounts.extend(
+        stickers
+            .iter()
+            .map(|sticker| AccountMeta::new_readonly(*sticker, false)),
+    );
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Create `BuyStickerPack` instruction
+#[allow(clippy::too_many_arguments)]
+pub fn buy_sticker_pack(
+    program_id: &Pubkey,
+    sticker_pack: &Pubkey,
+    artist_account: &Pubkey,
+    artist_token_acc: &Pubkey,
+    buyer_token_acc: &Pubkey,
+    buyer_transfer_authority: &Pubkey,
+    nft_token_owner: &Pubkey,
+    sticker_factory: &Pubkey,
+    treasury_token_acc: &Pubkey,
+    stickers: &[PackStickerAccounts],
+) -> Result<Instruction, ProgramError> {
+    let init_data = StickerInstruction::BuyStickerPack;
+    let data = init_data.try_to_vec()?;
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*sticker_pack, false),
+        AccountMeta::new_readonly(*artist_account, false),
+        AccountMeta::new(*artist_token_acc, false),
+        AccountMeta::new(*buyer_token_acc, false),
+        AccountMeta::new_readonly(*buyer_transfer_authority, true),
+        AccountMeta::new_readonly(*nft_token_owner, false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(spl_nft_erc_721::id(), false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new_readonly(*sticker_factory, false),
+        AccountMeta::new(*treasury_token_acc, false),
+    ];
+    for sticker in stickers {
+        accounts.push(AccountMeta::new(sticker.sticker, false));
+        accounts.push(AccountMeta::new_readonly(sticker.mint_authority, false));
+        accounts.push(AccountMeta::new(sticker.nft_token, false));
+        accounts.push(AccountMeta::new(sticker.nft_token_data, false));
+        accounts.push(AccountMeta::new_readonly(sticker.nft_token_mint, false));
+    }
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Create `SetStickerSale` instruction
+pub fn set_sticker_sale(
+    program_id: &Pubkey,
+    sticker: &Pubkey,
+    creator: &Pubkey,
+    sale: SaleSchedule,
+) -> Result<Instruction, ProgramError> {
+    let init_data = StickerInstruction::SetStickerSale(sale);
+    let data = init_data.try_to_vec()?;
+    let accounts = vec![
+        AccountMeta::new(*sticker, false),
+        AccountMeta::new_readonly(*creator, true),
+    ];
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Create `PauseSticker` instruction
+pub fn pause_sticker(
+    program_id: &Pubkey,
+    sticker: &Pubkey,
+    creator: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let init_data = StickerInstruction::PauseSticker;
+    let data = init_data.try_to_vec()?;
+    let accounts = vec![
+        AccountMeta::new(*sticker, false),
+        AccountMeta::new_readonly(*creator, true),
+    ];
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Create `ResumeSticker` instruction
+pub fn resume_sticker(
+    program_id: &Pubkey,
+    sticker: &Pubkey,
+    creator: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let init_data = StickerInstruction::ResumeSticker;
+    let data = init_data.try_to_vec()?;
+    let accounts = vec![
+        AccountMeta::new(*sticker, false),
+        AccountMeta::new_readonly(*creator, true),
+    ];
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Create `RetireSticker` instruction
+pub fn retire_sticker(
+    program_id: &Pubkey,
+    sticker: &Pubkey,
+    creator: &Pubkey,
+) -> Result<Instruction, ProgramError> {
+    let init_data = StickerInstruction::RetireSticker;
+    let data = init_data.try_to_vec()?;
+    let accounts = vec![
+        AccountMeta::new(*sticker, false),
+        AccountMeta::new_readonly(*creator, true),
+    ];
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Create `AirdropSticker` instruction
+pub fn airdrop_sticker(
+    program_id: &Pubkey,
+    sticker: &Pubkey,
+    creator: &Pubkey,
+    mint_authority: &Pubkey,
+    nft_token_mint: &Pubkey,
+    recipients: &[AirdropAccounts],
+) -> Result<Instruction, ProgramError> {
+    let init_data = StickerInstruction::AirdropSticker;
+    let data = init_data.try_to_vec()?;
+    let mut accounts = vec![
+        AccountMeta::new(*sticker, false),
+        AccountMeta::new_readonly(*creator, true),
+        AccountMeta::new_readonly(*mint_authority, false),
+        AccountMeta::new_readonly(*nft_token_mint, false),
+        AccountMeta::new_readonly(spl_nft_erc_721::id(), false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+    ];
+    for recipient in recipients {
+        accounts.push(AccountMeta::new_readonly(recipient.owner, false));
+        accounts.push(AccountMeta::new(recipient.nft_token, false));
+        accounts.push(AccountMeta::new(recipient.nft_token_data, false));
+    }
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
+
+/// Create `MigrateAccount` instruction
+pub fn migrate_account(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    account: &Pubkey,
+    sticker_factory: &Pubkey,
+    input: MigrateAccountInput,
+) -> Result<Instruction, ProgramError> {
+    let init_data = StickerInstruction::MigrateAccount(input);
+    let data = init_data.try_to_vec()?;
+    let accounts = vec![
+        AccountMeta::new(*payer, true),
+        AccountMeta::new(*account, false),
+        AccountMeta::new_readonly(*sticker_factory, false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+    Ok(Instruction {
+        program_id: *program_id,
+        accounts,
+        data,
+    })
+}
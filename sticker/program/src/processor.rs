@@ -2,13 +2,20 @@
 
 use crate::{
     error::StickerProgramError,
-    instruction::{AddressType, CreateNewSticker, RegisterArtist, StickerInstruction},
-    state::{Artist, Sticker, StickerFactory},
+    instruction::{
+        AddressType, CreateNewSticker, MigrateAccountInput, RegisterArtist, StickerInstruction,
+        UpdateArtist,
+    },
+    state::{
+        Artist, ArtistStatus, Listing, SaleSchedule, Sticker, StickerFactory, StickerPack,
+        StickerStatus,
+    },
 };
 use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::{
     account_info::next_account_info,
     account_info::AccountInfo,
+    clock::Clock,
     entrypoint::ProgramResult,
     msg,
     program::{invoke, invoke_signed},
@@ -19,7 +26,7 @@ use solana_program::{
     sysvar::rent::Rent,
     sysvar::Sysvar,
 };
-use spl_nft_erc_721::state::Mint;
+use spl_nft_erc_721::state::{Mint, Token as NftToken};
 use spl_token::state::Account;
 
 /// Program state handler.
@@ -57,6 +64,51 @@ impl Processor {
         )
     }
 
+    /// Pay price to artist and factory fee to treasury
+    #[allow(clippy::too_many_arguments)]
+    fn pay_with_fee<'a>(
+        token_program_id: AccountInfo<'a>,
+        source_account: AccountInfo<'a>,
+        artist_token_account: AccountInfo<'a>,
+        treasury_token_account: AccountInfo<'a>,
+        user_authority_account: AccountInfo<'a>,
+        sticker_factory: &StickerFactory,
+        payment_mint: &Pubkey,
+        price: u64,
+    ) -> ProgramResult {
+        let fee = sticker_factory
+            .fee(price)
+            .ok_or(StickerProgramError::CalculationError)?;
+        let artist_amount = price
+            .checked_sub(fee)
+            .ok_or(StickerProgramError::CalculationError)?;
+
+        if fee > 0 {
+            let treasury_token = Account::unpack(&treasury_token_account.data.borrow())?;
+            if treasury_token.owner != sticker_factory.treasury
+                || treasury_token.mint != *payment_mint
+            {
+                return Err(StickerProgramError::WrongTreasury.into());
+            }
+
+            Self::transfer(
+                token_program_id.clone(),
+                source_account.clone(),
+                treasury_token_account,
+                user_authority_account.clone(),
+                fee,
+            )?;
+        }
+
+        Self::transfer(
+            token_program_id,
+            source_account,
+            artist_token_account,
+            user_authority_account,
+            artist_amount,
+        )
+    }
+
     /// Initialize sticker mint
     fn initialize_nft_mint<'a>(
         nft_program_id: AccountInfo<'a>,
@@ -119,6 +171,125 @@ impl Processor {
         )
     }
 
+    /// Mint one NFT token of sticker and count it in sticker supply
+    #[allow(clippy::too_many_arguments)]
+    fn mint_sticker<'a>(
+        program_id: &Pubkey,
+        nft_program_id: AccountInfo<'a>,
+        sticker_account_info: &AccountInfo<'a>,
+        sticker: &mut Sticker,
+        mint_authority: AccountInfo<'a>,
+        token_account: AccountInfo<'a>,
+        token_data_account: AccountInfo<'a>,
+        mint_account: AccountInfo<'a>,
+        owner_account: AccountInfo<'a>,
+    ) -> ProgramResult {
+        let (generated_mint_auth, bump_seed) =
+            Pubkey::find_program_address(&[&sticker_account_info.key.to_bytes()[..32]], program_id);
+        if generated_mint_auth != *mint_authority.key {
+            return Err(StickerProgramError::WrongTokenMintAuthority.into());
+        }
+
+        if sticker.supply >= sticker.max_supply {
+            return Err(StickerProgramError::NoTokensToMint.into());
+        }
+
+        Self::create_new_sticker(
+            nft_program_id,
+            token_account,
+            token_data_account,
+            mint_account,
+            owner_account,
+            mint_authority,
+            sticker_account_info.key,
+            bump_seed,
+            *sticker_account_info.key,
+            sticker.uri,
+        )?;
+
+        sticker.supply = sticker
+            .supply
+            .checked_add(1)
+            .ok_or(StickerProgramError::CalculationError)?;
+
+        Ok(())
+    }
+
+    /// Approve NFT token transfer
+    fn approve_nft<'a>(
+        nft_program_id: AccountInfo<'a>,
+        token_account: AccountInfo<'a>,
+        approval: AccountInfo<'a>,
+        owner: AccountInfo<'a>,
+    ) -> ProgramResult {
+        invoke(
+            &spl_nft_erc_721::instruction::NftInstruction::approve(
+                *token_account.key,
+                *approval.key,
+                *owner.key,
+            ),
+            &[token_account, approval, owner, nft_program_id],
+        )
+    }
+
+    /// Approve NFT token transfer back to its owner by listing authority
+    fn approve_nft_to_owner<'a>(
+        nft_program_id: AccountInfo<'a>,
+        token_account: AccountInfo<'a>,
+        owner: AccountInfo<'a>,
+        listing_authority: AccountInfo<'a>,
+        listing_key: &Pubkey,
+        bump_seed: u8,
+    ) -> ProgramResult {
+        let authority_signature_seeds = [&listing_key.to_bytes()[..32], &[bump_seed]];
+        let signers = &[&authority_signature_seeds[..]];
+        invoke_signed(
+            &spl_nft_erc_721::instruction::NftInstruction::approve(
+                *token_account.key,
+                *owner.key,
+                *listing_authority.key,
+            ),
+            &[token_account, owner, listing_authority, nft_program_id],
+            signers,
+        )
+    }
+
+    /// Transfer NFT token approved to listing authority
+    fn transfer_nft<'a>(
+        nft_program_id: AccountInfo<'a>,
+        token_account: AccountInfo<'a>,
+        new_owner: AccountInfo<'a>,
+        listing_authority: AccountInfo<'a>,
+        listing_key: &Pubkey,
+        bump_seed: u8,
+    ) -> ProgramResult {
+        let authority_signature_seeds = [&listing_key.to_bytes()[..32], &[bump_seed]];
+        let signers = &[&authority_signature_seeds[..]];
+        invoke_signed(
+            &spl_nft_erc_721::instruction::NftInstruction::transfer(
+                *token_account.key,
+                *new_owner.key,
+                *listing_authority.key,
+            ),
+            &[token_account, new_owner, listing_authority, nft_program_id],
+            signers,
+        )
+    }
+
+    /// Close program account moving its lamports to destination
+    fn close_account(account: &AccountInfo, destination: &AccountInfo) -> ProgramResult {
+        let destination_lamports = destination
+            .lamports()
+            .checked_add(account.lamports())
+            .ok_or(StickerProgramError::CalculationError)?;
+        **destination.lamports.borrow_mut() = destination_lamports;
+        **account.lamports.borrow_mut() = 0;
+
+        account.data.borrow_mut().fill(0);
+
+        Ok(())
+    }
+
     #[allow(clippy::too_many_arguments)]
     fn create_account<'a>(
         program_id: &Pubkey,
@@ -162,6 +333,117 @@ impl Processor {
         )
     }
 
+    /// Read account to receive payments, its mint is artist's payment mint
+    fn check_user_token(user_token_account_info: &AccountInfo) -> Result<Account, ProgramError> {
+        if *user_token_account_info.owner != spl_token::id() {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        Account::unpack(&user_token_account_info.data.borrow())
+    }
+
+    /// Read initialized sticker factory owned by program
+    fn sticker_factory(
+        program_id: &Pubkey,
+        sticker_factory_account_info: &AccountInfo,
+    ) -> Result<StickerFactory, ProgramError> {
+        if sticker_factory_account_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let sticker_factory =
+            StickerFactory::try_from_slice(&sticker_factory_account_info.data.borrow())?;
+        if !sticker_factory.is_initialized() {
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        Ok(sticker_factory)
+    }
+
+    /// Read initialized sticker factory and check its owner signed
+    fn sticker_factory_by_owner(
+        sticker_factory_account_info: &AccountInfo,
+        owner_account_info: &AccountInfo,
+    ) -> Result<StickerFactory, ProgramError> {
+        let sticker_factory =
+            StickerFactory::try_from_slice(&sticker_factory_account_info.data.borrow())?;
+        if !sticker_factory.is_initialized() {
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        if *owner_account_info.key != sticker_factory.owner {
+            return Err(StickerProgramError::WrongStickerFactoryOwner.into());
+        }
+
+        if !owner_account_info.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        Ok(sticker_factory)
+    }
+
+    /// Check key is derived from sticker factory for entity with given seed and index
+    fn check_factory_index_key(
+        program_id: &Pubkey,
+        sticker_factory: &Pubkey,
+        seed: &str,
+        index: u64,
+        key: &Pubkey,
+    ) -> ProgramResult {
+        let (base, _) =
+            Pubkey::find_program_address(&[&sticker_factory.to_bytes()[..32]], program_id);
+        let generated_key =
+            Pubkey::create_with_seed(&base, &format!("{:?}{}", index, seed), program_id)?;
+        if generated_key != *key {
+            return Err(ProgramError::InvalidSeeds);
+        }
+
+        Ok(())
+    }
+
+    /// Read pending artist with given index and check owner or curator of its factory signed
+    fn pending_artist_by_curator(
+        program_id: &Pubkey,
+        artist_account_info: &AccountInfo,
+        curator_account_info: &AccountInfo,
+        sticker_factory_account_info: &AccountInfo,
+        index: u64,
+    ) -> Result<Artist, ProgramError> {
+        let sticker_factory = Self::sticker_factory(program_id, sticker_factory_account_info)?;
+
+        if !sticker_factory.can_register_artist(curator_account_info.key) {
+            return Err(StickerProgramError::WrongStickerFactoryOwner.into());
+        }
+
+        if !curator_account_info.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let (base, _) = Pubkey::find_program_address(
+            &[&sticker_factory_account_info.key.to_bytes()[..32]],
+            program_id,
+        );
+        let generated_artist_key = Pubkey::create_with_seed(
+            &base,
+            &format!("{:?}{}", index, Self::ARTIST_SEED),
+            program_id,
+        )?;
+        if generated_artist_key != *artist_account_info.key {
+            return Err(ProgramError::InvalidSeeds);
+        }
+
+        let artist = Artist::try_from_slice(&artist_account_info.data.borrow())?;
+        if !artist.is_initialized() {
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        if artist.status != ArtistStatus::Pending {
+            return Err(StickerProgramError::ArtistNotPending.into());
+        }
+
+        Ok(artist)
+    }
+
     /// Register new artist
     pub fn process_register_artist_instruction(
         program_id: &Pubkey,
@@ -177,10 +459,7 @@ impl Processor {
         let rent_account_info = next_account_info(account_info_iter)?;
         let rent = &Rent::from_account_info(rent_account_info)?;
 
-        let user_token = Account::unpack_from_slice(&user_token_account_info.data.borrow())?;
-        if !user_token.is_native() {
-            return Err(StickerProgramError::WrongTokenMint.into());
-        }
+        let user_token = Self::check_user_token(user_token_account_info)?;
 
         let mut sticker_factory =
             StickerFactory::try_from_slice(&sticker_factory_account_info.data.borrow())?;
@@ -188,7 +467,7 @@ impl Processor {
             return Err(ProgramError::UninitializedAccount);
         }
 
-        if *sticker_factory_owner_account_info.key != sticker_factory.owner {
+        if !sticker_factory.can_register_artist(sticker_factory_owner_account_info.key) {
             return Err(StickerProgramError::WrongStickerFactoryOwner.into());
         }
 
@@ -225,6 +504,9 @@ impl Processor {
         artist.name = args.name;
         artist.signature = args.signature;
         artist.description = args.description;
+        artist.status = ArtistStatus::Approved;
+        artist.payment_mint = user_token.mint;
+        artist.sticker_factory = *sticker_factory_account_info.key;
 
         sticker_factory.artist_count = sticker_factory
             .artist_count
@@ -258,11 +540,7 @@ impl Processor {
             return Err(ProgramError::IncorrectProgramId);
         }
 
-        let mut sticker_factory =
-            StickerFactory::try_from_slice(&sticker_factory_account_info.data.borrow())?;
-        if !sticker_factory.is_initialized() {
-            return Err(ProgramError::UninitializedAccount);
-        }
+        let mut sticker_factory = Self::sticker_factory(program_id, sticker_factory_account_info)?;
 
         let (base, _) = Pubkey::find_program_address(
             &[&sticker_factory_account_info.key.to_bytes()[..32]],
@@ -302,15 +580,31 @@ impl Processor {
             bump_seed,
         )?;
 
+        if artist_account_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
         let artist = Artist::try_from_slice(&artist_account_info.data.borrow())?;
         if !artist.is_initialized() {
             return Err(ProgramError::UninitializedAccount);
         }
 
+        // approval is given by owner or curators of one factory only
+        if artist.sticker_factory != *sticker_factory_account_info.key {
+            return Err(StickerProgramError::WrongStickerFactory.into());
+        }
+
+        if artist.status != ArtistStatus::Approved {
+            return Err(StickerProgramError::ArtistNotApproved.into());
+        }
+
         if *user_account_info.key != artist.user || !user_account_info.is_signer {
             return Err(ProgramError::MissingRequiredSignature);
         }
 
+        if args.payment_mint != artist.payment_mint {
+            return Err(StickerProgramError::WrongTokenMint.into());
+        }
+
         if !rent.is_exempt(
             sticker_account_info.lamports(),
             sticker_account_info.data_len(),
@@ -323,12 +617,19 @@ impl Processor {
             return Err(ProgramError::AccountAlreadyInitialized);
         }
 
+        if args.royalty_bps > Sticker::MAX_ROYALTY_BPS {
+            return Err(StickerProgramError::InvalidRoyalty.into());
+        }
+
         sticker.creator = artist.user;
         sticker.supply = 0;
         sticker.max_supply = args.max_supply;
         sticker.price = args.price;
         sticker.mint = *mint_account_info.key;
         sticker.uri = args.uri;
+        sticker.payment_mint = args.payment_mint;
+        sticker.royalty_bps = args.royalty_bps;
+        sticker.sticker_factory = *sticker_factory_account_info.key;
 
         sticker_factory.sticker_count = sticker_factory
             .sticker_count
@@ -398,6 +699,8 @@ impl Processor {
         let nft_token_program_id = next_account_info(account_info_iter)?;
         // Need in Rent account because we call NFT program instruction which uses it
         let _rent_account_info = next_account_info(account_info_iter)?;
+        let sticker_factory_account_info = next_account_info(account_info_iter)?;
+        let treasury_token_account_info = next_account_info(account_info_iter)?;
 
         if *token_program_id.key != spl_token::id()
             || *nft_token_program_id.key != spl_nft_erc_721::id()
@@ -410,6 +713,14 @@ impl Processor {
             return Err(ProgramError::UninitializedAccount);
         }
 
+        let sticker_factory = Self::sticker_factory(program_id, sticker_factory_account_info)?;
+        if sticker.sticker_factory != *sticker_factory_account_info.key {
+            return Err(StickerProgramError::WrongStickerFactory.into());
+        }
+
+        if artist_account_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
         let artist = Artist::try_from_slice(&artist_account_info.data.borrow())?;
         if !artist.is_initialized() {
             return Err(ProgramError::UninitializedAccount);
@@ -420,44 +731,37 @@ impl Processor {
             return Err(StickerProgramError::WrongStickerCreator.into());
         }
 
-        let (generated_mint_auth, bump_seed) = Pubkey::find_program_address(
-            &[&sticker_to_buy_account_info.key.to_bytes()[..32]],
-            program_id,
-        );
-        if generated_mint_auth != *mint_authority.key {
-            return Err(StickerProgramError::WrongTokenMintAuthority.into());
+        let buyer_token = Account::unpack(&buyer_token_account_info.data.borrow())?;
+        let artist_token = Account::unpack(&artist_token_account_info.data.borrow())?;
+        if buyer_token.mint != sticker.payment_mint || artist_token.mint != sticker.payment_mint {
+            return Err(StickerProgramError::WrongPaymentMint.into());
         }
 
-        if sticker.supply == sticker.max_supply {
-            return Err(StickerProgramError::NoTokensToMint.into());
-        }
+        let price = sticker.price_at(Clock::get()?.unix_timestamp)?;
 
-        Self::transfer(
+        Self::pay_with_fee(
             token_program_id.clone(),
             buyer_token_account_info.clone(),
             artist_token_account_info.clone(),
+            treasury_token_account_info.clone(),
             buyer_transfer_authority_account_info.clone(),
-            sticker.price,
+            &sticker_factory,
+            &sticker.payment_mint,
+            price,
         )?;
 
-        Self::create_new_sticker(
+        Self::mint_sticker(
+            program_id,
             nft_token_program_id.clone(),
+            sticker_to_buy_account_info,
+            &mut sticker,
+            mint_authority.clone(),
             nft_token_account_info.clone(),
             nft_token_data_account_info.clone(),
             nft_token_mint_account_info.clone(),
             nft_token_owner_account_info.clone(),
-            mint_authority.clone(),
-            sticker_to_buy_account_info.key,
-            bump_seed,
-            *sticker_to_buy_account_info.key,
-            sticker.uri,
         )?;
 
-        sticker.supply = sticker
-            .supply
-            .checked_add(1)
-            .ok_or_else(|| StickerProgramError::CalculationError)?;
-
         sticker
             .serialize(&mut *sticker_to_buy_account_info.data.borrow_mut())
             .map_err(|e| e.into())
@@ -545,31 +849,951 @@ impl Processor {
         Ok(())
     }
 
-    /// Processes an instruction
-    pub fn process_instruction(
-        program_id: &Pubkey,
+    /// Transfer sticker factory ownership
+    pub fn process_transfer_factory_ownership_instruction(
+        _program_id: &Pubkey,
         accounts: &[AccountInfo],
-        input: &[u8],
     ) -> ProgramResult {
-        let instruction = StickerInstruction::try_from_slice(input)?;
-        match instruction {
-            StickerInstruction::RegisterArtist(args) => {
-                msg!("Instruction: RegisterArtist");
-                Self::process_register_artist_instruction(program_id, accounts, args)
-            }
-            StickerInstruction::CreateNewSticker(args) => {
-                msg!("Instruction: CreateNewSticker");
-                Self::process_create_new_sticker_instruction(program_id, accounts, args)
-            }
-            StickerInstruction::BuySticker => {
-                msg!("Instruction: BuySticker");
-                Self::process_buy_sticker_instruction(program_id, accounts)
-            }
-            StickerInstruction::ChangeStickerPrice(new_price) => {
-                msg!("Instruction: ChangeStickerPrice");
-                Self::process_change_sticker_price_instruction(program_id, accounts, new_price)
+        let account_info_iter = &mut accounts.iter();
+        let sticker_factory_account_info = next_account_info(account_info_iter)?;
+        let owner_account_info = next_account_info(account_info_iter)?;
+        let new_owner_account_info = next_account_info(account_info_iter)?;
+
+        let mut sticker_factory =
+            Self::sticker_factory_by_owner(sticker_factory_account_info, owner_account_info)?;
+
+        if !new_owner_account_info.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        sticker_factory.owner = *new_owner_account_info.key;
+
+        sticker_factory
+            .serialize(&mut *sticker_factory_account_info.data.borrow_mut())
+            .map_err(|e| e.into())
+    }
+
+    /// Add sticker factory curator
+    pub fn process_add_curator_instruction(
+        _program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let sticker_factory_account_info = next_account_info(account_info_iter)?;
+        let owner_account_info = next_account_info(account_info_iter)?;
+        let curator_account_info = next_account_info(account_info_iter)?;
+
+        let mut sticker_factory =
+            Self::sticker_factory_by_owner(sticker_factory_account_info, owner_account_info)?;
+
+        if *curator_account_info.key == Pubkey::default() {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        if sticker_factory.is_curator(curator_account_info.key) {
+            return Err(StickerProgramError::CuratorAlreadyAdded.into());
+        }
+
+        let free_slot = sticker_factory
+            .curators
+            .iter_mut()
+            .find(|curator| **curator == Pubkey::default())
+            .ok_or(StickerProgramError::TooManyCurators)?;
+        *free_slot = *curator_account_info.key;
+
+        sticker_factory
+            .serialize(&mut *sticker_factory_account_info.data.borrow_mut())
+            .map_err(|e| e.into())
+    }
+
+    /// Remove sticker factory curator
+    pub fn process_remove_curator_instruction(
+        _program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let sticker_factory_account_info = next_account_info(account_info_iter)?;
+        let owner_account_info = next_account_info(account_info_iter)?;
+        let curator_account_info = next_account_info(account_info_iter)?;
+
+        let mut sticker_factory =
+            Self::sticker_factory_by_owner(sticker_factory_account_info, owner_account_info)?;
+
+        if !sticker_factory.is_curator(curator_account_info.key) {
+            return Err(StickerProgramError::CuratorNotFound.into());
+        }
+
+        for curator in sticker_factory.curators.iter_mut() {
+            if *curator == *curator_account_info.key {
+                *curator = Pubkey::default();
             }
-            StickerInstruction::CreateAccount(account_type) => {
+        }
+
+        sticker_factory
+            .serialize(&mut *sticker_factory_account_info.data.borrow_mut())
+            .map_err(|e| e.into())
+    }
+
+    /// Create pending artist
+    pub fn process_apply_as_artist_instruction(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        args: RegisterArtist,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let user_account_info = next_account_info(account_info_iter)?;
+        let user_token_account_info = next_account_info(account_info_iter)?;
+        let artist_to_create_account_info = next_account_info(account_info_iter)?;
+        let sticker_factory_account_info = next_account_info(account_info_iter)?;
+        let base_address_account_info = next_account_info(account_info_iter)?;
+        let rent_account_info = next_account_info(account_info_iter)?;
+        let rent = &Rent::from_account_info(rent_account_info)?;
+        // Need in System Program account because we call create_account_with_seed instruction which requires it
+        let _system_program = next_account_info(account_info_iter)?;
+
+        if !user_account_info.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let user_token = Self::check_user_token(user_token_account_info)?;
+
+        let mut sticker_factory =
+            StickerFactory::try_from_slice(&sticker_factory_account_info.data.borrow())?;
+        if !sticker_factory.is_initialized() {
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        Self::create_account(
+            program_id,
+            user_account_info.clone(),
+            artist_to_create_account_info.clone(),
+            sticker_factory_account_info.key,
+            base_address_account_info.clone(),
+            Self::ARTIST_SEED,
+            rent.minimum_balance(Artist::LEN),
+            Artist::LEN as u64,
+            program_id,
+            sticker_factory.artist_count,
+        )?;
+
+        let mut artist = Artist::try_from_slice(&artist_to_create_account_info.data.borrow())?;
+        artist.user = *user_account_info.key;
+        artist.user_token_acc = *user_token_account_info.key;
+        artist.name = args.name;
+        artist.signature = args.signature;
+        artist.description = args.description;
+        artist.status = ArtistStatus::Pending;
+        artist.payment_mint = user_token.mint;
+        artist.sticker_factory = *sticker_factory_account_info.key;
+
+        sticker_factory.artist_count = sticker_factory
+            .artist_count
+            .checked_add(1)
+            .ok_or(StickerProgramError::CalculationError)?;
+
+        artist.serialize(&mut *artist_to_create_account_info.data.borrow_mut())?;
+        sticker_factory
+            .serialize(&mut *sticker_factory_account_info.data.borrow_mut())
+            .map_err(|e| e.into())
+    }
+
+    /// Approve pending artist
+    pub fn process_approve_artist_instruction(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        index: u64,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let artist_account_info = next_account_info(account_info_iter)?;
+        let curator_account_info = next_account_info(account_info_iter)?;
+        let sticker_factory_account_info = next_account_info(account_info_iter)?;
+
+        let mut artist = Self::pending_artist_by_curator(
+            program_id,
+            artist_account_info,
+            curator_account_info,
+            sticker_factory_account_info,
+            index,
+        )?;
+
+        artist.status = ArtistStatus::Approved;
+
+        artist
+            .serialize(&mut *artist_account_info.data.borrow_mut())
+            .map_err(|e| e.into())
+    }
+
+    /// Reject pending artist and refund rent to user
+    pub fn process_reject_artist_instruction(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        index: u64,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let artist_account_info = next_account_info(account_info_iter)?;
+        let user_account_info = next_account_info(account_info_iter)?;
+        let curator_account_info = next_account_info(account_info_iter)?;
+        let sticker_factory_account_info = next_account_info(account_info_iter)?;
+
+        let artist = Self::pending_artist_by_curator(
+            program_id,
+            artist_account_info,
+            curator_account_info,
+            sticker_factory_account_info,
+            index,
+        )?;
+
+        if *user_account_info.key != artist.user {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        Self::close_account(artist_account_info, user_account_info)
+    }
+
+    /// Update artist profile and account to receive payments
+    pub fn process_update_artist_instruction(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        args: UpdateArtist,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let artist_account_info = next_account_info(account_info_iter)?;
+        let user_account_info = next_account_info(account_info_iter)?;
+        let user_token_account_info = next_account_info(account_info_iter)?;
+
+        if artist_account_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let mut artist = Artist::try_from_slice(&artist_account_info.data.borrow())?;
+        if !artist.is_initialized() {
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        if *user_account_info.key != artist.user || !user_account_info.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        // stickers keep payment mint, so artist must be paid with it
+        let user_token = Self::check_user_token(user_token_account_info)?;
+        if user_token.mint != artist.payment_mint {
+            return Err(StickerProgramError::WrongTokenMint.into());
+        }
+
+        artist.user_token_acc = *user_token_account_info.key;
+        artist.name = args.name;
+        artist.signature = args.signature;
+        artist.description = args.description;
+
+        artist
+            .serialize(&mut *artist_account_info.data.borrow_mut())
+            .map_err(|e| e.into())
+    }
+
+    /// Set sticker factory fee and treasury
+    pub fn process_set_factory_fee_instruction(
+        _program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        fee_bps: u16,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let sticker_factory_account_info = next_account_info(account_info_iter)?;
+        let owner_account_info = next_account_info(account_info_iter)?;
+        let treasury_account_info = next_account_info(account_info_iter)?;
+
+        let mut sticker_factory =
+            Self::sticker_factory_by_owner(sticker_factory_account_info, owner_account_info)?;
+
+        if fee_bps > StickerFactory::MAX_FEE_BPS {
+            return Err(StickerProgramError::InvalidFee.into());
+        }
+
+        sticker_factory.fee_bps = fee_bps;
+        sticker_factory.treasury = *treasury_account_info.key;
+
+        sticker_factory
+            .serialize(&mut *sticker_factory_account_info.data.borrow_mut())
+            .map_err(|e| e.into())
+    }
+
+    /// List sticker NFT token for resale
+    pub fn process_list_sticker_instruction(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        price: u64,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let listing_account_info = next_account_info(account_info_iter)?;
+        let sticker_account_info = next_account_info(account_info_iter)?;
+        let nft_token_account_info = next_account_info(account_info_iter)?;
+        let seller_account_info = next_account_info(account_info_iter)?;
+        let seller_token_account_info = next_account_info(account_info_iter)?;
+        let listing_authority_account_info = next_account_info(account_info_iter)?;
+        let nft_token_program_id = next_account_info(account_info_iter)?;
+        let rent_account_info = next_account_info(account_info_iter)?;
+        let rent = &Rent::from_account_info(rent_account_info)?;
+
+        if *nft_token_program_id.key != spl_nft_erc_721::id()
+            || *nft_token_account_info.owner != spl_nft_erc_721::id()
+        {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        if !seller_account_info.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        if !rent.is_exempt(
+            listing_account_info.lamports(),
+            listing_account_info.data_len(),
+        ) {
+            return Err(ProgramError::AccountNotRentExempt);
+        }
+
+        let mut listing = Listing::try_from_slice(&listing_account_info.data.borrow())?;
+        if listing.is_initialized() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        // royalty is read from sticker on buy, so it must not be forged
+        if sticker_account_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let sticker = Sticker::try_from_slice(&sticker_account_info.data.borrow())?;
+        if !sticker.is_initialized() {
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        let nft_token = NftToken::deserialize(&mut &nft_token_account_info.data.borrow()[..])?;
+        if nft_token.mint != sticker.mint || nft_token.owner != *seller_account_info.key {
+            return Err(StickerProgramError::WrongNftToken.into());
+        }
+
+        // approval is replaced on listing, so other delegate must not lose it silently
+        if matches!(nft_token.approval, Some(approval) if approval != nft_token.owner) {
+            return Err(StickerProgramError::NftTokenDelegated.into());
+        }
+
+        let seller_token = Account::unpack(&seller_token_account_info.data.borrow())?;
+        if seller_token.mint != sticker.payment_mint {
+            return Err(StickerProgramError::WrongPaymentMint.into());
+        }
+
+        let (generated_listing_authority, _) =
+            Pubkey::find_program_address(&[&listing_account_info.key.to_bytes()[..32]], program_id);
+        if generated_listing_authority != *listing_authority_account_info.key {
+            return Err(ProgramError::InvalidSeeds);
+        }
+
+        Self::approve_nft(
+            nft_token_program_id.clone(),
+            nft_token_account_info.clone(),
+            listing_authority_account_info.clone(),
+            seller_account_info.clone(),
+        )?;
+
+        listing.sticker = *sticker_account_info.key;
+        listing.token = *nft_token_account_info.key;
+        listing.seller = *seller_account_info.key;
+        listing.seller_token_acc = *seller_token_account_info.key;
+        listing.price = price;
+
+        listing
+            .serialize(&mut *listing_account_info.data.borrow_mut())
+            .map_err(|e| e.into())
+    }
+
+    /// Cancel resale listing
+    pub fn process_cancel_listing_instruction(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let listing_account_info = next_account_info(account_info_iter)?;
+        let seller_account_info = next_account_info(account_info_iter)?;
+        let nft_token_account_info = next_account_info(account_info_iter)?;
+        let listing_authority_account_info = next_account_info(account_info_iter)?;
+        let nft_token_program_id = next_account_info(account_info_iter)?;
+
+        if *nft_token_program_id.key != spl_nft_erc_721::id()
+            || *nft_token_account_info.owner != spl_nft_erc_721::id()
+        {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let listing = Listing::try_from_slice(&listing_account_info.data.borrow())?;
+        if !listing.is_initialized() {
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        if *seller_account_info.key != listing.seller || !seller_account_info.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        if listing.token != *nft_token_account_info.key {
+            return Err(StickerProgramError::WrongListing.into());
+        }
+
+        let (generated_listing_authority, bump_seed) =
+            Pubkey::find_program_address(&[&listing_account_info.key.to_bytes()[..32]], program_id);
+        if generated_listing_authority != *listing_authority_account_info.key {
+            return Err(ProgramError::InvalidSeeds);
+        }
+
+        // token may be transferred or approved to other account by its owner since listing
+        let nft_token = NftToken::deserialize(&mut &nft_token_account_info.data.borrow()[..])?;
+        if nft_token.approval == Some(generated_listing_authority) {
+            Self::approve_nft_to_owner(
+                nft_token_program_id.clone(),
+                nft_token_account_info.clone(),
+                seller_account_info.clone(),
+                listing_authority_account_info.clone(),
+                listing_account_info.key,
+                bump_seed,
+            )?;
+        }
+
+        Self::close_account(listing_account_info, seller_account_info)
+    }
+
+    /// Buy listed sticker NFT token
+    pub fn process_buy_listing_instruction(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let listing_account_info = next_account_info(account_info_iter)?;
+        let sticker_account_info = next_account_info(account_info_iter)?;
+        let artist_account_info = next_account_info(account_info_iter)?;
+        let artist_token_account_info = next_account_info(account_info_iter)?;
+        let seller_account_info = next_account_info(account_info_iter)?;
+        let seller_token_account_info = next_account_info(account_info_iter)?;
+        let buyer_token_account_info = next_account_info(account_info_iter)?;
+        let buyer_transfer_authority_account_info = next_account_info(account_info_iter)?;
+        let nft_token_account_info = next_account_info(account_info_iter)?;
+        let nft_token_owner_account_info = next_account_info(account_info_iter)?;
+        let listing_authority_account_info = next_account_info(account_info_iter)?;
+        let token_program_id = next_account_info(account_info_iter)?;
+        let nft_token_program_id = next_account_info(account_info_iter)?;
+
+        if *token_program_id.key != spl_token::id()
+            || *nft_token_program_id.key != spl_nft_erc_721::id()
+        {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let listing = Listing::try_from_slice(&listing_account_info.data.borrow())?;
+        if !listing.is_initialized() {
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        if listing.sticker != *sticker_account_info.key
+            || listing.token != *nft_token_account_info.key
+            || listing.seller != *seller_account_info.key
+            || listing.seller_token_acc != *seller_token_account_info.key
+        {
+            return Err(StickerProgramError::WrongListing.into());
+        }
+
+        if sticker_account_info.owner != program_id || artist_account_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let sticker = Sticker::try_from_slice(&sticker_account_info.data.borrow())?;
+        if !sticker.is_initialized() {
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        let artist = Artist::try_from_slice(&artist_account_info.data.borrow())?;
+        if !artist.is_initialized() {
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        if sticker.creator != artist.user || *artist_token_account_info.key != artist.user_token_acc
+        {
+            return Err(StickerProgramError::WrongStickerCreator.into());
+        }
+
+        let nft_token = NftToken::deserialize(&mut &nft_token_account_info.data.borrow()[..])?;
+        if nft_token.owner != listing.seller {
+            return Err(StickerProgramError::WrongNftToken.into());
+        }
+
+        let buyer_token = Account::unpack(&buyer_token_account_info.data.borrow())?;
+        let artist_token = Account::unpack(&artist_token_account_info.data.borrow())?;
+        if buyer_token.mint != sticker.payment_mint || artist_token.mint != sticker.payment_mint {
+            return Err(StickerProgramError::WrongPaymentMint.into());
+        }
+
+        let (generated_listing_authority, bump_seed) =
+            Pubkey::find_program_address(&[&listing_account_info.key.to_bytes()[..32]], program_id);
+        if generated_listing_authority != *listing_authority_account_info.key {
+            return Err(ProgramError::InvalidSeeds);
+        }
+
+        let royalty = sticker
+            .royalty(listing.price)
+            .ok_or(StickerProgramError::CalculationError)?;
+        let seller_amount = listing
+            .price
+            .checked_sub(royalty)
+            .ok_or(StickerProgramError::CalculationError)?;
+
+        if royalty > 0 {
+            Self::transfer(
+                token_program_id.clone(),
+                buyer_token_account_info.clone(),
+                artist_token_account_info.clone(),
+                buyer_transfer_authority_account_info.clone(),
+                royalty,
+            )?;
+        }
+
+        Self::transfer(
+            token_program_id.clone(),
+            buyer_token_account_info.clone(),
+            seller_token_account_info.clone(),
+            buyer_transfer_authority_account_info.clone(),
+            seller_amount,
+        )?;
+
+        Self::transfer_nft(
+            nft_token_program_id.clone(),
+            nft_token_account_info.clone(),
+            nft_token_owner_account_info.clone(),
+            listing_authority_account_info.clone(),
+            listing_account_info.key,
+            bump_seed,
+        )?;
+
+        Self::close_account(listing_account_info, seller_account_info)
+    }
+
+    /// Create sticker pack
+    pub fn process_create_sticker_pack_instruction(
+        _program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        price: u64,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let sticker_pack_account_info = next_account_info(account_info_iter)?;
+        let creator_account_info = next_account_info(account_info_iter)?;
+        let rent_account_info = next_account_info(account_info_iter)?;
+        let rent = &Rent::from_account_info(rent_account_info)?;
+        let sticker_account_infos = account_info_iter.as_slice();
+
+        if !creator_account_info.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        if !rent.is_exempt(
+            sticker_pack_account_info.lamports(),
+            sticker_pack_account_info.data_len(),
+        ) {
+            return Err(ProgramError::AccountNotRentExempt);
+        }
+
+        let mut sticker_pack =
+            StickerPack::try_from_slice(&sticker_pack_account_info.data.borrow())?;
+        if sticker_pack.is_initialized() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        if sticker_account_infos.is_empty()
+            || sticker_account_infos.len() > StickerPack::MAX_STICKERS
+        {
+            return Err(StickerProgramError::WrongStickerPackSize.into());
+        }
+
+        for (index, sticker_account_info) in sticker_account_infos.iter().enumerate() {
+            let sticker = Sticker::try_from_slice(&sticker_account_info.data.borrow())?;
+            if !sticker.is_initialized() {
+                return Err(ProgramError::UninitializedAccount);
+            }
+
+            if sticker.creator != *creator_account_info.key {
+                return Err(StickerProgramError::WrongStickerCreator.into());
+            }
+
+            if index == 0 {
+                sticker_pack.sticker_factory = sticker.sticker_factory;
+                sticker_pack.payment_mint = sticker.payment_mint;
+            } else if sticker.sticker_factory != sticker_pack.sticker_factory {
+                return Err(StickerProgramError::WrongStickerFactory.into());
+            } else if sticker.payment_mint != sticker_pack.payment_mint {
+                return Err(StickerProgramError::WrongPaymentMint.into());
+            }
+
+            sticker_pack.stickers[index] = *sticker_account_info.key;
+        }
+
+        sticker_pack.creator = *creator_account_info.key;
+        sticker_pack.price = price;
+        sticker_pack.sticker_count = sticker_account_infos.len() as u8;
+
+        sticker_pack
+            .serialize(&mut *sticker_pack_account_info.data.borrow_mut())
+            .map_err(|e| e.into())
+    }
+
+    /// Buy sticker pack
+    pub fn process_buy_sticker_pack_instruction(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let sticker_pack_account_info = next_account_info(account_info_iter)?;
+        let artist_account_info = next_account_info(account_info_iter)?;
+        let artist_token_account_info = next_account_info(account_info_iter)?;
+        let buyer_token_account_info = next_account_info(account_info_iter)?;
+        let buyer_transfer_authority_account_info = next_account_info(account_info_iter)?;
+        let nft_token_owner_account_info = next_account_info(account_info_iter)?;
+        let token_program_id = next_account_info(account_info_iter)?;
+        let nft_token_program_id = next_account_info(account_info_iter)?;
+        // Need in Rent account because we call NFT program instruction which uses it
+        let _rent_account_info = next_account_info(account_info_iter)?;
+        let sticker_factory_account_info = next_account_info(account_info_iter)?;
+        let treasury_token_account_info = next_account_info(account_info_iter)?;
+
+        if *token_program_id.key != spl_token::id()
+            || *nft_token_program_id.key != spl_nft_erc_721::id()
+        {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        // pack is only read on buy, so its owner is not checked by runtime
+        if sticker_pack_account_info.owner != program_id || artist_account_info.owner != program_id
+        {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let sticker_pack = StickerPack::try_from_slice(&sticker_pack_account_info.data.borrow())?;
+        if !sticker_pack.is_initialized() {
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        let sticker_factory = Self::sticker_factory(program_id, sticker_factory_account_info)?;
+        if sticker_pack.sticker_factory != *sticker_factory_account_info.key {
+            return Err(StickerProgramError::WrongStickerFactory.into());
+        }
+
+        let artist = Artist::try_from_slice(&artist_account_info.data.borrow())?;
+        if !artist.is_initialized() {
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        if sticker_pack.creator != artist.user
+            || *artist_token_account_info.key != artist.user_token_acc
+        {
+            return Err(StickerProgramError::WrongStickerCreator.into());
+        }
+
+        let buyer_token = Account::unpack(&buyer_token_account_info.data.borrow())?;
+        let artist_token = Account::unpack(&artist_token_account_info.data.borrow())?;
+        if buyer_token.mint != sticker_pack.payment_mint
+            || artist_token.mint != sticker_pack.payment_mint
+        {
+            return Err(StickerProgramError::WrongPaymentMint.into());
+        }
+
+        Self::pay_with_fee(
+            token_program_id.clone(),
+            buyer_token_account_info.clone(),
+            artist_token_account_info.clone(),
+            treasury_token_account_info.clone(),
+            buyer_transfer_authority_account_info.clone(),
+            &sticker_factory,
+            &sticker_pack.payment_mint,
+            sticker_pack.price,
+        )?;
+
+        let now = Clock::get()?.unix_timestamp;
+        for sticker_key in sticker_pack.stickers() {
+            let sticker_account_info = next_account_info(account_info_iter)?;
+            let mint_authority = next_account_info(account_info_iter)?;
+            let nft_token_account_info = next_account_info(account_info_iter)?;
+            let nft_token_data_account_info = next_account_info(account_info_iter)?;
+            let nft_token_mint_account_info = next_account_info(account_info_iter)?;
+
+            if *sticker_account_info.key != *sticker_key {
+                return Err(StickerProgramError::StickerNotInPack.into());
+            }
+
+            let mut sticker = Sticker::try_from_slice(&sticker_account_info.data.borrow())?;
+            if sticker.creator != sticker_pack.creator {
+                return Err(StickerProgramError::WrongStickerCreator.into());
+            }
+
+            if sticker.payment_mint != sticker_pack.payment_mint {
+                return Err(StickerProgramError::WrongPaymentMint.into());
+            }
+
+            // Pack has own price, only sticker sale window is checked
+            sticker.price_at(now)?;
+
+            Self::mint_sticker(
+                program_id,
+                nft_token_program_id.clone(),
+                sticker_account_info,
+                &mut sticker,
+                mint_authority.clone(),
+                nft_token_account_info.clone(),
+                nft_token_data_account_info.clone(),
+                nft_token_mint_account_info.clone(),
+                nft_token_owner_account_info.clone(),
+            )?;
+
+            sticker.serialize(&mut *sticker_account_info.data.borrow_mut())?;
+        }
+
+        Ok(())
+    }
+
+    /// Set sticker sale schedule
+    pub fn process_set_sticker_sale_instruction(
+        _program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        sale: SaleSchedule,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let sticker_account_info = next_account_info(account_info_iter)?;
+        let creator_account_info = next_account_info(account_info_iter)?;
+
+        let mut sticker = Sticker::try_from_slice(&sticker_account_info.data.borrow())?;
+        if !sticker.is_initialized() {
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        if *creator_account_info.key != sticker.creator || !creator_account_info.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        if !sale.is_valid() {
+            return Err(StickerProgramError::InvalidSaleSchedule.into());
+        }
+
+        sticker.sale = sale;
+
+        sticker
+            .serialize(&mut *sticker_account_info.data.borrow_mut())
+            .map_err(|e| e.into())
+    }
+
+    /// Pause, resume or retire sticker
+    pub fn process_set_sticker_status_instruction(
+        _program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        status: StickerStatus,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let sticker_account_info = next_account_info(account_info_iter)?;
+        let creator_account_info = next_account_info(account_info_iter)?;
+
+        let mut sticker = Sticker::try_from_slice(&sticker_account_info.data.borrow())?;
+        if !sticker.is_initialized() {
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        if *creator_account_info.key != sticker.creator || !creator_account_info.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        if sticker.status == StickerStatus::Retired {
+            return Err(StickerProgramError::StickerRetired.into());
+        }
+
+        if status == StickerStatus::Retired {
+            sticker.max_supply = sticker.supply;
+        }
+        sticker.status = status;
+
+        sticker
+            .serialize(&mut *sticker_account_info.data.borrow_mut())
+            .map_err(|e| e.into())
+    }
+
+    /// Airdrop sticker NFT tokens without payment
+    pub fn process_airdrop_sticker_instruction(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let sticker_account_info = next_account_info(account_info_iter)?;
+        let creator_account_info = next_account_info(account_info_iter)?;
+        let mint_authority = next_account_info(account_info_iter)?;
+        let nft_token_mint_account_info = next_account_info(account_info_iter)?;
+        let nft_token_program_id = next_account_info(account_info_iter)?;
+        // Need in Rent account because we call NFT program instruction which uses it
+        let _rent_account_info = next_account_info(account_info_iter)?;
+        let recipient_account_infos = account_info_iter.as_slice();
+
+        if *nft_token_program_id.key != spl_nft_erc_721::id() {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let mut sticker = Sticker::try_from_slice(&sticker_account_info.data.borrow())?;
+        if !sticker.is_initialized() {
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        if *creator_account_info.key != sticker.creator || !creator_account_info.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let recipients = recipient_account_infos.chunks_exact(3);
+        if recipient_account_infos.is_empty() || !recipients.remainder().is_empty() {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        }
+
+        for recipient in recipients {
+            Self::mint_sticker(
+                program_id,
+                nft_token_program_id.clone(),
+                sticker_account_info,
+                &mut sticker,
+                mint_authority.clone(),
+                recipient[1].clone(),
+                recipient[2].clone(),
+                nft_token_mint_account_info.clone(),
+                recipient[0].clone(),
+            )?;
+        }
+
+        sticker
+            .serialize(&mut *sticker_account_info.data.borrow_mut())
+            .map_err(|e| e.into())
+    }
+
+    /// Migrate account created with `LEN_V1` to current layout
+    pub fn process_migrate_account_instruction(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        input: MigrateAccountInput,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let payer_account_info = next_account_info(account_info_iter)?;
+        let account_info = next_account_info(account_info_iter)?;
+        let sticker_factory_account_info = next_account_info(account_info_iter)?;
+        let rent_account_info = next_account_info(account_info_iter)?;
+        let system_program_account_info = next_account_info(account_info_iter)?;
+        let rent = &Rent::from_account_info(rent_account_info)?;
+
+        if account_info.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let (len_v1, len) = match input {
+            MigrateAccountInput::StickerFactory => (StickerFactory::LEN_V1, StickerFactory::LEN),
+            MigrateAccountInput::Artist(index) => {
+                Self::check_factory_index_key(
+                    program_id,
+                    sticker_factory_account_info.key,
+                    Self::ARTIST_SEED,
+                    index,
+                    account_info.key,
+                )?;
+                (Artist::LEN_V1, Artist::LEN)
+            }
+            MigrateAccountInput::Sticker(index) => {
+                Self::check_factory_index_key(
+                    program_id,
+                    sticker_factory_account_info.key,
+                    Self::STICKER_SEED,
+                    index,
+                    account_info.key,
+                )?;
+                (Sticker::LEN_V1, Sticker::LEN)
+            }
+        };
+
+        if account_info.data_len() != len_v1 {
+            return Err(StickerProgramError::AccountAlreadyMigrated.into());
+        }
+
+        let required_lamports = rent
+            .minimum_balance(len)
+            .saturating_sub(account_info.lamports());
+        if required_lamports > 0 {
+            invoke(
+                &system_instruction::transfer(
+                    payer_account_info.key,
+                    account_info.key,
+                    required_lamports,
+                ),
+                &[
+                    payer_account_info.clone(),
+                    account_info.clone(),
+                    system_program_account_info.clone(),
+                ],
+            )?;
+        }
+
+        // new fields are appended, so old data stays in place and new fields are zeroed
+        account_info.realloc(len, true)?;
+
+        match input {
+            MigrateAccountInput::StickerFactory => {
+                let sticker_factory = StickerFactory::try_from_slice(&account_info.data.borrow())?;
+                if !sticker_factory.is_initialized() {
+                    return Err(ProgramError::UninitializedAccount);
+                }
+            }
+            MigrateAccountInput::Artist(_) => {
+                let mut artist = Artist::try_from_slice(&account_info.data.borrow())?;
+                if !artist.is_initialized() {
+                    return Err(ProgramError::UninitializedAccount);
+                }
+
+                // only factory owner could register artists before applications,
+                // and only accounts of wrapped SOL were accepted to receive payments
+                artist.status = ArtistStatus::Approved;
+                artist.payment_mint = spl_token::native_mint::id();
+                artist.sticker_factory = *sticker_factory_account_info.key;
+                artist.serialize(&mut *account_info.data.borrow_mut())?;
+            }
+            MigrateAccountInput::Sticker(_) => {
+                let mut sticker = Sticker::try_from_slice(&account_info.data.borrow())?;
+                if !sticker.is_initialized() {
+                    return Err(ProgramError::UninitializedAccount);
+                }
+
+                // artists were paid only with wrapped SOL before payment mints
+                sticker.payment_mint = spl_token::native_mint::id();
+                sticker.sticker_factory = *sticker_factory_account_info.key;
+                sticker.status = StickerStatus::Active;
+                sticker.serialize(&mut *account_info.data.borrow_mut())?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Processes an instruction
+    pub fn process_instruction(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        input: &[u8],
+    ) -> ProgramResult {
+        let instruction = StickerInstruction::try_from_slice(input)?;
+        match instruction {
+            StickerInstruction::RegisterArtist(args) => {
+                msg!("Instruction: RegisterArtist");
+                Self::process_register_artist_instruction(program_id, accounts, args)
+            }
+            StickerInstruction::CreateNewSticker(args) => {
+                msg!("Instruction: CreateNewSticker");
+                Self::process_create_new_sticker_instruction(program_id, accounts, args)
+            }
+            StickerInstruction::BuySticker => {
+                msg!("Instruction: BuySticker");
+                Self::process_buy_sticker_instruction(program_id, accounts)
+            }
+            StickerInstruction::ChangeStickerPrice(new_price) => {
+                msg!("Instruction: ChangeStickerPrice");
+                Self::process_change_sticker_price_instruction(program_id, accounts, new_price)
+            }
+            StickerInstruction::CreateAccount(account_type) => {
                 msg!("Instruction: CreateAccount");
                 Self::process_create_account_instruction(program_id, accounts, account_type)
             }
@@ -577,6 +1801,94 @@ impl Processor {
                 msg!("Instruction: CreateStickerFactory");
                 Self::process_create_new_sticker_factory_instruction(program_id, accounts)
             }
+            StickerInstruction::TransferFactoryOwnership => {
+                msg!("Instruction: TransferFactoryOwnership");
+                Self::process_transfer_factory_ownership_instruction(program_id, accounts)
+            }
+            StickerInstruction::AddCurator => {
+                msg!("Instruction: AddCurator");
+                Self::process_add_curator_instruction(program_id, accounts)
+            }
+            StickerInstruction::RemoveCurator => {
+                msg!("Instruction: RemoveCurator");
+                Self::process_remove_curator_instruction(program_id, accounts)
+            }
+            StickerInstruction::ApplyAsArtist(args) => {
+                msg!("Instruction: ApplyAsArtist");
+                Self::process_apply_as_artist_instruction(program_id, accounts, args)
+            }
+            StickerInstruction::ApproveArtist(index) => {
+                msg!("Instruction: ApproveArtist");
+                Self::process_approve_artist_instruction(program_id, accounts, index)
+            }
+            StickerInstruction::RejectArtist(index) => {
+                msg!("Instruction: RejectArtist");
+                Self::process_reject_artist_instruction(program_id, accounts, index)
+            }
+            StickerInstruction::UpdateArtist(args) => {
+                msg!("Instruction: UpdateArtist");
+                Self::process_update_artist_instruction(program_id, accounts, args)
+            }
+            StickerInstruction::SetFactoryFee(fee_bps) => {
+                msg!("Instruction: SetFactoryFee");
+                Self::process_set_factory_fee_instruction(program_id, accounts, fee_bps)
+            }
+            StickerInstruction::ListSticker(price) => {
+                msg!("Instruction: ListSticker");
+                Self::process_list_sticker_instruction(program_id, accounts, price)
+            }
+            StickerInstruction::CancelListing => {
+                msg!("Instruction: CancelListing");
+                Self::process_cancel_listing_instruction(program_id, accounts)
+            }
+            StickerInstruction::BuyListing => {
+                msg!("Instruction: BuyListing");
+                Self::process_buy_listing_instruction(program_id, accounts)
+            }
+            StickerInstruction::CreateStickerPack(price) => {
+                msg!("Instruction: CreateStickerPack");
+                Self::process_create_sticker_pack_instruction(program_id, accounts, price)
+            }
+            StickerInstruction::BuyStickerPack => {
+                msg!("Instruction: BuyStickerPack");
+                Self::process_buy_sticker_pack_instruction(program_id, accounts)
+            }
+            StickerInstruction::SetStickerSale(sale) => {
+                msg!("Instruction: SetStickerSale");
+                Self::process_set_sticker_sale_instruction(program_id, accounts, sale)
+            }
+            StickerInstruction::PauseSticker => {
+                msg!("Instruction: PauseSticker");
+                Self::process_set_sticker_status_instruction(
+                    program_id,
+                    accounts,
+                    StickerStatus::Paused,
+                )
+            }
+            StickerInstruction::ResumeSticker => {
+                msg!("Instruction: ResumeSticker");
+                Self::process_set_sticker_status_instruction(
+                    program_id,
+                    accounts,
+                    StickerStatus::Active,
+                )
+            }
+            StickerInstruction::RetireSticker => {
+                msg!("Instruction: RetireSticker");
+                Self::process_set_sticker_status_instruction(
+                    program_id,
+                    accounts,
+                    StickerStatus::Retired,
+                )
+            }
+            StickerInstruction::AirdropSticker => {
+                msg!("Instruction: AirdropSticker");
+                Self::process_airdrop_sticker_instruction(program_id, accounts)
+            }
+            StickerInstruction::MigrateAccount(input) => {
+                msg!("Instruction: MigrateAccount");
+                Self::process_migrate_account_instruction(program_id, accounts, input)
+            }
         }
     }
 }
@@ -1,6 +1,8 @@
 //! State transition types
+use crate::error::StickerProgramError;
 use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::pubkey::Pubkey;
+use std::convert::TryFrom;
 
 /// Sticker factory account
 #[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone, Default)]
@@ -11,6 +13,12 @@ pub struct StickerFactory {
     pub sticker_count: u64,
     /// Owner
     pub owner: Pubkey,
+    /// Keys allowed to register artists besides owner, empty slots are default keys
+    pub curators: [Pubkey; StickerFactory::MAX_CURATORS],
+    /// Fee taken from each sale in basis points
+    pub fee_bps: u16,
+    /// Owner of token accounts receiving fees, one per payment mint
+    pub treasury: Pubkey,
 }
 
 /// Artist account
@@ -26,6 +34,21 @@ pub struct Artist {
     pub signature: [u8; 256],
     /// Description
     pub description: [u8; 256],
+    /// Status
+    pub status: ArtistStatus,
+    /// Mint of account to receive payments, artist's stickers are paid with it
+    pub payment_mint: Pubkey,
+    /// Sticker factory artist is registered and approved in, artist creates stickers only there
+    pub sticker_factory: Pubkey,
+}
+
+/// Artist status, zeroed status is approved as all artists registered before applications were
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone, Copy)]
+pub enum ArtistStatus {
+    /// Approved, can create stickers
+    Approved,
+    /// Applied and waits for owner or curator approval
+    Pending,
 }
 
 /// Sticker account
@@ -43,35 +66,145 @@ pub struct Sticker {
     pub mint: Pubkey,
     /// URI
     pub uri: [u8; 256],
+    /// Mint of tokens sticker is paid with
+    pub payment_mint: Pubkey,
+    /// Sticker factory
+    pub sticker_factory: Pubkey,
+    /// Royalty paid to artist on resale in basis points
+    pub royalty_bps: u16,
+    /// Sale schedule
+    pub sale: SaleSchedule,
+    /// Status
+    pub status: StickerStatus,
+}
+
+/// Sticker status
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone, Copy)]
+pub enum StickerStatus {
+    /// On sale
+    Active,
+    /// Temporarily not on sale
+    Paused,
+    /// Permanently not on sale, max supply is capped at supply
+    Retired,
+}
+
+/// Sticker sale schedule, zero values are not set
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone, Copy, Default)]
+pub struct SaleSchedule {
+    /// Unix timestamp sale starts at
+    pub start: i64,
+    /// Unix timestamp sale ends at, exclusive
+    pub end: i64,
+    /// Price before `early_bird_end`
+    pub early_bird_price: u64,
+    /// Unix timestamp early bird price ends at, exclusive
+    pub early_bird_end: i64,
+}
+
+/// Sticker pack account, stickers of one artist sold together for one price
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone, Default)]
+pub struct StickerPack {
+    /// Creator
+    pub creator: Pubkey,
+    /// Sticker factory
+    pub sticker_factory: Pubkey,
+    /// Price
+    pub price: u64,
+    /// Mint of tokens pack is paid with
+    pub payment_mint: Pubkey,
+    /// Stickers count
+    pub sticker_count: u8,
+    /// Stickers, only first `sticker_count` are used
+    pub stickers: [Pubkey; StickerPack::MAX_STICKERS],
+}
+
+/// Resale listing account, its program address is approved to transfer listed NFT token
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone, Default)]
+pub struct Listing {
+    /// Sticker
+    pub sticker: Pubkey,
+    /// NFT token
+    pub token: Pubkey,
+    /// Seller, NFT token owner
+    pub seller: Pubkey,
+    /// Seller's token account to receive payments
+    pub seller_token_acc: Pubkey,
+    /// Price
+    pub price: u64,
 }
 
 // Implement Default by hands because of big arrays
 impl Default for Artist {
     fn default() -> Artist {
-        unsafe { std::mem::zeroed() }
+        Artist {
+            user: Pubkey::default(),
+            user_token_acc: Pubkey::default(),
+            name: [0; 32],
+            signature: [0; 256],
+            description: [0; 256],
+            status: ArtistStatus::Approved,
+            payment_mint: Pubkey::default(),
+            sticker_factory: Pubkey::default(),
+        }
     }
 }
 
 // Implement Default by hands because of big arrays
 impl Default for Sticker {
     fn default() -> Sticker {
-        unsafe { std::mem::zeroed() }
+        Sticker {
+            creator: Pubkey::default(),
+            supply: 0,
+            max_supply: 0,
+            price: 0,
+            mint: Pubkey::default(),
+            uri: [0; 256],
+            payment_mint: Pubkey::default(),
+            sticker_factory: Pubkey::default(),
+            royalty_bps: 0,
+            sale: SaleSchedule::default(),
+            status: StickerStatus::Active,
+        }
     }
 }
 
 impl StickerFactory {
     /// LEN
-    pub const LEN: usize = 48;
+    pub const LEN: usize = 338;
+    /// LEN before curators, fee and treasury were added, see `MigrateAccount`
+    pub const LEN_V1: usize = 48;
+    /// Max curators count
+    pub const MAX_CURATORS: usize = 8;
+    /// Fee of whole price
+    pub const MAX_FEE_BPS: u16 = 10_000;
 
     /// Check if struct is initialized
     pub fn is_initialized(&self) -> bool {
         self.owner != Pubkey::default()
     }
+
+    /// Check if key is one of curators
+    pub fn is_curator(&self, key: &Pubkey) -> bool {
+        *key != Pubkey::default() && self.curators.contains(key)
+    }
+
+    /// Check if key is owner or curator
+    pub fn can_register_artist(&self, key: &Pubkey) -> bool {
+        *key == self.owner || self.is_curator(key)
+    }
+
+    /// Fee for given price, rounded down in artist's favour
+    pub fn fee(&self, price: u64) -> Option<u64> {
+        bps_share(price, self.fee_bps)
+    }
 }
 
 impl Artist {
     /// LEN
-    pub const LEN: usize = 608;
+    pub const LEN: usize = 673;
+    /// LEN before status, payment mint and sticker factory were added, see `MigrateAccount`
+    pub const LEN_V1: usize = 608;
 
     /// Check if struct is initialized
     pub fn is_initialized(&self) -> bool {
@@ -81,10 +214,97 @@ impl Artist {
 
 impl Sticker {
     /// LEN
-    pub const LEN: usize = 344;
+    pub const LEN: usize = 443;
+    /// LEN before payment mint, factory, royalty, sale and status were added, see `MigrateAccount`
+    pub const LEN_V1: usize = 344;
+    /// Royalty of whole price
+    pub const MAX_ROYALTY_BPS: u16 = 10_000;
 
     /// Check if struct is initialized
     pub fn is_initialized(&self) -> bool {
         *self != Sticker::default()
     }
+
+    /// Royalty for given resale price, rounded down in seller's favour
+    pub fn royalty(&self, price: u64) -> Option<u64> {
+        bps_share(price, self.royalty_bps)
+    }
+
+    /// Price at given unix timestamp, fails if sticker is not active or outside of sale window
+    pub fn price_at(&self, now: i64) -> Result<u64, StickerProgramError> {
+        match self.status {
+            StickerStatus::Active => {}
+            StickerStatus::Paused => return Err(StickerProgramError::StickerPaused),
+            StickerStatus::Retired => return Err(StickerProgramError::StickerRetired),
+        }
+
+        if self.sale.start != 0 && now < self.sale.start {
+            return Err(StickerProgramError::SaleNotStarted);
+        }
+
+        if self.sale.end != 0 && now >= self.sale.end {
+            return Err(StickerProgramError::SaleEnded);
+        }
+
+        if now < self.sale.early_bird_end {
+            Ok(self.sale.early_bird_price)
+        } else {
+            Ok(self.price)
+        }
+    }
+}
+
+impl SaleSchedule {
+    /// LEN
+    pub const LEN: usize = 32;
+
+    /// Check start is before end and set early bird price ends after start and before end
+    pub fn is_valid(&self) -> bool {
+        if self.early_bird_end != 0
+            && (self.early_bird_price == 0 || self.early_bird_end < self.start)
+        {
+            return false;
+        }
+
+        if self.end == 0 {
+            return true;
+        }
+
+        self.start < self.end && self.early_bird_end <= self.end
+    }
+}
+
+impl StickerPack {
+    /// LEN
+    pub const LEN: usize = 361;
+    /// Max stickers count
+    pub const MAX_STICKERS: usize = 8;
+
+    /// Check if struct is initialized
+    pub fn is_initialized(&self) -> bool {
+        *self != StickerPack::default()
+    }
+
+    /// Stickers in pack
+    pub fn stickers(&self) -> &[Pubkey] {
+        &self.stickers[..self.sticker_count as usize]
+    }
+}
+
+impl Listing {
+    /// LEN
+    pub const LEN: usize = 136;
+
+    /// Check if struct is initialized
+    pub fn is_initialized(&self) -> bool {
+        *self != Listing::default()
+    }
+}
+
+/// Share of amount in basis points, rounded down
+fn bps_share(amount: u64, bps: u16) -> Option<u64> {
+    let share = (amount as u128)
+        .checked_mul(bps as u128)?
+        .checked_div(10_000)?;
+    u64::try_from(share).ok()
 }
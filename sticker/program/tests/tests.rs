@@ -1,14 +1,15 @@
 #![cfg(feature = "test-bpf")]
 
-use borsh::de::BorshDeserialize;
+use borsh::{de::BorshDeserialize, BorshSerialize};
 use satellite_stickers::*;
 use solana_program::pubkey::Pubkey;
 use solana_program::{program_pack::Pack, system_instruction};
 use solana_program_test::*;
 use solana_sdk::{
     account::Account,
+    instruction::{Instruction, InstructionError},
     signature::{Keypair, Signer},
-    transaction::Transaction,
+    transaction::{Transaction, TransactionError},
     transport::TransportError,
 };
 
@@ -230,6 +231,8 @@ pub async fn buy_sticker(
     nft_token_data_rent: u64,
     nft_token_mint: &Pubkey,
     nft_token_owner: &Pubkey,
+    sticker_factory: &Pubkey,
+    treasury_token_acc: &Pubkey,
 ) -> Result<Pubkey, TransportError> {
     let seed = "token_data";
     let token_data =
@@ -265,6 +268,8 @@ pub async fn buy_sticker(
                 &token_data,
                 nft_token_mint,
                 nft_token_owner,
+                sticker_factory,
+                treasury_token_acc,
             )
             .unwrap(),
         ],
@@ -305,79 +310,1432 @@ pub async fn change_sticker_price(
     Ok(())
 }
 
-#[tokio::test]
-async fn test_create_account_instruction() {
-    let mut program_context = program_test().start_with_context().await;
+pub async fn transfer_factory_ownership(
+    program_context: &mut ProgramTestContext,
+    sticker_factory: &Pubkey,
+    owner: &Keypair,
+    new_owner: &Keypair,
+) -> Result<(), TransportError> {
+    let mut transaction = Transaction::new_with_payer(
+        &[instruction::transfer_factory_ownership(
+            &id(),
+            sticker_factory,
+            &owner.pubkey(),
+            &new_owner.pubkey(),
+        )
+        .unwrap()],
+        Some(&program_context.payer.pubkey()),
+    );
+    transaction.sign(
+        &[&program_context.payer, owner, new_owner],
+        program_context.last_blockhash,
+    );
+    program_context
+        .banks_client
+        .process_transaction(transaction)
+        .await?;
+    Ok(())
+}
 
-    let sticker_factory = Keypair::new();
-    let sticker_factory_owner = Keypair::new();
+pub async fn add_curator(
+    program_context: &mut ProgramTestContext,
+    sticker_factory: &Pubkey,
+    owner: &Keypair,
+    curator: &Pubkey,
+) -> Result<(), TransportError> {
+    let mut transaction = Transaction::new_with_payer(
+        &[instruction::add_curator(&id(), sticker_factory, &owner.pubkey(), curator).unwrap()],
+        Some(&program_context.payer.pubkey()),
+    );
+    transaction.sign(
+        &[&program_context.payer, owner],
+        program_context.last_blockhash,
+    );
+    program_context
+        .banks_client
+        .process_transaction(transaction)
+        .await?;
+    Ok(())
+}
 
-    let rent = program_context.banks_client.get_rent().await.unwrap();
-    let sticker_factory_min_rent = rent.minimum_balance(state::StickerFactory::LEN);
+pub async fn remove_curator(
+    program_context: &mut ProgramTestContext,
+    sticker_factory: &Pubkey,
+    owner: &Keypair,
+    curator: &Pubkey,
+) -> Result<(), TransportError> {
+    let mut transaction = Transaction::new_with_payer(
+        &[instruction::remove_curator(&id(), sticker_factory, &owner.pubkey(), curator).unwrap()],
+        Some(&program_context.payer.pubkey()),
+    );
+    transaction.sign(
+        &[&program_context.payer, owner],
+        program_context.last_blockhash,
+    );
+    program_context
+        .banks_client
+        .process_transaction(transaction)
+        .await?;
+    Ok(())
+}
 
-    create_account(
-        &mut program_context,
-        &sticker_factory,
-        sticker_factory_min_rent,
-        state::StickerFactory::LEN as u64,
-        &id(),
-    )
-    .await
-    .unwrap();
+pub async fn apply_as_artist(
+    program_context: &mut ProgramTestContext,
+    user: &Keypair,
+    user_token_acc: &Pubkey,
+    artist_to_create: &Pubkey,
+    sticker_factory: &Pubkey,
+    base: &Pubkey,
+    args: instruction::RegisterArtist,
+) -> Result<(), TransportError> {
+    let mut transaction = Transaction::new_with_payer(
+        &[instruction::apply_as_artist(
+            &id(),
+            &user.pubkey(),
+            user_token_acc,
+            artist_to_create,
+            sticker_factory,
+            base,
+            args,
+        )
+        .unwrap()],
+        Some(&program_context.payer.pubkey()),
+    );
+    transaction.sign(
+        &[&program_context.payer, user],
+        program_context.last_blockhash,
+    );
+    program_context
+        .banks_client
+        .process_transaction(transaction)
+        .await?;
+    Ok(())
+}
 
-    create_sticker_factory(
-        &mut program_context,
-        &sticker_factory,
-        &sticker_factory_owner,
-    )
-    .await
-    .unwrap();
+pub async fn approve_artist(
+    program_context: &mut ProgramTestContext,
+    artist: &Pubkey,
+    curator: &Keypair,
+    sticker_factory: &Pubkey,
+    index: u64,
+) -> Result<(), TransportError> {
+    let mut transaction = Transaction::new_with_payer(
+        &[
+            instruction::approve_artist(&id(), artist, &curator.pubkey(), sticker_factory, index)
+                .unwrap(),
+        ],
+        Some(&program_context.payer.pubkey()),
+    );
+    transaction.sign(
+        &[&program_context.payer, curator],
+        program_context.last_blockhash,
+    );
+    program_context
+        .banks_client
+        .process_transaction(transaction)
+        .await?;
+    Ok(())
+}
 
-    let sticker_factory_data = get_account(&mut program_context, &sticker_factory.pubkey()).await;
-    let sticker_factory =
-        state::StickerFactory::try_from_slice(&sticker_factory_data.data.as_slice()).unwrap();
+pub async fn reject_artist(
+    program_context: &mut ProgramTestContext,
+    artist: &Pubkey,
+    user: &Pubkey,
+    curator: &Keypair,
+    sticker_factory: &Pubkey,
+    index: u64,
+) -> Result<(), TransportError> {
+    let mut transaction = Transaction::new_with_payer(
+        &[instruction::reject_artist(
+            &id(),
+            artist,
+            user,
+            &curator.pubkey(),
+            sticker_factory,
+            index,
+        )
+        .unwrap()],
+        Some(&program_context.payer.pubkey()),
+    );
+    transaction.sign(
+        &[&program_context.payer, curator],
+        program_context.last_blockhash,
+    );
+    program_context
+        .banks_client
+        .process_transaction(transaction)
+        .await?;
+    Ok(())
+}
 
-    assert!(sticker_factory.is_initialized());
+pub async fn update_artist(
+    program_context: &mut ProgramTestContext,
+    artist: &Pubkey,
+    user: &Keypair,
+    user_token_acc: &Pubkey,
+    args: instruction::UpdateArtist,
+) -> Result<(), TransportError> {
+    let mut transaction = Transaction::new_with_payer(
+        &[
+            instruction::update_artist(&id(), artist, &user.pubkey(), user_token_acc, args)
+                .unwrap(),
+        ],
+        Some(&program_context.payer.pubkey()),
+    );
+    transaction.sign(
+        &[&program_context.payer, user],
+        program_context.last_blockhash,
+    );
+    program_context
+        .banks_client
+        .process_transaction(transaction)
+        .await?;
+    Ok(())
 }
 
-#[tokio::test]
-async fn test_register_artist_instruction() {
-    let mut program_context = program_test().start_with_context().await;
+pub async fn create_mint(
+    program_context: &mut ProgramTestContext,
+    mint: &Keypair,
+    mint_rent: u64,
+    authority: &Pubkey,
+) -> Result<(), TransportError> {
+    let instructions = vec![
+        system_instruction::create_account(
+            &program_context.payer.pubkey(),
+            &mint.pubkey(),
+            mint_rent,
+            spl_token::state::Mint::LEN as u64,
+            &spl_token::id(),
+        ),
+        spl_token::instruction::initialize_mint(
+            &spl_token::id(),
+            &mint.pubkey(),
+            authority,
+            None,
+            0,
+        )
+        .unwrap(),
+    ];
 
-    let sticker_factory = Keypair::new();
-    let sticker_factory_owner = Keypair::new();
+    let mut transaction =
+        Transaction::new_with_payer(&instructions, Some(&program_context.payer.pubkey()));
 
-    let rent = program_context.banks_client.get_rent().await.unwrap();
-    let sticker_factory_min_rent = rent.minimum_balance(state::StickerFactory::LEN);
-    let token_account_rent = rent.minimum_balance(spl_token::state::Account::LEN);
+    transaction.sign(
+        &[&program_context.payer, mint],
+        program_context.last_blockhash,
+    );
+    program_context
+        .banks_client
+        .process_transaction(transaction)
+        .await?;
+    Ok(())
+}
 
-    create_account(
-        &mut program_context,
-        &sticker_factory,
-        sticker_factory_min_rent,
-        state::StickerFactory::LEN as u64,
-        &id(),
-    )
-    .await
-    .unwrap();
+pub async fn mint_tokens(
+    program_context: &mut ProgramTestContext,
+    mint: &Pubkey,
+    account: &Pubkey,
+    authority: &Keypair,
+    amount: u64,
+) -> Result<(), TransportError> {
+    let mut transaction = Transaction::new_with_payer(
+        &[spl_token::instruction::mint_to(
+            &spl_token::id(),
+            mint,
+            account,
+            &authority.pubkey(),
+            &[],
+            amount,
+        )
+        .unwrap()],
+        Some(&program_context.payer.pubkey()),
+    );
+    transaction.sign(
+        &[&program_context.payer, authority],
+        program_context.last_blockhash,
+    );
+    program_context
+        .banks_client
+        .process_transaction(transaction)
+        .await?;
+    Ok(())
+}
 
-    create_sticker_factory(
-        &mut program_context,
-        &sticker_factory,
-        &sticker_factory_owner,
-    )
-    .await
-    .unwrap();
+pub async fn get_token_balance(program_context: &mut ProgramTestContext, pubkey: &Pubkey) -> u64 {
+    let account = get_account(program_context, pubkey).await;
+    spl_token::state::Account::unpack(&account.data)
+        .unwrap()
+        .amount
+}
 
-    let factory_info_data = get_account(&mut program_context, &sticker_factory.pubkey()).await;
-    let factory_info =
-        state::StickerFactory::try_from_slice(&factory_info_data.data.as_slice()).unwrap();
+pub async fn set_factory_fee(
+    program_context: &mut ProgramTestContext,
+    sticker_factory: &Pubkey,
+    owner: &Keypair,
+    treasury: &Pubkey,
+    fee_bps: u16,
+) -> Result<(), TransportError> {
+    let mut transaction =
+        Transaction::new_with_payer(
+            &[instruction::set_factory_fee(
+                &id(),
+                sticker_factory,
+                &owner.pubkey(),
+                treasury,
+                fee_bps,
+            )
+            .unwrap()],
+            Some(&program_context.payer.pubkey()),
+        );
+    transaction.sign(
+        &[&program_context.payer, owner],
+        program_context.last_blockhash,
+    );
+    program_context
+        .banks_client
+        .process_transaction(transaction)
+        .await?;
+    Ok(())
+}
 
-    let (base, _) =
-        Pubkey::find_program_address(&[&sticker_factory.pubkey().to_bytes()[..32]], &id());
-    let address_to_create = Pubkey::create_with_seed(
-        &base,
-        &format!(
+pub async fn list_sticker(
+    program_context: &mut ProgramTestContext,
+    listing: &Keypair,
+    listing_rent: u64,
+    sticker: &Pubkey,
+    nft_token: &Pubkey,
+    seller: &Keypair,
+    seller_token_acc: &Pubkey,
+    price: u64,
+) -> Result<Pubkey, TransportError> {
+    let (listing_authority, _) =
+        Pubkey::find_program_address(&[&listing.pubkey().to_bytes()[..32]], &id());
+
+    let mut transaction = Transaction::new_with_payer(
+        &[
+            system_instruction::create_account(
+                &program_context.payer.pubkey(),
+                &listing.pubkey(),
+                listing_rent,
+                state::Listing::LEN as u64,
+                &id(),
+            ),
+            instruction::list_sticker(
+                &id(),
+                &listing.pubkey(),
+                sticker,
+                nft_token,
+                &seller.pubkey(),
+                seller_token_acc,
+                &listing_authority,
+                price,
+            )
+            .unwrap(),
+        ],
+        Some(&program_context.payer.pubkey()),
+    );
+    transaction.sign(
+        &[&program_context.payer, listing, seller],
+        program_context.last_blockhash,
+    );
+    program_context
+        .banks_client
+        .process_transaction(transaction)
+        .await?;
+    Ok(listing_authority)
+}
+
+pub async fn cancel_listing(
+    program_context: &mut ProgramTestContext,
+    listing: &Pubkey,
+    seller: &Keypair,
+    nft_token: &Pubkey,
+    listing_authority: &Pubkey,
+) -> Result<(), TransportError> {
+    let mut transaction = Transaction::new_with_payer(
+        &[instruction::cancel_listing(
+            &id(),
+            listing,
+            &seller.pubkey(),
+            nft_token,
+            listing_authority,
+        )
+        .unwrap()],
+        Some(&program_context.payer.pubkey()),
+    );
+    transaction.sign(
+        &[&program_context.payer, seller],
+        program_context.last_blockhash,
+    );
+    program_context
+        .banks_client
+        .process_transaction(transaction)
+        .await?;
+    Ok(())
+}
+
+pub async fn buy_listing(
+    program_context: &mut ProgramTestContext,
+    listing: &Pubkey,
+    sticker: &Pubkey,
+    artist_account: &Pubkey,
+    artist_token_acc: &Pubkey,
+    seller: &Pubkey,
+    seller_token_acc: &Pubkey,
+    buyer_token_acc: &Pubkey,
+    buyer_transfer_authority: &Keypair,
+    nft_token: &Pubkey,
+    nft_token_owner: &Pubkey,
+    listing_authority: &Pubkey,
+) -> Result<(), TransportError> {
+    let mut transaction = Transaction::new_with_payer(
+        &[instruction::buy_listing(
+            &id(),
+            listing,
+            sticker,
+            artist_account,
+            artist_token_acc,
+            seller,
+            seller_token_acc,
+            buyer_token_acc,
+            &buyer_transfer_authority.pubkey(),
+            nft_token,
+            nft_token_owner,
+            listing_authority,
+        )
+        .unwrap()],
+        Some(&program_context.payer.pubkey()),
+    );
+    transaction.sign(
+        &[&program_context.payer, buyer_transfer_authority],
+        program_context.last_blockhash,
+    );
+    program_context
+        .banks_client
+        .process_transaction(transaction)
+        .await?;
+    Ok(())
+}
+
+pub async fn create_sticker_pack(
+    program_context: &mut ProgramTestContext,
+    sticker_pack: &Keypair,
+    sticker_pack_rent: u64,
+    creator: &Keypair,
+    stickers: &[Pubkey],
+    price: u64,
+) -> Result<(), TransportError> {
+    let mut transaction = Transaction::new_with_payer(
+        &[
+            system_instruction::create_account(
+                &program_context.payer.pubkey(),
+                &sticker_pack.pubkey(),
+                sticker_pack_rent,
+                state::StickerPack::LEN as u64,
+                &id(),
+            ),
+            instruction::create_sticker_pack(
+                &id(),
+                &sticker_pack.pubkey(),
+                &creator.pubkey(),
+                stickers,
+                price,
+            )
+            .unwrap(),
+        ],
+        Some(&program_context.payer.pubkey()),
+    );
+    transaction.sign(
+        &[&program_context.payer, sticker_pack, creator],
+        program_context.last_blockhash,
+    );
+    program_context
+        .banks_client
+        .process_transaction(transaction)
+        .await?;
+    Ok(())
+}
+
+pub async fn create_nft_token_accounts(
+    program_context: &mut ProgramTestContext,
+    nft_token: &Keypair,
+    nft_token_rent: u64,
+    nft_token_data_rent: u64,
+) -> Result<Pubkey, TransportError> {
+    let seed = "token_data";
+    let token_data =
+        Pubkey::create_with_seed(&nft_token.pubkey(), seed, &spl_nft_erc_721::id()).unwrap();
+
+    let mut transaction = Transaction::new_with_payer(
+        &[
+            system_instruction::create_account(
+                &program_context.payer.pubkey(),
+                &nft_token.pubkey(),
+                nft_token_rent,
+                spl_nft_erc_721::state::Token::LEN,
+                &spl_nft_erc_721::id(),
+            ),
+            system_instruction::create_account_with_seed(
+                &program_context.payer.pubkey(),
+                &token_data,
+                &nft_token.pubkey(),
+                seed,
+                nft_token_data_rent,
+                spl_nft_erc_721::state::TokenData::LEN,
+                &spl_nft_erc_721::id(),
+            ),
+        ],
+        Some(&program_context.payer.pubkey()),
+    );
+    transaction.sign(
+        &[&program_context.payer, nft_token],
+        program_context.last_blockhash,
+    );
+    program_context
+        .banks_client
+        .process_transaction(transaction)
+        .await?;
+    Ok(token_data)
+}
+
+pub async fn buy_sticker_pack(
+    program_context: &mut ProgramTestContext,
+    sticker_pack: &Pubkey,
+    artist_account: &Pubkey,
+    artist_token_acc: &Pubkey,
+    buyer_token_acc: &Pubkey,
+    buyer_transfer_authority: &Keypair,
+    nft_token_owner: &Pubkey,
+    sticker_factory: &Pubkey,
+    treasury_token_acc: &Pubkey,
+    stickers: &[instruction::PackStickerAccounts],
+) -> Result<(), TransportError> {
+    let mut transaction = Transaction::new_with_payer(
+        &[instruction::buy_sticker_pack(
+            &id(),
+            sticker_pack,
+            artist_account,
+            artist_token_acc,
+            buyer_token_acc,
+            &buyer_transfer_authority.pubkey(),
+            nft_token_owner,
+            sticker_factory,
+            treasury_token_acc,
+            stickers,
+        )
+        .unwrap()],
+        Some(&program_context.payer.pubkey()),
+    );
+    transaction.sign(
+        &[&program_context.payer, buyer_transfer_authority],
+        program_context.last_blockhash,
+    );
+    program_context
+        .banks_client
+        .process_transaction(transaction)
+        .await?;
+    Ok(())
+}
+
+pub async fn set_sticker_sale(
+    program_context: &mut ProgramTestContext,
+    sticker: &Pubkey,
+    creator: &Keypair,
+    sale: state::SaleSchedule,
+) -> Result<(), TransportError> {
+    let mut transaction = Transaction::new_with_payer(
+        &[instruction::set_sticker_sale(&id(), sticker, &creator.pubkey(), sale).unwrap()],
+        Some(&program_context.payer.pubkey()),
+    );
+    transaction.sign(
+        &[&program_context.payer, creator],
+        program_context.last_blockhash,
+    );
+    program_context
+        .banks_client
+        .process_transaction(transaction)
+        .await?;
+    Ok(())
+}
+
+pub async fn set_sticker_status(
+    program_context: &mut ProgramTestContext,
+    instruction: Instruction,
+    creator: &Keypair,
+) -> Result<(), TransportError> {
+    let mut transaction =
+        Transaction::new_with_payer(&[instruction], Some(&program_context.payer.pubkey()));
+    transaction.sign(
+        &[&program_context.payer, creator],
+        program_context.last_blockhash,
+    );
+    program_context
+        .banks_client
+        .process_transaction(transaction)
+        .await?;
+    Ok(())
+}
+
+pub async fn airdrop_sticker(
+    program_context: &mut ProgramTestContext,
+    sticker: &Pubkey,
+    creator: &Keypair,
+    mint_authority: &Pubkey,
+    nft_token_mint: &Pubkey,
+    recipients: &[instruction::AirdropAccounts],
+) -> Result<(), TransportError> {
+    let mut transaction = Transaction::new_with_payer(
+        &[instruction::airdrop_sticker(
+            &id(),
+            sticker,
+            &creator.pubkey(),
+            mint_authority,
+            nft_token_mint,
+            recipients,
+        )
+        .unwrap()],
+        Some(&program_context.payer.pubkey()),
+    );
+    transaction.sign(
+        &[&program_context.payer, creator],
+        program_context.last_blockhash,
+    );
+    program_context
+        .banks_client
+        .process_transaction(transaction)
+        .await?;
+    Ok(())
+}
+
+pub async fn migrate_account(
+    program_context: &mut ProgramTestContext,
+    account: &Pubkey,
+    sticker_factory: &Pubkey,
+    input: instruction::MigrateAccountInput,
+) -> Result<(), TransportError> {
+    let mut transaction = Transaction::new_with_payer(
+        &[instruction::migrate_account(
+            &id(),
+            &program_context.payer.pubkey(),
+            account,
+            sticker_factory,
+            input,
+        )
+        .unwrap()],
+        Some(&program_context.payer.pubkey()),
+    );
+    transaction.sign(&[&program_context.payer], program_context.last_blockhash);
+    program_context
+        .banks_client
+        .process_transaction(transaction)
+        .await?;
+    Ok(())
+}
+
+pub fn program_error(error: TransportError) -> Option<error::StickerProgramError> {
+    match error {
+        TransportError::TransactionError(TransactionError::InstructionError(
+            _,
+            InstructionError::Custom(code),
+        )) => num_traits::FromPrimitive::from_u32(code),
+        _ => None,
+    }
+}
+
+/// Put account with given data owned by other program, as attacker could create it
+pub fn forge_account(program_context: &mut ProgramTestContext, data: Vec<u8>) -> Pubkey {
+    let key = Pubkey::new_unique();
+    program_context.set_account(
+        &key,
+        &Account {
+            lamports: 1_000_000_000,
+            data,
+            owner: Pubkey::new_unique(),
+            ..Account::default()
+        }
+        .into(),
+    );
+    key
+}
+
+/// Sticker factory with registered artist and created sticker
+pub struct StickerSetup {
+    pub sticker_factory: Keypair,
+    pub sticker_factory_owner: Keypair,
+    pub artist_key: Pubkey,
+    pub artist_user: Keypair,
+    pub artist_token_acc: Keypair,
+    pub sticker_key: Pubkey,
+    pub nft_mint: Keypair,
+    pub mint_auth: Pubkey,
+}
+
+pub async fn setup_sticker(
+    program_context: &mut ProgramTestContext,
+    sticker_data: instruction::CreateNewSticker,
+) -> StickerSetup {
+    let sticker_factory = Keypair::new();
+    let sticker_factory_owner = Keypair::new();
+
+    let rent = program_context.banks_client.get_rent().await.unwrap();
+    let sticker_factory_min_rent = rent.minimum_balance(state::StickerFactory::LEN);
+    let token_account_rent = rent.minimum_balance(spl_token::state::Account::LEN);
+    let nft_mint_rent = rent.minimum_balance(spl_nft_erc_721::state::Mint::LEN as usize);
+
+    create_account(
+        program_context,
+        &sticker_factory,
+        sticker_factory_min_rent,
+        state::StickerFactory::LEN as u64,
+        &id(),
+    )
+    .await
+    .unwrap();
+
+    create_sticker_factory(program_context, &sticker_factory, &sticker_factory_owner)
+        .await
+        .unwrap();
+
+    let (base, _) =
+        Pubkey::find_program_address(&[&sticker_factory.pubkey().to_bytes()[..32]], &id());
+    let artist_key = Pubkey::create_with_seed(
+        &base,
+        &format!("{:?}{}", 0, processor::Processor::ARTIST_SEED),
+        &id(),
+    )
+    .unwrap();
+    create_program_account(
+        program_context,
+        &sticker_factory.pubkey(),
+        &base,
+        &artist_key,
+        instruction::AddressType::Artist,
+    )
+    .await
+    .unwrap();
+
+    let artist_user = Keypair::new();
+    let artist_token_acc = Keypair::new();
+
+    create_token_account(
+        program_context,
+        &artist_token_acc,
+        token_account_rent,
+        &sticker_data.payment_mint,
+        &artist_user.pubkey(),
+    )
+    .await
+    .unwrap();
+
+    register_new_artist(
+        program_context,
+        &artist_user.pubkey(),
+        &artist_token_acc.pubkey(),
+        &artist_key,
+        &sticker_factory_owner,
+        &sticker_factory.pubkey(),
+        instruction::RegisterArtist {
+            name: [1; 32],
+            signature: [2; 256],
+            description: [3; 256],
+        },
+    )
+    .await
+    .unwrap();
+
+    let sticker_key = Pubkey::create_with_seed(
+        &base,
+        &format!("{:?}{}", 0, processor::Processor::STICKER_SEED),
+        &id(),
+    )
+    .unwrap();
+    create_program_account(
+        program_context,
+        &sticker_factory.pubkey(),
+        &base,
+        &sticker_key,
+        instruction::AddressType::Sticker,
+    )
+    .await
+    .unwrap();
+
+    let nft_mint = Keypair::new();
+    create_account(
+        program_context,
+        &nft_mint,
+        nft_mint_rent,
+        spl_nft_erc_721::state::Mint::LEN,
+        &spl_nft_erc_721::id(),
+    )
+    .await
+    .unwrap();
+
+    let (mint_auth, _) = Pubkey::find_program_address(&[&sticker_key.to_bytes()[..32]], &id());
+
+    create_new_sticker(
+        program_context,
+        &sticker_key,
+        &sticker_factory.pubkey(),
+        &nft_mint.pubkey(),
+        &artist_key,
+        &artist_user,
+        &mint_auth,
+        sticker_data,
+    )
+    .await
+    .unwrap();
+
+    StickerSetup {
+        sticker_factory,
+        sticker_factory_owner,
+        artist_key,
+        artist_user,
+        artist_token_acc,
+        sticker_key,
+        nft_mint,
+        mint_auth,
+    }
+}
+
+#[tokio::test]
+async fn test_create_account_instruction() {
+    let mut program_context = program_test().start_with_context().await;
+
+    let sticker_factory = Keypair::new();
+    let sticker_factory_owner = Keypair::new();
+
+    let rent = program_context.banks_client.get_rent().await.unwrap();
+    let sticker_factory_min_rent = rent.minimum_balance(state::StickerFactory::LEN);
+
+    create_account(
+        &mut program_context,
+        &sticker_factory,
+        sticker_factory_min_rent,
+        state::StickerFactory::LEN as u64,
+        &id(),
+    )
+    .await
+    .unwrap();
+
+    create_sticker_factory(
+        &mut program_context,
+        &sticker_factory,
+        &sticker_factory_owner,
+    )
+    .await
+    .unwrap();
+
+    let sticker_factory_data = get_account(&mut program_context, &sticker_factory.pubkey()).await;
+    let sticker_factory =
+        state::StickerFactory::try_from_slice(&sticker_factory_data.data.as_slice()).unwrap();
+
+    assert!(sticker_factory.is_initialized());
+}
+
+#[tokio::test]
+async fn test_register_artist_instruction() {
+    let mut program_context = program_test().start_with_context().await;
+
+    let sticker_factory = Keypair::new();
+    let sticker_factory_owner = Keypair::new();
+
+    let rent = program_context.banks_client.get_rent().await.unwrap();
+    let sticker_factory_min_rent = rent.minimum_balance(state::StickerFactory::LEN);
+    let token_account_rent = rent.minimum_balance(spl_token::state::Account::LEN);
+
+    create_account(
+        &mut program_context,
+        &sticker_factory,
+        sticker_factory_min_rent,
+        state::StickerFactory::LEN as u64,
+        &id(),
+    )
+    .await
+    .unwrap();
+
+    create_sticker_factory(
+        &mut program_context,
+        &sticker_factory,
+        &sticker_factory_owner,
+    )
+    .await
+    .unwrap();
+
+    let factory_info_data = get_account(&mut program_context, &sticker_factory.pubkey()).await;
+    let factory_info =
+        state::StickerFactory::try_from_slice(&factory_info_data.data.as_slice()).unwrap();
+
+    let (base, _) =
+        Pubkey::find_program_address(&[&sticker_factory.pubkey().to_bytes()[..32]], &id());
+    let address_to_create = Pubkey::create_with_seed(
+        &base,
+        &format!(
+            "{:?}{}",
+            factory_info.artist_count,
+            processor::Processor::ARTIST_SEED
+        ),
+        &id(),
+    )
+    .unwrap();
+    create_program_account(
+        &mut program_context,
+        &sticker_factory.pubkey(),
+        &base,
+        &address_to_create,
+        instruction::AddressType::Artist,
+    )
+    .await
+    .unwrap();
+
+    let artist_user = Keypair::new();
+    let artist_token_acc = Keypair::new();
+    let owner = Keypair::new();
+
+    create_token_account(
+        &mut program_context,
+        &artist_token_acc,
+        token_account_rent,
+        &spl_token::native_mint::id(),
+        &owner.pubkey(),
+    )
+    .await
+    .unwrap();
+
+    let artist_data = instruction::RegisterArtist {
+        name: [1; 32],
+        signature: [2; 256],
+        description: [3; 256],
+    };
+
+    let sticker_factory_data = get_account(&mut program_context, &sticker_factory.pubkey()).await;
+    let sticker_factory_acc =
+        state::StickerFactory::try_from_slice(&sticker_factory_data.data.as_slice()).unwrap();
+    let artist_index_before = sticker_factory_acc.artist_count;
+
+    register_new_artist(
+        &mut program_context,
+        &artist_user.pubkey(),
+        &artist_token_acc.pubkey(),
+        &address_to_create,
+        &sticker_factory_owner,
+        &sticker_factory.pubkey(),
+        artist_data,
+    )
+    .await
+    .unwrap();
+
+    let artist_acc_data = get_account(&mut program_context, &address_to_create).await;
+    let artist_acc = state::Artist::try_from_slice(&artist_acc_data.data.as_slice()).unwrap();
+
+    let sticker_factory_data = get_account(&mut program_context, &sticker_factory.pubkey()).await;
+    let sticker_factory_acc =
+        state::StickerFactory::try_from_slice(&sticker_factory_data.data.as_slice()).unwrap();
+
+    assert!(artist_acc.is_initialized());
+    assert_eq!(artist_acc.user, artist_user.pubkey());
+    assert_eq!(artist_acc.user_token_acc, artist_token_acc.pubkey());
+    assert_eq!(artist_acc.sticker_factory, sticker_factory.pubkey());
+    assert_eq!(sticker_factory_acc.artist_count, artist_index_before + 1);
+}
+
+#[tokio::test]
+async fn test_create_new_sticker_instruction() {
+    let mut program_context = program_test().start_with_context().await;
+
+    let sticker_factory = Keypair::new();
+    let sticker_factory_owner = Keypair::new();
+
+    let rent = program_context.banks_client.get_rent().await.unwrap();
+    let sticker_factory_min_rent = rent.minimum_balance(state::StickerFactory::LEN);
+    let token_account_rent = rent.minimum_balance(spl_token::state::Account::LEN);
+    let nft_mint_rent = rent.minimum_balance(spl_nft_erc_721::state::Mint::LEN as usize);
+
+    create_account(
+        &mut program_context,
+        &sticker_factory,
+        sticker_factory_min_rent,
+        state::StickerFactory::LEN as u64,
+        &id(),
+    )
+    .await
+    .unwrap();
+
+    create_sticker_factory(
+        &mut program_context,
+        &sticker_factory,
+        &sticker_factory_owner,
+    )
+    .await
+    .unwrap();
+
+    let factory_info_data = get_account(&mut program_context, &sticker_factory.pubkey()).await;
+    let factory_info =
+        state::StickerFactory::try_from_slice(&factory_info_data.data.as_slice()).unwrap();
+
+    let (base, _) =
+        Pubkey::find_program_address(&[&sticker_factory.pubkey().to_bytes()[..32]], &id());
+    let artist_key = Pubkey::create_with_seed(
+        &base,
+        &format!(
+            "{:?}{}",
+            factory_info.artist_count,
+            processor::Processor::ARTIST_SEED
+        ),
+        &id(),
+    )
+    .unwrap();
+    create_program_account(
+        &mut program_context,
+        &sticker_factory.pubkey(),
+        &base,
+        &artist_key,
+        instruction::AddressType::Artist,
+    )
+    .await
+    .unwrap();
+
+    let artist_user = Keypair::new();
+    let artist_token_acc = Keypair::new();
+    let owner = Keypair::new();
+
+    create_token_account(
+        &mut program_context,
+        &artist_token_acc,
+        token_account_rent,
+        &spl_token::native_mint::id(),
+        &owner.pubkey(),
+    )
+    .await
+    .unwrap();
+
+    let artist_data = instruction::RegisterArtist {
+        name: [1; 32],
+        signature: [2; 256],
+        description: [3; 256],
+    };
+    register_new_artist(
+        &mut program_context,
+        &artist_user.pubkey(),
+        &artist_token_acc.pubkey(),
+        &artist_key,
+        &sticker_factory_owner,
+        &sticker_factory.pubkey(),
+        artist_data,
+    )
+    .await
+    .unwrap();
+
+    let (base, _) =
+        Pubkey::find_program_address(&[&sticker_factory.pubkey().to_bytes()[..32]], &id());
+    let sticker_key = Pubkey::create_with_seed(
+        &base,
+        &format!(
+            "{:?}{}",
+            factory_info.sticker_count,
+            processor::Processor::STICKER_SEED
+        ),
+        &id(),
+    )
+    .unwrap();
+    create_program_account(
+        &mut program_context,
+        &sticker_factory.pubkey(),
+        &base,
+        &sticker_key,
+        instruction::AddressType::Sticker,
+    )
+    .await
+    .unwrap();
+
+    let nft_mint = Keypair::new();
+
+    // create nft mint account
+    create_account(
+        &mut program_context,
+        &nft_mint,
+        nft_mint_rent,
+        spl_nft_erc_721::state::Mint::LEN,
+        &spl_nft_erc_721::id(),
+    )
+    .await
+    .unwrap();
+
+    let (mint_auth, _) = Pubkey::find_program_address(&[&sticker_key.to_bytes()[..32]], &id());
+
+    let sticker_data = instruction::CreateNewSticker {
+        max_supply: 1000,
+        price: 100,
+        uri: [5; 256],
+        symbol: [7; 8],
+        name: [6; 32],
+        payment_mint: spl_token::native_mint::id(),
+        royalty_bps: 0,
+    };
+
+    let sticker_factory_data = get_account(&mut program_context, &sticker_factory.pubkey()).await;
+    let sticker_factory_acc =
+        state::StickerFactory::try_from_slice(&sticker_factory_data.data.as_slice()).unwrap();
+    let sticker_index_before = sticker_factory_acc.sticker_count;
+
+    // artist is paid with wrapped SOL
+    assert_eq!(
+        create_new_sticker(
+            &mut program_context,
+            &sticker_key,
+            &sticker_factory.pubkey(),
+            &nft_mint.pubkey(),
+            &artist_key,
+            &artist_user,
+            &mint_auth,
+            instruction::CreateNewSticker {
+                payment_mint: Pubkey::new_unique(),
+                ..sticker_data.clone()
+            },
+        )
+        .await
+        .map_err(program_error),
+        Err(Some(error::StickerProgramError::WrongTokenMint))
+    );
+
+    // artist approved in other factory is not approved in this one
+    let other = setup_sticker(&mut program_context, sticker_data.clone()).await;
+    assert_eq!(
+        create_new_sticker(
+            &mut program_context,
+            &sticker_key,
+            &sticker_factory.pubkey(),
+            &nft_mint.pubkey(),
+            &other.artist_key,
+            &other.artist_user,
+            &mint_auth,
+            sticker_data.clone(),
+        )
+        .await
+        .map_err(program_error),
+        Err(Some(error::StickerProgramError::WrongStickerFactory))
+    );
+
+    create_new_sticker(
+        &mut program_context,
+        &sticker_key,
+        &sticker_factory.pubkey(),
+        &nft_mint.pubkey(),
+        &artist_key,
+        &artist_user,
+        &mint_auth,
+        sticker_data.clone(),
+    )
+    .await
+    .unwrap();
+
+    let sticker_acc_data = get_account(&mut program_context, &sticker_key).await;
+    let sticker_acc = state::Sticker::try_from_slice(&sticker_acc_data.data.as_slice()).unwrap();
+
+    assert!(sticker_acc.is_initialized());
+    assert_eq!(sticker_acc.creator, artist_user.pubkey());
+    assert_eq!(sticker_acc.supply, 0);
+    assert_eq!(sticker_acc.max_supply, sticker_data.max_supply);
+    assert_eq!(sticker_acc.price, sticker_data.price);
+    assert_eq!(sticker_acc.mint, nft_mint.pubkey());
+    assert_eq!(sticker_acc.uri, sticker_data.uri);
+    assert_eq!(sticker_acc.payment_mint, sticker_data.payment_mint);
+    assert_eq!(sticker_acc.royalty_bps, sticker_data.royalty_bps);
+    assert_eq!(sticker_acc.sticker_factory, sticker_factory.pubkey());
+
+    let sticker_factory_data = get_account(&mut program_context, &sticker_factory.pubkey()).await;
+    let sticker_factory_acc =
+        state::StickerFactory::try_from_slice(&sticker_factory_data.data.as_slice()).unwrap();
+
+    assert_eq!(sticker_factory_acc.sticker_count, sticker_index_before + 1);
+}
+
+#[tokio::test]
+async fn test_buy_sticker_instruction() {
+    let mut program_context = program_test().start_with_context().await;
+
+    let sticker_factory = Keypair::new();
+    let sticker_factory_owner = Keypair::new();
+
+    let rent = program_context.banks_client.get_rent().await.unwrap();
+    let sticker_factory_min_rent = rent.minimum_balance(state::StickerFactory::LEN);
+    let token_account_rent = rent.minimum_balance(spl_token::state::Account::LEN);
+    let nft_mint_rent = rent.minimum_balance(spl_nft_erc_721::state::Mint::LEN as usize);
+    let nft_token_acc_rent = rent.minimum_balance(spl_nft_erc_721::state::Token::LEN as usize);
+    let nft_token_data_acc_rent =
+        rent.minimum_balance(spl_nft_erc_721::state::TokenData::LEN as usize);
+
+    create_account(
+        &mut program_context,
+        &sticker_factory,
+        sticker_factory_min_rent,
+        state::StickerFactory::LEN as u64,
+        &id(),
+    )
+    .await
+    .unwrap();
+
+    create_sticker_factory(
+        &mut program_context,
+        &sticker_factory,
+        &sticker_factory_owner,
+    )
+    .await
+    .unwrap();
+
+    let factory_info_data = get_account(&mut program_context, &sticker_factory.pubkey()).await;
+    let factory_info =
+        state::StickerFactory::try_from_slice(&factory_info_data.data.as_slice()).unwrap();
+
+    let (base, _) =
+        Pubkey::find_program_address(&[&sticker_factory.pubkey().to_bytes()[..32]], &id());
+    let artist_key = Pubkey::create_with_seed(
+        &base,
+        &format!(
+            "{:?}{}",
+            factory_info.artist_count,
+            processor::Processor::ARTIST_SEED
+        ),
+        &id(),
+    )
+    .unwrap();
+    create_program_account(
+        &mut program_context,
+        &sticker_factory.pubkey(),
+        &base,
+        &artist_key,
+        instruction::AddressType::Artist,
+    )
+    .await
+    .unwrap();
+
+    let artist_user = Keypair::new();
+    let artist_token_acc = Keypair::new();
+    let owner = Keypair::new();
+
+    create_token_account(
+        &mut program_context,
+        &artist_token_acc,
+        token_account_rent,
+        &spl_token::native_mint::id(),
+        &owner.pubkey(),
+    )
+    .await
+    .unwrap();
+
+    let artist_data = instruction::RegisterArtist {
+        name: [1; 32],
+        signature: [2; 256],
+        description: [3; 256],
+    };
+    register_new_artist(
+        &mut program_context,
+        &artist_user.pubkey(),
+        &artist_token_acc.pubkey(),
+        &artist_key,
+        &sticker_factory_owner,
+        &sticker_factory.pubkey(),
+        artist_data,
+    )
+    .await
+    .unwrap();
+
+    let (base, _) =
+        Pubkey::find_program_address(&[&sticker_factory.pubkey().to_bytes()[..32]], &id());
+    let sticker_key = Pubkey::create_with_seed(
+        &base,
+        &format!(
+            "{:?}{}",
+            factory_info.sticker_count,
+            processor::Processor::STICKER_SEED
+        ),
+        &id(),
+    )
+    .unwrap();
+    create_program_account(
+        &mut program_context,
+        &sticker_factory.pubkey(),
+        &base,
+        &sticker_key,
+        instruction::AddressType::Sticker,
+    )
+    .await
+    .unwrap();
+
+    let nft_mint = Keypair::new();
+
+    // create nft mint account
+    create_account(
+        &mut program_context,
+        &nft_mint,
+        nft_mint_rent,
+        spl_nft_erc_721::state::Mint::LEN,
+        &spl_nft_erc_721::id(),
+    )
+    .await
+    .unwrap();
+
+    let (mint_auth, _) = Pubkey::find_program_address(&[&sticker_key.to_bytes()[..32]], &id());
+
+    let sticker_price = 100;
+    let url = url::Url::parse("ipfs://bafybeiemxf5abjwjbikoz4mc3a3dla6ual3jsgpdr4cjr3oz3evfyavhwq/wiki/Vincent_van_Gogh.html").unwrap();
+    let mut uri = [0; 256];
+    let url_bytes = url.as_str().as_bytes();
+    let (left, _) = uri.split_at_mut(url_bytes.len());
+    left.copy_from_slice(url_bytes);
+
+    let sticker_data = instruction::CreateNewSticker {
+        max_supply: 1000,
+        price: sticker_price,
+        uri,
+        symbol: [7; 8],
+        name: [6; 32],
+        payment_mint: spl_token::native_mint::id(),
+        royalty_bps: 0,
+    };
+
+    create_new_sticker(
+        &mut program_context,
+        &sticker_key,
+        &sticker_factory.pubkey(),
+        &nft_mint.pubkey(),
+        &artist_key,
+        &artist_user,
+        &mint_auth,
+        sticker_data,
+    )
+    .await
+    .unwrap();
+
+    let user_token_acc = Keypair::new();
+    let user_owner = Keypair::new();
+
+    create_token_account(
+        &mut program_context,
+        &user_token_acc,
+        token_account_rent + sticker_price,
+        &spl_token::native_mint::id(),
+        &user_owner.pubkey(),
+    )
+    .await
+    .unwrap();
+
+    let user_nft_token_acc = Keypair::new();
+    let user_nft_token_owner = Keypair::new();
+
+    let user_token_account_data = get_account(&mut program_context, &user_token_acc.pubkey()).await;
+    let user_token_acc_balance_before = user_token_account_data.lamports;
+
+    let artist_token_account_data =
+        get_account(&mut program_context, &artist_token_acc.pubkey()).await;
+    let artist_token_acc_balance_before = artist_token_account_data.lamports;
+
+    let sticker_account_data = get_account(&mut program_context, &sticker_key).await;
+    let sticker_acc =
+        state::Sticker::try_from_slice(&sticker_account_data.data.as_slice()).unwrap();
+    let sticker_supply_before = sticker_acc.supply;
+
+    let token_data = buy_sticker(
+        &mut program_context,
+        &sticker_key,
+        &artist_key,
+        &artist_token_acc.pubkey(),
+        &user_token_acc.pubkey(),
+        &user_owner,
+        &mint_auth,
+        &user_nft_token_acc,
+        nft_token_acc_rent,
+        nft_token_data_acc_rent,
+        &nft_mint.pubkey(),
+        &user_nft_token_owner.pubkey(),
+        &sticker_factory.pubkey(),
+        // no fee is set, treasury is not used
+        &artist_token_acc.pubkey(),
+    )
+    .await
+    .unwrap();
+
+    let user_nft_token_account_data =
+        get_account(&mut program_context, &user_nft_token_acc.pubkey()).await;
+    let user_nft_token_account = spl_nft_erc_721::state::Token::deserialize(
+        &mut &user_nft_token_account_data.data.as_slice()[..],
+    )
+    .unwrap();
+
+    assert_eq!(user_nft_token_account.mint, nft_mint.pubkey());
+    assert_eq!(user_nft_token_account.owner, user_nft_token_owner.pubkey());
+
+    let user_nft_token_data_account = get_account(&mut program_context, &token_data).await;
+    let user_nft_token_data_account = spl_nft_erc_721::state::TokenData::deserialize(
+        &mut &user_nft_token_data_account.data.as_slice()[..],
+    )
+    .unwrap();
+
+    assert_eq!(
+        user_nft_token_data_account.token,
+        user_nft_token_acc.pubkey()
+    );
+    assert_eq!(
+        url_bytes,
+        &user_nft_token_data_account.uri[0..url_bytes.len()]
+    );
+
+    let user_token_account_data = get_account(&mut program_context, &user_token_acc.pubkey()).await;
+    let artist_token_account_data =
+        get_account(&mut program_context, &artist_token_acc.pubkey()).await;
+    let sticker_account_data = get_account(&mut program_context, &sticker_key).await;
+    let sticker_acc =
+        state::Sticker::try_from_slice(&sticker_account_data.data.as_slice()).unwrap();
+
+    assert_eq!(
+        user_token_account_data.lamports,
+        user_token_acc_balance_before - sticker_price
+    );
+    assert_eq!(
+        artist_token_account_data.lamports,
+        artist_token_acc_balance_before + sticker_price
+    );
+    assert_eq!(sticker_acc.supply, sticker_supply_before + 1);
+}
+
+#[tokio::test]
+async fn test_change_sticker_price_instruction() {
+    let mut program_context = program_test().start_with_context().await;
+
+    let sticker_factory = Keypair::new();
+    let sticker_factory_owner = Keypair::new();
+
+    let rent = program_context.banks_client.get_rent().await.unwrap();
+    let sticker_factory_min_rent = rent.minimum_balance(state::StickerFactory::LEN);
+    let token_account_rent = rent.minimum_balance(spl_token::state::Account::LEN);
+    let nft_mint_rent = rent.minimum_balance(spl_nft_erc_721::state::Mint::LEN as usize);
+
+    create_account(
+        &mut program_context,
+        &sticker_factory,
+        sticker_factory_min_rent,
+        state::StickerFactory::LEN as u64,
+        &id(),
+    )
+    .await
+    .unwrap();
+
+    create_sticker_factory(
+        &mut program_context,
+        &sticker_factory,
+        &sticker_factory_owner,
+    )
+    .await
+    .unwrap();
+
+    let factory_info_data = get_account(&mut program_context, &sticker_factory.pubkey()).await;
+    let factory_info =
+        state::StickerFactory::try_from_slice(&factory_info_data.data.as_slice()).unwrap();
+
+    let (base, _) =
+        Pubkey::find_program_address(&[&sticker_factory.pubkey().to_bytes()[..32]], &id());
+    let artist_key = Pubkey::create_with_seed(
+        &base,
+        &format!(
             "{:?}{}",
             factory_info.artist_count,
             processor::Processor::ARTIST_SEED
@@ -385,638 +1743,1957 @@ async fn test_register_artist_instruction() {
         &id(),
     )
     .unwrap();
-    create_program_account(
+    create_program_account(
+        &mut program_context,
+        &sticker_factory.pubkey(),
+        &base,
+        &artist_key,
+        instruction::AddressType::Artist,
+    )
+    .await
+    .unwrap();
+
+    let artist_user = Keypair::new();
+    let artist_token_acc = Keypair::new();
+    let owner = Keypair::new();
+
+    create_token_account(
+        &mut program_context,
+        &artist_token_acc,
+        token_account_rent,
+        &spl_token::native_mint::id(),
+        &owner.pubkey(),
+    )
+    .await
+    .unwrap();
+
+    let artist_data = instruction::RegisterArtist {
+        name: [1; 32],
+        signature: [2; 256],
+        description: [3; 256],
+    };
+    register_new_artist(
+        &mut program_context,
+        &artist_user.pubkey(),
+        &artist_token_acc.pubkey(),
+        &artist_key,
+        &sticker_factory_owner,
+        &sticker_factory.pubkey(),
+        artist_data,
+    )
+    .await
+    .unwrap();
+
+    let (base, _) =
+        Pubkey::find_program_address(&[&sticker_factory.pubkey().to_bytes()[..32]], &id());
+    let sticker_key = Pubkey::create_with_seed(
+        &base,
+        &format!(
+            "{:?}{}",
+            factory_info.sticker_count,
+            processor::Processor::STICKER_SEED
+        ),
+        &id(),
+    )
+    .unwrap();
+    create_program_account(
+        &mut program_context,
+        &sticker_factory.pubkey(),
+        &base,
+        &sticker_key,
+        instruction::AddressType::Sticker,
+    )
+    .await
+    .unwrap();
+
+    let nft_mint = Keypair::new();
+
+    // create nft mint account
+    create_account(
+        &mut program_context,
+        &nft_mint,
+        nft_mint_rent,
+        spl_nft_erc_721::state::Mint::LEN,
+        &spl_nft_erc_721::id(),
+    )
+    .await
+    .unwrap();
+
+    let (mint_auth, _) = Pubkey::find_program_address(&[&sticker_key.to_bytes()[..32]], &id());
+
+    let sticker_price = 100;
+    let url = url::Url::parse("ipfs://bafybeiemxf5abjwjbikoz4mc3a3dla6ual3jsgpdr4cjr3oz3evfyavhwq/wiki/Vincent_van_Gogh.html").unwrap();
+    let mut uri = [0; 256];
+    let url_bytes = url.as_str().as_bytes();
+    let (left, _) = uri.split_at_mut(url_bytes.len());
+    left.copy_from_slice(url_bytes);
+
+    let sticker_data = instruction::CreateNewSticker {
+        max_supply: 1000,
+        price: sticker_price,
+        uri,
+        symbol: [7; 8],
+        name: [6; 32],
+        payment_mint: spl_token::native_mint::id(),
+        royalty_bps: 0,
+    };
+
+    create_new_sticker(
+        &mut program_context,
+        &sticker_key,
+        &sticker_factory.pubkey(),
+        &nft_mint.pubkey(),
+        &artist_key,
+        &artist_user,
+        &mint_auth,
+        sticker_data,
+    )
+    .await
+    .unwrap();
+
+    let new_price = 777;
+
+    change_sticker_price(&mut program_context, &sticker_key, &artist_user, new_price)
+        .await
+        .unwrap();
+
+    let sticker_account_data = get_account(&mut program_context, &sticker_key).await;
+    let sticker_acc =
+        state::Sticker::try_from_slice(&sticker_account_data.data.as_slice()).unwrap();
+
+    assert_eq!(sticker_acc.price, new_price);
+}
+
+#[tokio::test]
+async fn test_factory_ownership_and_curators() {
+    let mut program_context = program_test().start_with_context().await;
+
+    let sticker_factory = Keypair::new();
+    let sticker_factory_owner = Keypair::new();
+    let new_owner = Keypair::new();
+    let curator = Keypair::new();
+
+    let rent = program_context.banks_client.get_rent().await.unwrap();
+    let sticker_factory_min_rent = rent.minimum_balance(state::StickerFactory::LEN);
+    let token_account_rent = rent.minimum_balance(spl_token::state::Account::LEN);
+
+    create_account(
+        &mut program_context,
+        &sticker_factory,
+        sticker_factory_min_rent,
+        state::StickerFactory::LEN as u64,
+        &id(),
+    )
+    .await
+    .unwrap();
+
+    create_sticker_factory(
+        &mut program_context,
+        &sticker_factory,
+        &sticker_factory_owner,
+    )
+    .await
+    .unwrap();
+
+    add_curator(
+        &mut program_context,
+        &sticker_factory.pubkey(),
+        &sticker_factory_owner,
+        &curator.pubkey(),
+    )
+    .await
+    .unwrap();
+
+    let (base, _) =
+        Pubkey::find_program_address(&[&sticker_factory.pubkey().to_bytes()[..32]], &id());
+    let artist_key = |index: u64| {
+        Pubkey::create_with_seed(
+            &base,
+            &format!("{:?}{}", index, processor::Processor::ARTIST_SEED),
+            &id(),
+        )
+        .unwrap()
+    };
+
+    let artist_token_acc = Keypair::new();
+    create_token_account(
+        &mut program_context,
+        &artist_token_acc,
+        token_account_rent,
+        &spl_token::native_mint::id(),
+        &Keypair::new().pubkey(),
+    )
+    .await
+    .unwrap();
+
+    let artist_data = instruction::RegisterArtist {
+        name: [1; 32],
+        signature: [2; 256],
+        description: [3; 256],
+    };
+
+    create_program_account(
+        &mut program_context,
+        &sticker_factory.pubkey(),
+        &base,
+        &artist_key(0),
+        instruction::AddressType::Artist,
+    )
+    .await
+    .unwrap();
+
+    register_new_artist(
+        &mut program_context,
+        &Keypair::new().pubkey(),
+        &artist_token_acc.pubkey(),
+        &artist_key(0),
+        &curator,
+        &sticker_factory.pubkey(),
+        artist_data.clone(),
+    )
+    .await
+    .unwrap();
+
+    transfer_factory_ownership(
+        &mut program_context,
+        &sticker_factory.pubkey(),
+        &sticker_factory_owner,
+        &new_owner,
+    )
+    .await
+    .unwrap();
+
+    assert!(add_curator(
+        &mut program_context,
+        &sticker_factory.pubkey(),
+        &sticker_factory_owner,
+        &Keypair::new().pubkey(),
+    )
+    .await
+    .is_err());
+
+    remove_curator(
+        &mut program_context,
+        &sticker_factory.pubkey(),
+        &new_owner,
+        &curator.pubkey(),
+    )
+    .await
+    .unwrap();
+
+    let sticker_factory_data = get_account(&mut program_context, &sticker_factory.pubkey()).await;
+    let sticker_factory_acc =
+        state::StickerFactory::try_from_slice(&sticker_factory_data.data.as_slice()).unwrap();
+
+    assert_eq!(sticker_factory_acc.owner, new_owner.pubkey());
+    assert_eq!(sticker_factory_acc.artist_count, 1);
+    assert!(!sticker_factory_acc.is_curator(&curator.pubkey()));
+
+    create_program_account(
+        &mut program_context,
+        &sticker_factory.pubkey(),
+        &base,
+        &artist_key(1),
+        instruction::AddressType::Artist,
+    )
+    .await
+    .unwrap();
+
+    assert!(register_new_artist(
+        &mut program_context,
+        &Keypair::new().pubkey(),
+        &artist_token_acc.pubkey(),
+        &artist_key(1),
+        &curator,
+        &sticker_factory.pubkey(),
+        artist_data,
+    )
+    .await
+    .is_err());
+}
+
+#[tokio::test]
+async fn test_apply_approve_reject_artist() {
+    let mut program_context = program_test().start_with_context().await;
+
+    let sticker_factory = Keypair::new();
+    let sticker_factory_owner = Keypair::new();
+
+    let rent = program_context.banks_client.get_rent().await.unwrap();
+    let sticker_factory_min_rent = rent.minimum_balance(state::StickerFactory::LEN);
+    let token_account_rent = rent.minimum_balance(spl_token::state::Account::LEN);
+    let artist_rent = rent.minimum_balance(state::Artist::LEN);
+
+    create_account(
+        &mut program_context,
+        &sticker_factory,
+        sticker_factory_min_rent,
+        state::StickerFactory::LEN as u64,
+        &id(),
+    )
+    .await
+    .unwrap();
+
+    create_sticker_factory(
+        &mut program_context,
+        &sticker_factory,
+        &sticker_factory_owner,
+    )
+    .await
+    .unwrap();
+
+    let (base, _) =
+        Pubkey::find_program_address(&[&sticker_factory.pubkey().to_bytes()[..32]], &id());
+    let artist_key = |index: u64| {
+        Pubkey::create_with_seed(
+            &base,
+            &format!("{:?}{}", index, processor::Processor::ARTIST_SEED),
+            &id(),
+        )
+        .unwrap()
+    };
+
+    let approved_user = Keypair::new();
+    let rejected_user = Keypair::new();
+    for user in [&approved_user, &rejected_user].iter() {
+        create_account(
+            &mut program_context,
+            user,
+            artist_rent * 2,
+            0,
+            &solana_program::system_program::id(),
+        )
+        .await
+        .unwrap();
+    }
+
+    let artist_token_acc = Keypair::new();
+    create_token_account(
+        &mut program_context,
+        &artist_token_acc,
+        token_account_rent,
+        &spl_token::native_mint::id(),
+        &approved_user.pubkey(),
+    )
+    .await
+    .unwrap();
+
+    let artist_data = instruction::RegisterArtist {
+        name: [1; 32],
+        signature: [2; 256],
+        description: [3; 256],
+    };
+
+    apply_as_artist(
+        &mut program_context,
+        &approved_user,
+        &artist_token_acc.pubkey(),
+        &artist_key(0),
+        &sticker_factory.pubkey(),
+        &base,
+        artist_data.clone(),
+    )
+    .await
+    .unwrap();
+
+    let artist_acc_data = get_account(&mut program_context, &artist_key(0)).await;
+    let artist_acc = state::Artist::try_from_slice(&artist_acc_data.data.as_slice()).unwrap();
+    assert_eq!(artist_acc.status, state::ArtistStatus::Pending);
+    assert_eq!(artist_acc.user, approved_user.pubkey());
+
+    assert!(approve_artist(
+        &mut program_context,
+        &artist_key(0),
+        &approved_user,
+        &sticker_factory.pubkey(),
+        0,
+    )
+    .await
+    .is_err());
+
+    approve_artist(
+        &mut program_context,
+        &artist_key(0),
+        &sticker_factory_owner,
+        &sticker_factory.pubkey(),
+        0,
+    )
+    .await
+    .unwrap();
+
+    let artist_acc_data = get_account(&mut program_context, &artist_key(0)).await;
+    let artist_acc = state::Artist::try_from_slice(&artist_acc_data.data.as_slice()).unwrap();
+    assert_eq!(artist_acc.status, state::ArtistStatus::Approved);
+
+    apply_as_artist(
+        &mut program_context,
+        &rejected_user,
+        &artist_token_acc.pubkey(),
+        &artist_key(1),
+        &sticker_factory.pubkey(),
+        &base,
+        artist_data,
+    )
+    .await
+    .unwrap();
+
+    let rejected_user_data = get_account(&mut program_context, &rejected_user.pubkey()).await;
+    let rejected_user_balance_before = rejected_user_data.lamports;
+
+    reject_artist(
+        &mut program_context,
+        &artist_key(1),
+        &rejected_user.pubkey(),
+        &sticker_factory_owner,
+        &sticker_factory.pubkey(),
+        1,
+    )
+    .await
+    .unwrap();
+
+    let rejected_artist = program_context
+        .banks_client
+        .get_account(artist_key(1))
+        .await
+        .unwrap();
+    assert!(rejected_artist.is_none());
+
+    let rejected_user_data = get_account(&mut program_context, &rejected_user.pubkey()).await;
+    assert_eq!(
+        rejected_user_data.lamports,
+        rejected_user_balance_before + artist_rent
+    );
+
+    let sticker_factory_data = get_account(&mut program_context, &sticker_factory.pubkey()).await;
+    let sticker_factory_acc =
+        state::StickerFactory::try_from_slice(&sticker_factory_data.data.as_slice()).unwrap();
+    assert_eq!(sticker_factory_acc.artist_count, 2);
+}
+
+#[tokio::test]
+async fn test_update_artist_instruction() {
+    let mut program_context = program_test().start_with_context().await;
+
+    let sticker_factory = Keypair::new();
+    let sticker_factory_owner = Keypair::new();
+
+    let rent = program_context.banks_client.get_rent().await.unwrap();
+    let sticker_factory_min_rent = rent.minimum_balance(state::StickerFactory::LEN);
+    let token_account_rent = rent.minimum_balance(spl_token::state::Account::LEN);
+
+    create_account(
+        &mut program_context,
+        &sticker_factory,
+        sticker_factory_min_rent,
+        state::StickerFactory::LEN as u64,
+        &id(),
+    )
+    .await
+    .unwrap();
+
+    create_sticker_factory(
+        &mut program_context,
+        &sticker_factory,
+        &sticker_factory_owner,
+    )
+    .await
+    .unwrap();
+
+    let (base, _) =
+        Pubkey::find_program_address(&[&sticker_factory.pubkey().to_bytes()[..32]], &id());
+    let artist_key = Pubkey::create_with_seed(
+        &base,
+        &format!("{:?}{}", 0, processor::Processor::ARTIST_SEED),
+        &id(),
+    )
+    .unwrap();
+    create_program_account(
+        &mut program_context,
+        &sticker_factory.pubkey(),
+        &base,
+        &artist_key,
+        instruction::AddressType::Artist,
+    )
+    .await
+    .unwrap();
+
+    let artist_user = Keypair::new();
+    let artist_token_acc = Keypair::new();
+    let new_artist_token_acc = Keypair::new();
+    for token_acc in [&artist_token_acc, &new_artist_token_acc].iter() {
+        create_token_account(
+            &mut program_context,
+            token_acc,
+            token_account_rent,
+            &spl_token::native_mint::id(),
+            &artist_user.pubkey(),
+        )
+        .await
+        .unwrap();
+    }
+
+    register_new_artist(
+        &mut program_context,
+        &artist_user.pubkey(),
+        &artist_token_acc.pubkey(),
+        &artist_key,
+        &sticker_factory_owner,
+        &sticker_factory.pubkey(),
+        instruction::RegisterArtist {
+            name: [1; 32],
+            signature: [2; 256],
+            description: [3; 256],
+        },
+    )
+    .await
+    .unwrap();
+
+    let artist_data = instruction::UpdateArtist {
+        name: [4; 32],
+        signature: [5; 256],
+        description: [6; 256],
+    };
+
+    assert!(update_artist(
+        &mut program_context,
+        &artist_key,
+        &sticker_factory_owner,
+        &new_artist_token_acc.pubkey(),
+        artist_data.clone(),
+    )
+    .await
+    .is_err());
+
+    assert!(update_artist(
+        &mut program_context,
+        &artist_key,
+        &artist_user,
+        &sticker_factory.pubkey(),
+        artist_data.clone(),
+    )
+    .await
+    .is_err());
+
+    // stickers are paid with wrapped SOL
+    let mint = Keypair::new();
+    create_mint(
+        &mut program_context,
+        &mint,
+        rent.minimum_balance(spl_token::state::Mint::LEN),
+        &Keypair::new().pubkey(),
+    )
+    .await
+    .unwrap();
+    let other_mint_token_acc = Keypair::new();
+    create_token_account(
         &mut program_context,
-        &sticker_factory.pubkey(),
-        &base,
-        &address_to_create,
-        instruction::AddressType::Artist,
+        &other_mint_token_acc,
+        token_account_rent,
+        &mint.pubkey(),
+        &artist_user.pubkey(),
     )
     .await
     .unwrap();
+    assert_eq!(
+        update_artist(
+            &mut program_context,
+            &artist_key,
+            &artist_user,
+            &other_mint_token_acc.pubkey(),
+            artist_data.clone(),
+        )
+        .await
+        .map_err(program_error),
+        Err(Some(error::StickerProgramError::WrongTokenMint))
+    );
 
-    let artist_user = Keypair::new();
-    let artist_token_acc = Keypair::new();
-    let owner = Keypair::new();
+    update_artist(
+        &mut program_context,
+        &artist_key,
+        &artist_user,
+        &new_artist_token_acc.pubkey(),
+        artist_data.clone(),
+    )
+    .await
+    .unwrap();
+
+    let artist_acc_data = get_account(&mut program_context, &artist_key).await;
+    let artist_acc = state::Artist::try_from_slice(&artist_acc_data.data.as_slice()).unwrap();
+
+    assert_eq!(artist_acc.user, artist_user.pubkey());
+    assert_eq!(artist_acc.user_token_acc, new_artist_token_acc.pubkey());
+    assert_eq!(artist_acc.name, artist_data.name);
+    assert_eq!(artist_acc.signature, artist_data.signature);
+    assert_eq!(artist_acc.description, artist_data.description);
+}
+
+#[tokio::test]
+async fn test_buy_sticker_with_spl_mint() {
+    let mut program_context = program_test().start_with_context().await;
+
+    let rent = program_context.banks_client.get_rent().await.unwrap();
+    let mint_rent = rent.minimum_balance(spl_token::state::Mint::LEN);
+    let token_account_rent = rent.minimum_balance(spl_token::state::Account::LEN);
+    let nft_token_acc_rent = rent.minimum_balance(spl_nft_erc_721::state::Token::LEN as usize);
+    let nft_token_data_acc_rent =
+        rent.minimum_balance(spl_nft_erc_721::state::TokenData::LEN as usize);
+
+    let payment_mint = Keypair::new();
+    let payment_mint_authority = Keypair::new();
+    create_mint(
+        &mut program_context,
+        &payment_mint,
+        mint_rent,
+        &payment_mint_authority.pubkey(),
+    )
+    .await
+    .unwrap();
+
+    let sticker_price = 100;
+    let setup = setup_sticker(
+        &mut program_context,
+        instruction::CreateNewSticker {
+            max_supply: 1000,
+            price: sticker_price,
+            uri: [5; 256],
+            symbol: [7; 8],
+            name: [6; 32],
+            payment_mint: payment_mint.pubkey(),
+            royalty_bps: 0,
+        },
+    )
+    .await;
 
+    let user_owner = Keypair::new();
+    let user_token_acc = Keypair::new();
     create_token_account(
         &mut program_context,
-        &artist_token_acc,
+        &user_token_acc,
         token_account_rent,
-        &spl_token::native_mint::id(),
-        &owner.pubkey(),
+        &payment_mint.pubkey(),
+        &user_owner.pubkey(),
+    )
+    .await
+    .unwrap();
+    mint_tokens(
+        &mut program_context,
+        &payment_mint.pubkey(),
+        &user_token_acc.pubkey(),
+        &payment_mint_authority,
+        sticker_price,
     )
     .await
     .unwrap();
 
-    let artist_data = instruction::RegisterArtist {
-        name: [1; 32],
-        signature: [2; 256],
-        description: [3; 256],
-    };
+    let native_token_acc = Keypair::new();
+    create_token_account(
+        &mut program_context,
+        &native_token_acc,
+        token_account_rent + sticker_price,
+        &spl_token::native_mint::id(),
+        &user_owner.pubkey(),
+    )
+    .await
+    .unwrap();
 
-    let sticker_factory_data = get_account(&mut program_context, &sticker_factory.pubkey()).await;
-    let sticker_factory_acc =
-        state::StickerFactory::try_from_slice(&sticker_factory_data.data.as_slice()).unwrap();
-    let artist_index_before = sticker_factory_acc.artist_count;
+    assert!(buy_sticker(
+        &mut program_context,
+        &setup.sticker_key,
+        &setup.artist_key,
+        &setup.artist_token_acc.pubkey(),
+        &native_token_acc.pubkey(),
+        &user_owner,
+        &setup.mint_auth,
+        &Keypair::new(),
+        nft_token_acc_rent,
+        nft_token_data_acc_rent,
+        &setup.nft_mint.pubkey(),
+        &user_owner.pubkey(),
+        &setup.sticker_factory.pubkey(),
+        &setup.artist_token_acc.pubkey(),
+    )
+    .await
+    .is_err());
 
-    register_new_artist(
+    buy_sticker(
         &mut program_context,
-        &artist_user.pubkey(),
-        &artist_token_acc.pubkey(),
-        &address_to_create,
-        &sticker_factory_owner,
-        &sticker_factory.pubkey(),
-        artist_data,
+        &setup.sticker_key,
+        &setup.artist_key,
+        &setup.artist_token_acc.pubkey(),
+        &user_token_acc.pubkey(),
+        &user_owner,
+        &setup.mint_auth,
+        &Keypair::new(),
+        nft_token_acc_rent,
+        nft_token_data_acc_rent,
+        &setup.nft_mint.pubkey(),
+        &user_owner.pubkey(),
+        &setup.sticker_factory.pubkey(),
+        &setup.artist_token_acc.pubkey(),
     )
     .await
     .unwrap();
 
-    let artist_acc_data = get_account(&mut program_context, &address_to_create).await;
-    let artist_acc = state::Artist::try_from_slice(&artist_acc_data.data.as_slice()).unwrap();
-
-    let sticker_factory_data = get_account(&mut program_context, &sticker_factory.pubkey()).await;
-    let sticker_factory_acc =
-        state::StickerFactory::try_from_slice(&sticker_factory_data.data.as_slice()).unwrap();
+    assert_eq!(
+        get_token_balance(&mut program_context, &user_token_acc.pubkey()).await,
+        0
+    );
+    assert_eq!(
+        get_token_balance(&mut program_context, &setup.artist_token_acc.pubkey()).await,
+        sticker_price
+    );
 
-    assert!(artist_acc.is_initialized());
-    assert_eq!(artist_acc.user, artist_user.pubkey());
-    assert_eq!(artist_acc.user_token_acc, artist_token_acc.pubkey());
-    assert_eq!(sticker_factory_acc.artist_count, artist_index_before + 1);
+    let sticker_account_data = get_account(&mut program_context, &setup.sticker_key).await;
+    let sticker_acc =
+        state::Sticker::try_from_slice(&sticker_account_data.data.as_slice()).unwrap();
+    assert_eq!(sticker_acc.supply, 1);
 }
 
 #[tokio::test]
-async fn test_create_new_sticker_instruction() {
+async fn test_buy_sticker_with_fee() {
     let mut program_context = program_test().start_with_context().await;
 
-    let sticker_factory = Keypair::new();
-    let sticker_factory_owner = Keypair::new();
-
     let rent = program_context.banks_client.get_rent().await.unwrap();
-    let sticker_factory_min_rent = rent.minimum_balance(state::StickerFactory::LEN);
+    let mint_rent = rent.minimum_balance(spl_token::state::Mint::LEN);
     let token_account_rent = rent.minimum_balance(spl_token::state::Account::LEN);
-    let nft_mint_rent = rent.minimum_balance(spl_nft_erc_721::state::Mint::LEN as usize);
+    let nft_token_acc_rent = rent.minimum_balance(spl_nft_erc_721::state::Token::LEN as usize);
+    let nft_token_data_acc_rent =
+        rent.minimum_balance(spl_nft_erc_721::state::TokenData::LEN as usize);
 
-    create_account(
+    let payment_mint = Keypair::new();
+    let payment_mint_authority = Keypair::new();
+    create_mint(
         &mut program_context,
-        &sticker_factory,
-        sticker_factory_min_rent,
-        state::StickerFactory::LEN as u64,
-        &id(),
+        &payment_mint,
+        mint_rent,
+        &payment_mint_authority.pubkey(),
     )
     .await
     .unwrap();
 
-    create_sticker_factory(
+    let sticker_price = 999;
+    let setup = setup_sticker(
         &mut program_context,
-        &sticker_factory,
-        &sticker_factory_owner,
+        instruction::CreateNewSticker {
+            max_supply: 1000,
+            price: sticker_price,
+            uri: [5; 256],
+            symbol: [7; 8],
+            name: [6; 32],
+            payment_mint: payment_mint.pubkey(),
+            royalty_bps: 0,
+        },
+    )
+    .await;
+
+    let treasury = Keypair::new();
+    let treasury_token_acc = Keypair::new();
+    create_token_account(
+        &mut program_context,
+        &treasury_token_acc,
+        token_account_rent,
+        &payment_mint.pubkey(),
+        &treasury.pubkey(),
     )
     .await
     .unwrap();
 
-    let factory_info_data = get_account(&mut program_context, &sticker_factory.pubkey()).await;
-    let factory_info =
-        state::StickerFactory::try_from_slice(&factory_info_data.data.as_slice()).unwrap();
-
-    let (base, _) =
-        Pubkey::find_program_address(&[&sticker_factory.pubkey().to_bytes()[..32]], &id());
-    let artist_key = Pubkey::create_with_seed(
-        &base,
-        &format!(
-            "{:?}{}",
-            factory_info.artist_count,
-            processor::Processor::ARTIST_SEED
-        ),
-        &id(),
+    assert!(set_factory_fee(
+        &mut program_context,
+        &setup.sticker_factory.pubkey(),
+        &setup.sticker_factory_owner,
+        &treasury.pubkey(),
+        state::StickerFactory::MAX_FEE_BPS + 1,
     )
-    .unwrap();
-    create_program_account(
+    .await
+    .is_err());
+
+    set_factory_fee(
         &mut program_context,
-        &sticker_factory.pubkey(),
-        &base,
-        &artist_key,
-        instruction::AddressType::Artist,
+        &setup.sticker_factory.pubkey(),
+        &setup.sticker_factory_owner,
+        &treasury.pubkey(),
+        250,
     )
     .await
     .unwrap();
 
-    let artist_user = Keypair::new();
-    let artist_token_acc = Keypair::new();
-    let owner = Keypair::new();
-
+    let user_owner = Keypair::new();
+    let user_token_acc = Keypair::new();
     create_token_account(
         &mut program_context,
-        &artist_token_acc,
+        &user_token_acc,
         token_account_rent,
-        &spl_token::native_mint::id(),
-        &owner.pubkey(),
+        &payment_mint.pubkey(),
+        &user_owner.pubkey(),
+    )
+    .await
+    .unwrap();
+    mint_tokens(
+        &mut program_context,
+        &payment_mint.pubkey(),
+        &user_token_acc.pubkey(),
+        &payment_mint_authority,
+        sticker_price,
     )
     .await
     .unwrap();
 
-    let artist_data = instruction::RegisterArtist {
-        name: [1; 32],
-        signature: [2; 256],
-        description: [3; 256],
-    };
-    register_new_artist(
+    assert!(buy_sticker(
         &mut program_context,
-        &artist_user.pubkey(),
-        &artist_token_acc.pubkey(),
-        &artist_key,
-        &sticker_factory_owner,
-        &sticker_factory.pubkey(),
-        artist_data,
+        &setup.sticker_key,
+        &setup.artist_key,
+        &setup.artist_token_acc.pubkey(),
+        &user_token_acc.pubkey(),
+        &user_owner,
+        &setup.mint_auth,
+        &Keypair::new(),
+        nft_token_acc_rent,
+        nft_token_data_acc_rent,
+        &setup.nft_mint.pubkey(),
+        &user_owner.pubkey(),
+        &setup.sticker_factory.pubkey(),
+        &setup.artist_token_acc.pubkey(),
+    )
+    .await
+    .is_err());
+
+    // artist of other program can not return payment to buyer
+    let artist_data = get_account(&mut program_context, &setup.artist_key).await;
+    let mut forged_artist = state::Artist::try_from_slice(&artist_data.data.as_slice()).unwrap();
+    forged_artist.user_token_acc = user_token_acc.pubkey();
+    let forged_artist_key =
+        forge_account(&mut program_context, forged_artist.try_to_vec().unwrap());
+    assert!(buy_sticker(
+        &mut program_context,
+        &setup.sticker_key,
+        &forged_artist_key,
+        &user_token_acc.pubkey(),
+        &user_token_acc.pubkey(),
+        &user_owner,
+        &setup.mint_auth,
+        &Keypair::new(),
+        nft_token_acc_rent,
+        nft_token_data_acc_rent,
+        &setup.nft_mint.pubkey(),
+        &user_owner.pubkey(),
+        &setup.sticker_factory.pubkey(),
+        &treasury_token_acc.pubkey(),
+    )
+    .await
+    .is_err());
+
+    buy_sticker(
+        &mut program_context,
+        &setup.sticker_key,
+        &setup.artist_key,
+        &setup.artist_token_acc.pubkey(),
+        &user_token_acc.pubkey(),
+        &user_owner,
+        &setup.mint_auth,
+        &Keypair::new(),
+        nft_token_acc_rent,
+        nft_token_data_acc_rent,
+        &setup.nft_mint.pubkey(),
+        &user_owner.pubkey(),
+        &setup.sticker_factory.pubkey(),
+        &treasury_token_acc.pubkey(),
     )
     .await
     .unwrap();
 
-    let (base, _) =
-        Pubkey::find_program_address(&[&sticker_factory.pubkey().to_bytes()[..32]], &id());
-    let sticker_key = Pubkey::create_with_seed(
-        &base,
-        &format!(
-            "{:?}{}",
-            factory_info.sticker_count,
-            processor::Processor::STICKER_SEED
-        ),
-        &id(),
+    // 2.5% of 999 is 24.975, fee is rounded down
+    assert_eq!(
+        get_token_balance(&mut program_context, &treasury_token_acc.pubkey()).await,
+        24
+    );
+    assert_eq!(
+        get_token_balance(&mut program_context, &setup.artist_token_acc.pubkey()).await,
+        975
+    );
+    assert_eq!(
+        get_token_balance(&mut program_context, &user_token_acc.pubkey()).await,
+        0
+    );
+}
+
+#[tokio::test]
+async fn test_resale_with_royalty() {
+    let mut program_context = program_test().start_with_context().await;
+
+    let rent = program_context.banks_client.get_rent().await.unwrap();
+    let mint_rent = rent.minimum_balance(spl_token::state::Mint::LEN);
+    let token_account_rent = rent.minimum_balance(spl_token::state::Account::LEN);
+    let listing_rent = rent.minimum_balance(state::Listing::LEN);
+    let nft_token_acc_rent = rent.minimum_balance(spl_nft_erc_721::state::Token::LEN as usize);
+    let nft_token_data_acc_rent =
+        rent.minimum_balance(spl_nft_erc_721::state::TokenData::LEN as usize);
+
+    let payment_mint = Keypair::new();
+    let payment_mint_authority = Keypair::new();
+    create_mint(
+        &mut program_context,
+        &payment_mint,
+        mint_rent,
+        &payment_mint_authority.pubkey(),
     )
+    .await
     .unwrap();
-    create_program_account(
+
+    let sticker_price = 100;
+    let resale_price = 1000;
+    let setup = setup_sticker(
         &mut program_context,
-        &sticker_factory.pubkey(),
-        &base,
-        &sticker_key,
-        instruction::AddressType::Sticker,
+        instruction::CreateNewSticker {
+            max_supply: 1000,
+            price: sticker_price,
+            uri: [5; 256],
+            symbol: [7; 8],
+            name: [6; 32],
+            payment_mint: payment_mint.pubkey(),
+            royalty_bps: 500,
+        },
+    )
+    .await;
+
+    let seller = Keypair::new();
+    let seller_token_acc = Keypair::new();
+    let buyer = Keypair::new();
+    let buyer_token_acc = Keypair::new();
+    for (token_acc, owner, amount) in [
+        (&seller_token_acc, &seller, sticker_price),
+        (&buyer_token_acc, &buyer, resale_price),
+    ]
+    .iter()
+    {
+        create_token_account(
+            &mut program_context,
+            token_acc,
+            token_account_rent,
+            &payment_mint.pubkey(),
+            &owner.pubkey(),
+        )
+        .await
+        .unwrap();
+        mint_tokens(
+            &mut program_context,
+            &payment_mint.pubkey(),
+            &token_acc.pubkey(),
+            &payment_mint_authority,
+            *amount,
+        )
+        .await
+        .unwrap();
+    }
+
+    let nft_token = Keypair::new();
+    buy_sticker(
+        &mut program_context,
+        &setup.sticker_key,
+        &setup.artist_key,
+        &setup.artist_token_acc.pubkey(),
+        &seller_token_acc.pubkey(),
+        &seller,
+        &setup.mint_auth,
+        &nft_token,
+        nft_token_acc_rent,
+        nft_token_data_acc_rent,
+        &setup.nft_mint.pubkey(),
+        &seller.pubkey(),
+        &setup.sticker_factory.pubkey(),
+        &setup.artist_token_acc.pubkey(),
+    )
+    .await
+    .unwrap();
+
+    let canceled_listing = Keypair::new();
+    let canceled_listing_authority = list_sticker(
+        &mut program_context,
+        &canceled_listing,
+        listing_rent,
+        &setup.sticker_key,
+        &nft_token.pubkey(),
+        &seller,
+        &seller_token_acc.pubkey(),
+        resale_price,
     )
     .await
     .unwrap();
 
-    let nft_mint = Keypair::new();
+    // token approved to other listing can not be listed
+    assert_eq!(
+        list_sticker(
+            &mut program_context,
+            &Keypair::new(),
+            listing_rent,
+            &setup.sticker_key,
+            &nft_token.pubkey(),
+            &seller,
+            &seller_token_acc.pubkey(),
+            resale_price,
+        )
+        .await
+        .map_err(program_error),
+        Err(Some(error::StickerProgramError::NftTokenDelegated))
+    );
 
-    // create nft mint account
-    create_account(
+    cancel_listing(
         &mut program_context,
-        &nft_mint,
-        nft_mint_rent,
-        spl_nft_erc_721::state::Mint::LEN,
-        &spl_nft_erc_721::id(),
+        &canceled_listing.pubkey(),
+        &seller,
+        &nft_token.pubkey(),
+        &canceled_listing_authority,
     )
     .await
     .unwrap();
 
-    let (mint_auth, _) = Pubkey::find_program_address(&[&sticker_key.to_bytes()[..32]], &id());
+    let nft_token_data = get_account(&mut program_context, &nft_token.pubkey()).await;
+    let nft_token_acc =
+        spl_nft_erc_721::state::Token::deserialize(&mut &nft_token_data.data.as_slice()[..])
+            .unwrap();
+    assert_eq!(nft_token_acc.approval, Some(seller.pubkey()));
 
-    let sticker_data = instruction::CreateNewSticker {
-        max_supply: 1000,
-        price: 100,
-        uri: [5; 256],
-        symbol: [7; 8],
-        name: [6; 32],
-    };
+    let closed_listing = program_context
+        .banks_client
+        .get_account(canceled_listing.pubkey())
+        .await
+        .unwrap();
+    assert!(closed_listing.is_none());
+
+    // sticker of other program can not waive royalty
+    let sticker_data = get_account(&mut program_context, &setup.sticker_key).await;
+    let mut forged_sticker = state::Sticker::try_from_slice(&sticker_data.data.as_slice()).unwrap();
+    forged_sticker.royalty_bps = 0;
+    let forged_sticker_key =
+        forge_account(&mut program_context, forged_sticker.try_to_vec().unwrap());
+    assert!(list_sticker(
+        &mut program_context,
+        &Keypair::new(),
+        listing_rent,
+        &forged_sticker_key,
+        &nft_token.pubkey(),
+        &seller,
+        &seller_token_acc.pubkey(),
+        resale_price,
+    )
+    .await
+    .is_err());
 
-    let sticker_factory_data = get_account(&mut program_context, &sticker_factory.pubkey()).await;
-    let sticker_factory_acc =
-        state::StickerFactory::try_from_slice(&sticker_factory_data.data.as_slice()).unwrap();
-    let sticker_index_before = sticker_factory_acc.sticker_count;
+    let listing = Keypair::new();
+    assert!(list_sticker(
+        &mut program_context,
+        &listing,
+        listing_rent,
+        &setup.sticker_key,
+        &nft_token.pubkey(),
+        &buyer,
+        &buyer_token_acc.pubkey(),
+        resale_price,
+    )
+    .await
+    .is_err());
 
-    create_new_sticker(
+    let listing_authority = list_sticker(
         &mut program_context,
-        &sticker_key,
-        &sticker_factory.pubkey(),
-        &nft_mint.pubkey(),
-        &artist_key,
-        &artist_user,
-        &mint_auth,
-        sticker_data.clone(),
+        &listing,
+        listing_rent,
+        &setup.sticker_key,
+        &nft_token.pubkey(),
+        &seller,
+        &seller_token_acc.pubkey(),
+        resale_price,
     )
     .await
     .unwrap();
 
-    let sticker_acc_data = get_account(&mut program_context, &sticker_key).await;
-    let sticker_acc = state::Sticker::try_from_slice(&sticker_acc_data.data.as_slice()).unwrap();
+    // artist of other program can not redirect royalty to buyer
+    let artist_data = get_account(&mut program_context, &setup.artist_key).await;
+    let mut forged_artist = state::Artist::try_from_slice(&artist_data.data.as_slice()).unwrap();
+    forged_artist.user_token_acc = buyer_token_acc.pubkey();
+    let forged_artist_key =
+        forge_account(&mut program_context, forged_artist.try_to_vec().unwrap());
+    assert!(buy_listing(
+        &mut program_context,
+        &listing.pubkey(),
+        &setup.sticker_key,
+        &forged_artist_key,
+        &buyer_token_acc.pubkey(),
+        &seller.pubkey(),
+        &seller_token_acc.pubkey(),
+        &buyer_token_acc.pubkey(),
+        &buyer,
+        &nft_token.pubkey(),
+        &buyer.pubkey(),
+        &listing_authority,
+    )
+    .await
+    .is_err());
 
-    assert!(sticker_acc.is_initialized());
-    assert_eq!(sticker_acc.creator, artist_user.pubkey());
-    assert_eq!(sticker_acc.supply, 0);
-    assert_eq!(sticker_acc.max_supply, sticker_data.max_supply);
-    assert_eq!(sticker_acc.price, sticker_data.price);
-    assert_eq!(sticker_acc.mint, nft_mint.pubkey());
-    assert_eq!(sticker_acc.uri, sticker_data.uri);
+    buy_listing(
+        &mut program_context,
+        &listing.pubkey(),
+        &setup.sticker_key,
+        &setup.artist_key,
+        &setup.artist_token_acc.pubkey(),
+        &seller.pubkey(),
+        &seller_token_acc.pubkey(),
+        &buyer_token_acc.pubkey(),
+        &buyer,
+        &nft_token.pubkey(),
+        &buyer.pubkey(),
+        &listing_authority,
+    )
+    .await
+    .unwrap();
 
-    let sticker_factory_data = get_account(&mut program_context, &sticker_factory.pubkey()).await;
-    let sticker_factory_acc =
-        state::StickerFactory::try_from_slice(&sticker_factory_data.data.as_slice()).unwrap();
+    let nft_token_data = get_account(&mut program_context, &nft_token.pubkey()).await;
+    let nft_token_acc =
+        spl_nft_erc_721::state::Token::deserialize(&mut &nft_token_data.data.as_slice()[..])
+            .unwrap();
+    assert_eq!(nft_token_acc.owner, buyer.pubkey());
+    assert_eq!(nft_token_acc.approval, None);
 
-    assert_eq!(sticker_factory_acc.sticker_count, sticker_index_before + 1);
+    let closed_listing = program_context
+        .banks_client
+        .get_account(listing.pubkey())
+        .await
+        .unwrap();
+    assert!(closed_listing.is_none());
+
+    assert_eq!(
+        get_token_balance(&mut program_context, &setup.artist_token_acc.pubkey()).await,
+        sticker_price + 50
+    );
+    assert_eq!(
+        get_token_balance(&mut program_context, &seller_token_acc.pubkey()).await,
+        950
+    );
+    assert_eq!(
+        get_token_balance(&mut program_context, &buyer_token_acc.pubkey()).await,
+        0
+    );
 }
 
 #[tokio::test]
-async fn test_buy_sticker_instruction() {
+async fn test_buy_sticker_pack() {
     let mut program_context = program_test().start_with_context().await;
 
-    let sticker_factory = Keypair::new();
-    let sticker_factory_owner = Keypair::new();
-
     let rent = program_context.banks_client.get_rent().await.unwrap();
-    let sticker_factory_min_rent = rent.minimum_balance(state::StickerFactory::LEN);
     let token_account_rent = rent.minimum_balance(spl_token::state::Account::LEN);
+    let sticker_pack_rent = rent.minimum_balance(state::StickerPack::LEN);
     let nft_mint_rent = rent.minimum_balance(spl_nft_erc_721::state::Mint::LEN as usize);
     let nft_token_acc_rent = rent.minimum_balance(spl_nft_erc_721::state::Token::LEN as usize);
     let nft_token_data_acc_rent =
         rent.minimum_balance(spl_nft_erc_721::state::TokenData::LEN as usize);
 
-    create_account(
+    let setup = setup_sticker(
         &mut program_context,
-        &sticker_factory,
-        sticker_factory_min_rent,
-        state::StickerFactory::LEN as u64,
+        instruction::CreateNewSticker {
+            max_supply: 1000,
+            price: 100,
+            uri: [5; 256],
+            symbol: [7; 8],
+            name: [6; 32],
+            payment_mint: spl_token::native_mint::id(),
+            royalty_bps: 0,
+        },
+    )
+    .await;
+
+    // second sticker of same artist, only one can be minted
+    let (base, _) =
+        Pubkey::find_program_address(&[&setup.sticker_factory.pubkey().to_bytes()[..32]], &id());
+    let second_sticker_key = Pubkey::create_with_seed(
+        &base,
+        &format!("{:?}{}", 1, processor::Processor::STICKER_SEED),
         &id(),
     )
+    .unwrap();
+    create_program_account(
+        &mut program_context,
+        &setup.sticker_factory.pubkey(),
+        &base,
+        &second_sticker_key,
+        instruction::AddressType::Sticker,
+    )
     .await
     .unwrap();
 
-    create_sticker_factory(
+    let second_nft_mint = Keypair::new();
+    create_account(
         &mut program_context,
-        &sticker_factory,
-        &sticker_factory_owner,
+        &second_nft_mint,
+        nft_mint_rent,
+        spl_nft_erc_721::state::Mint::LEN,
+        &spl_nft_erc_721::id(),
     )
     .await
     .unwrap();
 
-    let factory_info_data = get_account(&mut program_context, &sticker_factory.pubkey()).await;
-    let factory_info =
-        state::StickerFactory::try_from_slice(&factory_info_data.data.as_slice()).unwrap();
-
-    let (base, _) =
-        Pubkey::find_program_address(&[&sticker_factory.pubkey().to_bytes()[..32]], &id());
-    let artist_key = Pubkey::create_with_seed(
-        &base,
-        &format!(
-            "{:?}{}",
-            factory_info.artist_count,
-            processor::Processor::ARTIST_SEED
-        ),
-        &id(),
+    let (second_mint_auth, _) =
+        Pubkey::find_program_address(&[&second_sticker_key.to_bytes()[..32]], &id());
+    create_new_sticker(
+        &mut program_context,
+        &second_sticker_key,
+        &setup.sticker_factory.pubkey(),
+        &second_nft_mint.pubkey(),
+        &setup.artist_key,
+        &setup.artist_user,
+        &second_mint_auth,
+        instruction::CreateNewSticker {
+            max_supply: 1,
+            price: 100,
+            uri: [8; 256],
+            symbol: [7; 8],
+            name: [9; 32],
+            payment_mint: spl_token::native_mint::id(),
+            royalty_bps: 0,
+        },
     )
+    .await
     .unwrap();
-    create_program_account(
+
+    let pack_price = 150;
+    let sticker_pack = Keypair::new();
+    let stickers = [setup.sticker_key, second_sticker_key];
+
+    assert!(create_sticker_pack(
         &mut program_context,
-        &sticker_factory.pubkey(),
-        &base,
-        &artist_key,
-        instruction::AddressType::Artist,
+        &sticker_pack,
+        sticker_pack_rent,
+        &setup.sticker_factory_owner,
+        &stickers,
+        pack_price,
     )
     .await
-    .unwrap();
+    .is_err());
 
-    let artist_user = Keypair::new();
-    let artist_token_acc = Keypair::new();
-    let owner = Keypair::new();
+    create_sticker_pack(
+        &mut program_context,
+        &sticker_pack,
+        sticker_pack_rent,
+        &setup.artist_user,
+        &stickers,
+        pack_price,
+    )
+    .await
+    .unwrap();
 
+    let user_owner = Keypair::new();
+    let user_token_acc = Keypair::new();
     create_token_account(
         &mut program_context,
-        &artist_token_acc,
-        token_account_rent,
+        &user_token_acc,
+        token_account_rent + pack_price * 2,
         &spl_token::native_mint::id(),
-        &owner.pubkey(),
+        &user_owner.pubkey(),
     )
     .await
     .unwrap();
 
-    let artist_data = instruction::RegisterArtist {
-        name: [1; 32],
-        signature: [2; 256],
-        description: [3; 256],
-    };
-    register_new_artist(
+    let artist_token_account_data =
+        get_account(&mut program_context, &setup.artist_token_acc.pubkey()).await;
+    let artist_token_acc_balance_before = artist_token_account_data.lamports;
+
+    // accounts of other program can not lower price or return payment to buyer
+    let sticker_pack_data = get_account(&mut program_context, &sticker_pack.pubkey()).await;
+    let mut forged_sticker_pack =
+        state::StickerPack::try_from_slice(&sticker_pack_data.data.as_slice()).unwrap();
+    forged_sticker_pack.price = 1;
+    let forged_sticker_pack_key = forge_account(
         &mut program_context,
-        &artist_user.pubkey(),
-        &artist_token_acc.pubkey(),
-        &artist_key,
-        &sticker_factory_owner,
-        &sticker_factory.pubkey(),
-        artist_data,
+        forged_sticker_pack.try_to_vec().unwrap(),
+    );
+    let artist_data = get_account(&mut program_context, &setup.artist_key).await;
+    let mut forged_artist = state::Artist::try_from_slice(&artist_data.data.as_slice()).unwrap();
+    forged_artist.user_token_acc = user_token_acc.pubkey();
+    let forged_artist_key =
+        forge_account(&mut program_context, forged_artist.try_to_vec().unwrap());
+
+    for (attempt, (sticker_pack_key, artist_key, artist_token_acc, succeeds)) in [
+        (
+            forged_sticker_pack_key,
+            setup.artist_key,
+            setup.artist_token_acc.pubkey(),
+            false,
+        ),
+        (
+            sticker_pack.pubkey(),
+            forged_artist_key,
+            user_token_acc.pubkey(),
+            false,
+        ),
+        (
+            sticker_pack.pubkey(),
+            setup.artist_key,
+            setup.artist_token_acc.pubkey(),
+            true,
+        ),
+        (
+            sticker_pack.pubkey(),
+            setup.artist_key,
+            setup.artist_token_acc.pubkey(),
+            false,
+        ),
+    ]
+    .iter()
+    .enumerate()
+    {
+        let mut pack_stickers = Vec::new();
+        for (sticker, mint_authority, nft_token_mint) in [
+            (setup.sticker_key, setup.mint_auth, setup.nft_mint.pubkey()),
+            (
+                second_sticker_key,
+                second_mint_auth,
+                second_nft_mint.pubkey(),
+            ),
+        ]
+        .iter()
+        {
+            // NFT token accounts are created in separate transaction to fit transaction size
+            let nft_token = Keypair::new();
+            let nft_token_data = create_nft_token_accounts(
+                &mut program_context,
+                &nft_token,
+                nft_token_acc_rent,
+                nft_token_data_acc_rent,
+            )
+            .await
+            .unwrap();
+            pack_stickers.push(instruction::PackStickerAccounts {
+                sticker: *sticker,
+                mint_authority: *mint_authority,
+                nft_token: nft_token.pubkey(),
+                nft_token_data,
+                nft_token_mint: *nft_token_mint,
+            });
+        }
+
+        let result = buy_sticker_pack(
+            &mut program_context,
+            sticker_pack_key,
+            artist_key,
+            artist_token_acc,
+            &user_token_acc.pubkey(),
+            &user_owner,
+            &user_owner.pubkey(),
+            &setup.sticker_factory.pubkey(),
+            // no fee is set, treasury is not used
+            &setup.artist_token_acc.pubkey(),
+            &pack_stickers,
+        )
+        .await;
+        assert_eq!(result.is_ok(), *succeeds, "attempt {}", attempt);
+
+        if *succeeds {
+            for pack_sticker in pack_stickers.iter() {
+                let nft_token_data =
+                    get_account(&mut program_context, &pack_sticker.nft_token).await;
+                let nft_token = spl_nft_erc_721::state::Token::deserialize(
+                    &mut &nft_token_data.data.as_slice()[..],
+                )
+                .unwrap();
+                assert_eq!(nft_token.mint, pack_sticker.nft_token_mint);
+                assert_eq!(nft_token.owner, user_owner.pubkey());
+            }
+        }
+    }
+
+    for sticker_key in stickers.iter() {
+        let sticker_account_data = get_account(&mut program_context, sticker_key).await;
+        let sticker_acc =
+            state::Sticker::try_from_slice(&sticker_account_data.data.as_slice()).unwrap();
+        assert_eq!(sticker_acc.supply, 1);
+    }
+
+    let artist_token_account_data =
+        get_account(&mut program_context, &setup.artist_token_acc.pubkey()).await;
+    assert_eq!(
+        artist_token_account_data.lamports,
+        artist_token_acc_balance_before + pack_price
+    );
+}
+
+#[tokio::test]
+async fn test_sticker_sale_schedule() {
+    let mut program_context = program_test().start_with_context().await;
+
+    let rent = program_context.banks_client.get_rent().await.unwrap();
+    let mint_rent = rent.minimum_balance(spl_token::state::Mint::LEN);
+    let token_account_rent = rent.minimum_balance(spl_token::state::Account::LEN);
+    let nft_token_acc_rent = rent.minimum_balance(spl_nft_erc_721::state::Token::LEN as usize);
+    let nft_token_data_acc_rent =
+        rent.minimum_balance(spl_nft_erc_721::state::TokenData::LEN as usize);
+
+    let payment_mint = Keypair::new();
+    let payment_mint_authority = Keypair::new();
+    create_mint(
+        &mut program_context,
+        &payment_mint,
+        mint_rent,
+        &payment_mint_authority.pubkey(),
     )
     .await
     .unwrap();
 
-    let (base, _) =
-        Pubkey::find_program_address(&[&sticker_factory.pubkey().to_bytes()[..32]], &id());
-    let sticker_key = Pubkey::create_with_seed(
-        &base,
-        &format!(
-            "{:?}{}",
-            factory_info.sticker_count,
-            processor::Processor::STICKER_SEED
-        ),
-        &id(),
+    let sticker_price = 100;
+    let early_bird_price = 40;
+    let setup = setup_sticker(
+        &mut program_context,
+        instruction::CreateNewSticker {
+            max_supply: 1000,
+            price: sticker_price,
+            uri: [5; 256],
+            symbol: [7; 8],
+            name: [6; 32],
+            payment_mint: payment_mint.pubkey(),
+            royalty_bps: 0,
+        },
     )
-    .unwrap();
-    create_program_account(
+    .await;
+
+    let user_owner = Keypair::new();
+    let user_token_acc = Keypair::new();
+    create_token_account(
         &mut program_context,
-        &sticker_factory.pubkey(),
-        &base,
-        &sticker_key,
-        instruction::AddressType::Sticker,
+        &user_token_acc,
+        token_account_rent,
+        &payment_mint.pubkey(),
+        &user_owner.pubkey(),
     )
     .await
     .unwrap();
-
-    let nft_mint = Keypair::new();
-
-    // create nft mint account
-    create_account(
+    mint_tokens(
         &mut program_context,
-        &nft_mint,
-        nft_mint_rent,
-        spl_nft_erc_721::state::Mint::LEN,
-        &spl_nft_erc_721::id(),
+        &payment_mint.pubkey(),
+        &user_token_acc.pubkey(),
+        &payment_mint_authority,
+        sticker_price,
     )
     .await
     .unwrap();
 
-    let (mint_auth, _) = Pubkey::find_program_address(&[&sticker_key.to_bytes()[..32]], &id());
+    for sale in [
+        state::SaleSchedule {
+            start: 10,
+            end: 5,
+            ..state::SaleSchedule::default()
+        },
+        // early bird without price
+        state::SaleSchedule {
+            early_bird_end: 5,
+            ..state::SaleSchedule::default()
+        },
+        // early bird ends before sale without end starts
+        state::SaleSchedule {
+            start: 10,
+            early_bird_price: 1,
+            early_bird_end: 5,
+            ..state::SaleSchedule::default()
+        },
+    ]
+    .iter()
+    {
+        assert_eq!(
+            set_sticker_sale(
+                &mut program_context,
+                &setup.sticker_key,
+                &setup.artist_user,
+                *sale,
+            )
+            .await
+            .map_err(program_error),
+            Err(Some(error::StickerProgramError::InvalidSaleSchedule))
+        );
+    }
+
+    // timestamps are far from test clock in both directions
+    let cases = [
+        (
+            state::SaleSchedule {
+                start: i64::MAX - 1,
+                end: i64::MAX,
+                ..state::SaleSchedule::default()
+            },
+            Err(Some(error::StickerProgramError::SaleNotStarted)),
+        ),
+        (
+            state::SaleSchedule {
+                start: 1,
+                end: 2,
+                ..state::SaleSchedule::default()
+            },
+            Err(Some(error::StickerProgramError::SaleEnded)),
+        ),
+        (
+            state::SaleSchedule {
+                start: 1,
+                end: i64::MAX,
+                early_bird_price,
+                early_bird_end: i64::MAX,
+            },
+            Ok(()),
+        ),
+    ];
 
-    let sticker_price = 100;
-    let url = url::Url::parse("ipfs://bafybeiemxf5abjwjbikoz4mc3a3dla6ual3jsgpdr4cjr3oz3evfyavhwq/wiki/Vincent_van_Gogh.html").unwrap();
-    let mut uri = [0; 256];
-    let url_bytes = url.as_str().as_bytes();
-    let (left, _) = uri.split_at_mut(url_bytes.len());
-    left.copy_from_slice(url_bytes);
+    for (sale, expected) in cases.iter() {
+        set_sticker_sale(
+            &mut program_context,
+            &setup.sticker_key,
+            &setup.artist_user,
+            *sale,
+        )
+        .await
+        .unwrap();
 
-    let sticker_data = instruction::CreateNewSticker {
-        max_supply: 1000,
-        price: sticker_price,
-        uri,
-        symbol: [7; 8],
-        name: [6; 32],
-    };
+        let result = buy_sticker(
+            &mut program_context,
+            &setup.sticker_key,
+            &setup.artist_key,
+            &setup.artist_token_acc.pubkey(),
+            &user_token_acc.pubkey(),
+            &user_owner,
+            &setup.mint_auth,
+            &Keypair::new(),
+            nft_token_acc_rent,
+            nft_token_data_acc_rent,
+            &setup.nft_mint.pubkey(),
+            &user_owner.pubkey(),
+            &setup.sticker_factory.pubkey(),
+            &setup.artist_token_acc.pubkey(),
+        )
+        .await;
+        assert_eq!(result.map(|_| ()).map_err(program_error), *expected);
+    }
 
-    create_new_sticker(
-        &mut program_context,
-        &sticker_key,
-        &sticker_factory.pubkey(),
-        &nft_mint.pubkey(),
-        &artist_key,
-        &artist_user,
-        &mint_auth,
-        sticker_data,
+    assert_eq!(
+        get_token_balance(&mut program_context, &setup.artist_token_acc.pubkey()).await,
+        early_bird_price
+    );
+    assert_eq!(
+        get_token_balance(&mut program_context, &user_token_acc.pubkey()).await,
+        sticker_price - early_bird_price
+    );
+}
+
+#[tokio::test]
+async fn test_pause_resume_retire_sticker() {
+    let mut program_context = program_test().start_with_context().await;
+
+    let rent = program_context.banks_client.get_rent().await.unwrap();
+    let mint_rent = rent.minimum_balance(spl_token::state::Mint::LEN);
+    let token_account_rent = rent.minimum_balance(spl_token::state::Account::LEN);
+    let nft_token_acc_rent = rent.minimum_balance(spl_nft_erc_721::state::Token::LEN as usize);
+    let nft_token_data_acc_rent =
+        rent.minimum_balance(spl_nft_erc_721::state::TokenData::LEN as usize);
+
+    let payment_mint = Keypair::new();
+    let payment_mint_authority = Keypair::new();
+    create_mint(
+        &mut program_context,
+        &payment_mint,
+        mint_rent,
+        &payment_mint_authority.pubkey(),
     )
     .await
     .unwrap();
 
-    let user_token_acc = Keypair::new();
-    let user_owner = Keypair::new();
+    let sticker_price = 100;
+    let setup = setup_sticker(
+        &mut program_context,
+        instruction::CreateNewSticker {
+            max_supply: 1000,
+            price: sticker_price,
+            uri: [5; 256],
+            symbol: [7; 8],
+            name: [6; 32],
+            payment_mint: payment_mint.pubkey(),
+            royalty_bps: 0,
+        },
+    )
+    .await;
 
+    let user_owner = Keypair::new();
+    let user_token_acc = Keypair::new();
     create_token_account(
         &mut program_context,
         &user_token_acc,
-        token_account_rent + sticker_price,
-        &spl_token::native_mint::id(),
+        token_account_rent,
+        &payment_mint.pubkey(),
         &user_owner.pubkey(),
     )
     .await
     .unwrap();
-
-    let user_nft_token_acc = Keypair::new();
-    let user_nft_token_owner = Keypair::new();
-
-    let user_token_account_data = get_account(&mut program_context, &user_token_acc.pubkey()).await;
-    let user_token_acc_balance_before = user_token_account_data.lamports;
-
-    let artist_token_account_data =
-        get_account(&mut program_context, &artist_token_acc.pubkey()).await;
-    let artist_token_acc_balance_before = artist_token_account_data.lamports;
-
-    let sticker_account_data = get_account(&mut program_context, &sticker_key).await;
-    let sticker_acc =
-        state::Sticker::try_from_slice(&sticker_account_data.data.as_slice()).unwrap();
-    let sticker_supply_before = sticker_acc.supply;
-
-    let token_data = buy_sticker(
+    mint_tokens(
         &mut program_context,
-        &sticker_key,
-        &artist_key,
-        &artist_token_acc.pubkey(),
+        &payment_mint.pubkey(),
         &user_token_acc.pubkey(),
-        &user_owner,
-        &mint_auth,
-        &user_nft_token_acc,
-        nft_token_acc_rent,
-        nft_token_data_acc_rent,
-        &nft_mint.pubkey(),
-        &user_nft_token_owner.pubkey(),
+        &payment_mint_authority,
+        sticker_price,
     )
     .await
     .unwrap();
 
-    let user_nft_token_account_data =
-        get_account(&mut program_context, &user_nft_token_acc.pubkey()).await;
-    let user_nft_token_account = spl_nft_erc_721::state::Token::deserialize(
-        &mut &user_nft_token_account_data.data.as_slice()[..],
-    )
-    .unwrap();
-
-    assert_eq!(user_nft_token_account.mint, nft_mint.pubkey());
-    assert_eq!(user_nft_token_account.owner, user_nft_token_owner.pubkey());
-
-    let user_nft_token_data_account = get_account(&mut program_context, &token_data).await;
-    let user_nft_token_data_account = spl_nft_erc_721::state::TokenData::deserialize(
-        &mut &user_nft_token_data_account.data.as_slice()[..],
+    let pause =
+        instruction::pause_sticker(&id(), &setup.sticker_key, &setup.artist_user.pubkey()).unwrap();
+    let resume =
+        instruction::resume_sticker(&id(), &setup.sticker_key, &setup.artist_user.pubkey())
+            .unwrap();
+    let retire =
+        instruction::retire_sticker(&id(), &setup.sticker_key, &setup.artist_user.pubkey())
+            .unwrap();
+
+    // only creator can change status
+    let not_creator = Keypair::new();
+    assert!(set_sticker_status(
+        &mut program_context,
+        instruction::pause_sticker(&id(), &setup.sticker_key, &not_creator.pubkey()).unwrap(),
+        &not_creator,
     )
-    .unwrap();
-
-    assert_eq!(
-        user_nft_token_data_account.token,
-        user_nft_token_acc.pubkey()
-    );
-    assert_eq!(
-        url_bytes,
-        &user_nft_token_data_account.uri[0..url_bytes.len()]
-    );
+    .await
+    .is_err());
+
+    let steps = [
+        (pause, Err(Some(error::StickerProgramError::StickerPaused))),
+        (resume, Ok(())),
+        (
+            retire,
+            Err(Some(error::StickerProgramError::StickerRetired)),
+        ),
+    ];
 
-    let user_token_account_data = get_account(&mut program_context, &user_token_acc.pubkey()).await;
-    let artist_token_account_data =
-        get_account(&mut program_context, &artist_token_acc.pubkey()).await;
-    let sticker_account_data = get_account(&mut program_context, &sticker_key).await;
-    let sticker_acc =
-        state::Sticker::try_from_slice(&sticker_account_data.data.as_slice()).unwrap();
+    for (status_instruction, expected) in steps.iter() {
+        set_sticker_status(
+            &mut program_context,
+            status_instruction.clone(),
+            &setup.artist_user,
+        )
+        .await
+        .unwrap();
 
+        let result = buy_sticker(
+            &mut program_context,
+            &setup.sticker_key,
+            &setup.artist_key,
+            &setup.artist_token_acc.pubkey(),
+            &user_token_acc.pubkey(),
+            &user_owner,
+            &setup.mint_auth,
+            &Keypair::new(),
+            nft_token_acc_rent,
+            nft_token_data_acc_rent,
+            &setup.nft_mint.pubkey(),
+            &user_owner.pubkey(),
+            &setup.sticker_factory.pubkey(),
+            &setup.artist_token_acc.pubkey(),
+        )
+        .await;
+        assert_eq!(result.map(|_| ()).map_err(program_error), *expected);
+    }
+
+    let sticker_account_data = get_account(&mut program_context, &setup.sticker_key).await;
+    let sticker = state::Sticker::try_from_slice(&sticker_account_data.data.as_slice()).unwrap();
+    assert_eq!(sticker.status, state::StickerStatus::Retired);
+    assert_eq!(sticker.supply, 1);
+    assert_eq!(sticker.max_supply, 1);
+
+    // retirement is permanent, new blockhash so resume is not a duplicate transaction
+    program_context.last_blockhash = program_context
+        .banks_client
+        .get_new_latest_blockhash(&program_context.last_blockhash)
+        .await
+        .unwrap();
+    let resume =
+        instruction::resume_sticker(&id(), &setup.sticker_key, &setup.artist_user.pubkey())
+            .unwrap();
     assert_eq!(
-        user_token_account_data.lamports,
-        user_token_acc_balance_before - sticker_price
-    );
-    assert_eq!(
-        artist_token_account_data.lamports,
-        artist_token_acc_balance_before + sticker_price
+        set_sticker_status(&mut program_context, resume, &setup.artist_user)
+            .await
+            .map_err(program_error),
+        Err(Some(error::StickerProgramError::StickerRetired))
     );
-    assert_eq!(sticker_acc.supply, sticker_supply_before + 1);
 }
 
 #[tokio::test]
-async fn test_change_sticker_price_instruction() {
+async fn test_airdrop_sticker() {
     let mut program_context = program_test().start_with_context().await;
 
-    let sticker_factory = Keypair::new();
-    let sticker_factory_owner = Keypair::new();
-
     let rent = program_context.banks_client.get_rent().await.unwrap();
-    let sticker_factory_min_rent = rent.minimum_balance(state::StickerFactory::LEN);
-    let token_account_rent = rent.minimum_balance(spl_token::state::Account::LEN);
-    let nft_mint_rent = rent.minimum_balance(spl_nft_erc_721::state::Mint::LEN as usize);
+    let mint_rent = rent.minimum_balance(spl_token::state::Mint::LEN);
+    let nft_token_acc_rent = rent.minimum_balance(spl_nft_erc_721::state::Token::LEN as usize);
+    let nft_token_data_acc_rent =
+        rent.minimum_balance(spl_nft_erc_721::state::TokenData::LEN as usize);
 
-    create_account(
+    let payment_mint = Keypair::new();
+    create_mint(
         &mut program_context,
-        &sticker_factory,
-        sticker_factory_min_rent,
-        state::StickerFactory::LEN as u64,
-        &id(),
+        &payment_mint,
+        mint_rent,
+        &Keypair::new().pubkey(),
     )
     .await
     .unwrap();
 
-    create_sticker_factory(
+    let max_supply = 2;
+    let setup = setup_sticker(
         &mut program_context,
-        &sticker_factory,
-        &sticker_factory_owner,
+        instruction::CreateNewSticker {
+            max_supply,
+            price: 100,
+            uri: [5; 256],
+            symbol: [7; 8],
+            name: [6; 32],
+            payment_mint: payment_mint.pubkey(),
+            royalty_bps: 0,
+        },
+    )
+    .await;
+
+    let mut recipients = Vec::new();
+    let mut owners = Vec::new();
+    for _ in 0..max_supply + 1 {
+        let owner = Keypair::new();
+        let nft_token = Keypair::new();
+        let nft_token_data = create_nft_token_accounts(
+            &mut program_context,
+            &nft_token,
+            nft_token_acc_rent,
+            nft_token_data_acc_rent,
+        )
+        .await
+        .unwrap();
+        recipients.push(instruction::AirdropAccounts {
+            owner: owner.pubkey(),
+            nft_token: nft_token.pubkey(),
+            nft_token_data,
+        });
+        owners.push(owner);
+    }
+
+    // only creator can airdrop
+    let not_creator = Keypair::new();
+    assert!(airdrop_sticker(
+        &mut program_context,
+        &setup.sticker_key,
+        &not_creator,
+        &setup.mint_auth,
+        &setup.nft_mint.pubkey(),
+        &recipients[..1],
     )
     .await
-    .unwrap();
-
-    let factory_info_data = get_account(&mut program_context, &sticker_factory.pubkey()).await;
-    let factory_info =
-        state::StickerFactory::try_from_slice(&factory_info_data.data.as_slice()).unwrap();
+    .is_err());
 
-    let (base, _) =
-        Pubkey::find_program_address(&[&sticker_factory.pubkey().to_bytes()[..32]], &id());
-    let artist_key = Pubkey::create_with_seed(
-        &base,
-        &format!(
-            "{:?}{}",
-            factory_info.artist_count,
-            processor::Processor::ARTIST_SEED
-        ),
-        &id(),
-    )
-    .unwrap();
-    create_program_account(
+    airdrop_sticker(
         &mut program_context,
-        &sticker_factory.pubkey(),
-        &base,
-        &artist_key,
-        instruction::AddressType::Artist,
+        &setup.sticker_key,
+        &setup.artist_user,
+        &setup.mint_auth,
+        &setup.nft_mint.pubkey(),
+        &recipients[..2],
     )
     .await
     .unwrap();
 
-    let artist_user = Keypair::new();
-    let artist_token_acc = Keypair::new();
-    let owner = Keypair::new();
+    for (recipient, owner) in recipients.iter().zip(owners.iter()).take(2) {
+        let nft_token_data = get_account(&mut program_context, &recipient.nft_token).await;
+        let nft_token =
+            spl_nft_erc_721::state::Token::deserialize(&mut &nft_token_data.data.as_slice()[..])
+                .unwrap();
+        assert_eq!(nft_token.mint, setup.nft_mint.pubkey());
+        assert_eq!(nft_token.owner, owner.pubkey());
+    }
 
-    create_token_account(
-        &mut program_context,
-        &artist_token_acc,
-        token_account_rent,
-        &spl_token::native_mint::id(),
-        &owner.pubkey(),
+    let sticker_account_data = get_account(&mut program_context, &setup.sticker_key).await;
+    let sticker = state::Sticker::try_from_slice(&sticker_account_data.data.as_slice()).unwrap();
+    assert_eq!(sticker.supply, max_supply);
+
+    // airdrop counts toward supply
+    assert_eq!(
+        airdrop_sticker(
+            &mut program_context,
+            &setup.sticker_key,
+            &setup.artist_user,
+            &setup.mint_auth,
+            &setup.nft_mint.pubkey(),
+            &recipients[2..],
+        )
+        .await
+        .map_err(program_error),
+        Err(Some(error::StickerProgramError::NoTokensToMint))
+    );
+}
+
+#[tokio::test]
+async fn test_migrate_account() {
+    let mut program_test = program_test();
+
+    // accounts of old layouts are prefixes of current ones
+    let mut add_v1_account = |key: Pubkey, data: Vec<u8>, len: usize| {
+        program_test.add_account(
+            key,
+            Account {
+                lamports: 1_000_000,
+                data: data[..len].to_vec(),
+                owner: id(),
+                ..Account::default()
+            },
+        )
+    };
+
+    let sticker_factory_key = Pubkey::new_unique();
+    let sticker_factory_owner = Pubkey::new_unique();
+    let sticker_factory_v1 = state::StickerFactory {
+        artist_count: 1,
+        sticker_count: 1,
+        owner: sticker_factory_owner,
+        ..state::StickerFactory::default()
+    };
+    add_v1_account(
+        sticker_factory_key,
+        sticker_factory_v1.try_to_vec().unwrap(),
+        state::StickerFactory::LEN_V1,
+    );
+
+    let (base, _) = Pubkey::find_program_address(&[&sticker_factory_key.to_bytes()[..32]], &id());
+    let artist_key = Pubkey::create_with_seed(
+        &base,
+        &format!("{:?}{}", 0, processor::Processor::ARTIST_SEED),
+        &id(),
     )
-    .await
     .unwrap();
-
-    let artist_data = instruction::RegisterArtist {
+    let artist_v1 = state::Artist {
+        user: Pubkey::new_unique(),
+        user_token_acc: Pubkey::new_unique(),
         name: [1; 32],
         signature: [2; 256],
         description: [3; 256],
+        status: state::ArtistStatus::Approved,
+        payment_mint: Pubkey::default(),
+        sticker_factory: Pubkey::default(),
     };
-    register_new_artist(
-        &mut program_context,
-        &artist_user.pubkey(),
-        &artist_token_acc.pubkey(),
-        &artist_key,
-        &sticker_factory_owner,
-        &sticker_factory.pubkey(),
-        artist_data,
-    )
-    .await
-    .unwrap();
+    add_v1_account(
+        artist_key,
+        artist_v1.try_to_vec().unwrap(),
+        state::Artist::LEN_V1,
+    );
 
-    let (base, _) =
-        Pubkey::find_program_address(&[&sticker_factory.pubkey().to_bytes()[..32]], &id());
     let sticker_key = Pubkey::create_with_seed(
         &base,
-        &format!(
-            "{:?}{}",
-            factory_info.sticker_count,
-            processor::Processor::STICKER_SEED
-        ),
+        &format!("{:?}{}", 0, processor::Processor::STICKER_SEED),
         &id(),
     )
     .unwrap();
-    create_program_account(
+    let sticker_v1 = state::Sticker {
+        creator: artist_v1.user,
+        supply: 1,
+        max_supply: 10,
+        price: 100,
+        mint: Pubkey::new_unique(),
+        uri: [4; 256],
+        ..state::Sticker::default()
+    };
+    add_v1_account(
+        sticker_key,
+        sticker_v1.try_to_vec().unwrap(),
+        state::Sticker::LEN_V1,
+    );
+
+    let mut program_context = program_test.start_with_context().await;
+
+    // sticker must be derived from given factory
+    assert!(migrate_account(
         &mut program_context,
-        &sticker_factory.pubkey(),
-        &base,
         &sticker_key,
-        instruction::AddressType::Sticker,
+        &sticker_factory_key,
+        instruction::MigrateAccountInput::Sticker(1),
     )
     .await
-    .unwrap();
-
-    let nft_mint = Keypair::new();
+    .is_err());
 
-    // create nft mint account
-    create_account(
+    migrate_account(
         &mut program_context,
-        &nft_mint,
-        nft_mint_rent,
-        spl_nft_erc_721::state::Mint::LEN,
-        &spl_nft_erc_721::id(),
+        &sticker_factory_key,
+        &sticker_factory_key,
+        instruction::MigrateAccountInput::StickerFactory,
     )
     .await
     .unwrap();
-
-    let (mint_auth, _) = Pubkey::find_program_address(&[&sticker_key.to_bytes()[..32]], &id());
-
-    let sticker_price = 100;
-    let url = url::Url::parse("ipfs://bafybeiemxf5abjwjbikoz4mc3a3dla6ual3jsgpdr4cjr3oz3evfyavhwq/wiki/Vincent_van_Gogh.html").unwrap();
-    let mut uri = [0; 256];
-    let url_bytes = url.as_str().as_bytes();
-    let (left, _) = uri.split_at_mut(url_bytes.len());
-    left.copy_from_slice(url_bytes);
-
-    let sticker_data = instruction::CreateNewSticker {
-        max_supply: 1000,
-        price: sticker_price,
-        uri,
-        symbol: [7; 8],
-        name: [6; 32],
-    };
-
-    create_new_sticker(
+    // artist must be derived from factory it is approved in
+    assert!(migrate_account(
         &mut program_context,
-        &sticker_key,
-        &sticker_factory.pubkey(),
-        &nft_mint.pubkey(),
         &artist_key,
-        &artist_user,
-        &mint_auth,
-        sticker_data,
+        &sticker_factory_key,
+        instruction::MigrateAccountInput::Artist(1),
+    )
+    .await
+    .is_err());
+    migrate_account(
+        &mut program_context,
+        &artist_key,
+        &sticker_factory_key,
+        instruction::MigrateAccountInput::Artist(0),
+    )
+    .await
+    .unwrap();
+    migrate_account(
+        &mut program_context,
+        &sticker_key,
+        &sticker_factory_key,
+        instruction::MigrateAccountInput::Sticker(0),
     )
     .await
     .unwrap();
 
-    let new_price = 777;
+    let sticker_factory_data = get_account(&mut program_context, &sticker_factory_key).await;
+    assert_eq!(sticker_factory_data.data.len(), state::StickerFactory::LEN);
+    let sticker_factory =
+        state::StickerFactory::try_from_slice(sticker_factory_data.data.as_slice()).unwrap();
+    assert_eq!(sticker_factory, sticker_factory_v1);
 
-    change_sticker_price(&mut program_context, &sticker_key, &artist_user, new_price)
-        .await
-        .unwrap();
+    let artist_data = get_account(&mut program_context, &artist_key).await;
+    let artist = state::Artist::try_from_slice(artist_data.data.as_slice()).unwrap();
+    assert_eq!(
+        artist,
+        state::Artist {
+            payment_mint: spl_token::native_mint::id(),
+            sticker_factory: sticker_factory_key,
+            ..artist_v1
+        }
+    );
+    assert_eq!(
+        state::Artist::default().status,
+        state::ArtistStatus::Approved
+    );
 
-    let sticker_account_data = get_account(&mut program_context, &sticker_key).await;
-    let sticker_acc =
-        state::Sticker::try_from_slice(&sticker_account_data.data.as_slice()).unwrap();
+    let sticker_data = get_account(&mut program_context, &sticker_key).await;
+    let sticker = state::Sticker::try_from_slice(sticker_data.data.as_slice()).unwrap();
+    assert_eq!(
+        sticker,
+        state::Sticker {
+            payment_mint: spl_token::native_mint::id(),
+            sticker_factory: sticker_factory_key,
+            status: state::StickerStatus::Active,
+            ..sticker_v1
+        }
+    );
 
-    assert_eq!(sticker_acc.price, new_price);
+    let rent = program_context.banks_client.get_rent().await.unwrap();
+    assert!(rent.is_exempt(sticker_data.lamports, state::Sticker::LEN));
+
+    program_context.last_blockhash = program_context
+        .banks_client
+        .get_new_latest_blockhash(&program_context.last_blockhash)
+        .await
+        .unwrap();
+    assert_eq!(
+        migrate_account(
+            &mut program_context,
+            &sticker_key,
+            &sticker_factory_key,
+            instruction::MigrateAccountInput::Sticker(0),
+        )
+        .await
+        .map_err(program_error),
+        Err(Some(error::StickerProgramError::AccountAlreadyMigrated))
+    );
 }